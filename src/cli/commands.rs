@@ -1,5 +1,6 @@
 use clap::Subcommand;
 use super::options::NewOptions;
+use crate::handlers::destroy::COMPONENT_KINDS;
 
 #[derive(Subcommand)]
 pub enum Commands {
@@ -21,6 +22,74 @@ pub enum Commands {
     /// 🧠 Create a use case to connect ports, services, and implementations
     Usecase {
         name: String,
+        #[arg(long = "port", help = "Port module in app/ports to inject (repeatable)")]
+        ports: Vec<String>,
+        #[arg(long = "service", help = "Service module in app/services to inject (repeatable)")]
+        services: Vec<String>,
+    },
+    /// ➕ Add tooling to an existing project
+    Add {
+        #[command(subcommand)]
+        item: AddItem,
+    },
+    /// 🏛️ Check the project against its architecture rules
+    Check {
+        #[command(subcommand)]
+        target: CheckTarget,
+    },
+    /// 🕸️ Export the import graph as Mermaid or Graphviz DOT
+    Graph {
+        #[arg(long, default_value = "mermaid", value_parser = ["mermaid", "dot"], help = "Output format")]
+        format: String,
+        #[arg(long, default_value = "layer", value_parser = ["layer", "module"], help = "Graph granularity")]
+        level: String,
+        #[arg(short, long, help = "Write to a file instead of stdout")]
+        out: Option<String>,
+    },
+    /// 📘 Generate the OpenAPI document from the sources, without running the app
+    Openapi {
+        #[arg(long, default_value = "json", value_parser = ["json", "yaml"], help = "Output format")]
+        format: String,
+        #[arg(short, long, help = "Write to a file instead of stdout")]
+        out: Option<String>,
+    },
+    /// 🛣️ List the API routes found in app/routes
+    Routes {
+        #[arg(long, help = "Print the routes as JSON")]
+        json: bool,
+    },
+    /// 🔀 Compare API versions
+    Api {
+        #[command(subcommand)]
+        command: ApiCommand,
+    },
+    /// 📥 Generate code from an existing API description
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+    /// 📤 Export the routes as ready-to-send requests
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
+    },
+    /// 🧩 Generate a typed client SDK for the routes
+    Client {
+        #[command(subcommand)]
+        language: ClientLanguage,
+    },
+    /// 🗄️ Manage Alembic revisions without a Python environment
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+    /// 🔍 Overview of the project: features, layers, tests, dependencies and env vars
+    Inspect,
+    /// 🗑️ Remove a generated component, its test and its registrations
+    Destroy {
+        #[arg(value_parser = COMPONENT_KINDS, help = "Component type")]
+        kind: String,
+        name: String,
     },
 }
 
@@ -33,6 +102,19 @@ pub enum MakeItem {
     },
     Model {
         name: String,
+        #[arg(long = "from-json", help = "JSON sample to infer the fields from (repeatable)")]
+        from_json: Vec<String>,
+    },
+    /// Model, repository port, adapter and CRUD router on the project's database
+    Resource {
+        #[arg(help = "Resource name in plural, e.g. products")]
+        name: String,
+        #[arg(short, long = "field", help = "Field as name:type, '?' after the type when optional, e.g. 'notes:str?' (repeatable)")]
+        fields: Vec<String>,
+        #[arg(long, value_parser = ["sql", "mongo"], help = "Store to use when the project has both SQL and MongoDB")]
+        store: Option<String>,
+        #[arg(long, help = "Overwrite files that already exist")]
+        force: bool,
     },
     Service {
         name: String,
@@ -40,4 +122,146 @@ pub enum MakeItem {
     Core {
         name: String,
     },
+    /// Abstract port (ABC) in app/ports
+    #[command(alias = "ports")]
+    Port {
+        name: String,
+        #[arg(short, long = "method", help = "Abstract method, e.g. 'get_user(id:int)->User' (repeatable)")]
+        methods: Vec<String>,
+    },
+    /// Adapter implementing a port in app/infrastructure
+    #[command(alias = "infrastructure")]
+    Adapter {
+        name: String,
+        #[arg(short, long, help = "Port module in app/ports to implement")]
+        port: String,
+    },
+    /// In-memory fake of a port in tests/fakes, with a pytest fixture
+    Fake {
+        #[arg(help = "Port module in app/ports to fake")]
+        port: String,
+    },
+    /// Contract test suite shared by every adapter of a port
+    Contract {
+        #[arg(help = "Port module in app/ports")]
+        port: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CheckTarget {
+    /// Report imports that break the layer rules in faspi.toml
+    Architecture {
+        #[arg(long, default_value = "text", value_parser = ["text", "json"], help = "Output format")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ApiCommand {
+    /// Classify changes between two OpenAPI documents, exiting with 1 on breaking ones
+    Diff {
+        #[arg(help = "Spec file, project directory, git revision or 'revision:spec.json'")]
+        old: String,
+        #[arg(help = "Same as OLD, defaults to the working tree")]
+        new: Option<String>,
+        #[arg(long, default_value = "text", value_parser = ["text", "json"], help = "Output format")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ExportTarget {
+    /// REST Client `.http` file (VS Code, JetBrains)
+    Http {
+        #[arg(short, long, help = "Write to a file instead of stdout")]
+        out: Option<String>,
+        #[arg(long, default_value = "http://localhost:8000", help = "Base URL of the running API")]
+        base_url: String,
+    },
+    /// Postman v2.1 collection, one folder per tag
+    Postman {
+        #[arg(short, long, help = "Write to a file instead of stdout")]
+        out: Option<String>,
+        #[arg(long, default_value = "http://localhost:8000", help = "Base URL of the running API")]
+        base_url: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ClientLanguage {
+    /// TypeScript interfaces and a fetch-based client
+    Ts {
+        #[arg(short, long, default_value = "sdk", help = "Directory to write the SDK to")]
+        out: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommand {
+    /// Create an empty revision on top of the current head
+    Revision {
+        #[arg(short, long, help = "Revision message")]
+        message: String,
+        #[arg(long, help = "Join every head into a merge revision")]
+        merge: bool,
+    },
+    /// Show the current head revisions
+    Heads,
+    /// List the revisions from the heads down to the base
+    History,
+}
+
+#[derive(Subcommand)]
+pub enum ImportSource {
+    /// Models, routers, ports and use cases from an OpenAPI 3 spec (JSON or YAML)
+    Openapi {
+        spec: String,
+        #[arg(long, default_value = "schemas", help = "Module in app/models for the component schemas")]
+        models: String,
+        #[arg(long, help = "Overwrite files that already exist")]
+        force: bool,
+    },
+    /// Routers, models and route tests reproducing the requests of a Postman collection
+    Postman {
+        collection: String,
+        #[arg(long, default_value = "schemas", help = "Module in app/models for the inferred body models")]
+        models: String,
+        #[arg(long, help = "Overwrite files that already exist")]
+        force: bool,
+    },
+    /// Routers, models and route tests reproducing the API calls of a HAR capture
+    Har {
+        capture: String,
+        #[arg(long, default_value = "schemas", help = "Module in app/models for the inferred body models")]
+        models: String,
+        #[arg(long, help = "Overwrite files that already exist")]
+        force: bool,
+    },
+    /// SQLAlchemy models and Pydantic schemas from an existing database
+    Db {
+        #[arg(long, help = "Path to the SQLite database file")]
+        sqlite: String,
+        #[arg(long, help = "Also generate a repository port, SQL adapter and CRUD router per table")]
+        crud: bool,
+        #[arg(long, help = "Overwrite files that already exist")]
+        force: bool,
+    },
+    /// SQLAlchemy models and Pydantic schemas from CREATE/ALTER TABLE statements
+    Ddl {
+        #[arg(help = "SQL file with the schema, e.g. a pg_dump --schema-only output")]
+        file: String,
+        #[arg(long, help = "Also generate a repository port, SQL adapter and CRUD router per table")]
+        crud: bool,
+        #[arg(long, help = "Overwrite files that already exist")]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AddItem {
+    /// import-linter contracts (.importlinter) mirroring the architecture rules
+    Lint,
+    /// Unit of Work port with its SQLAlchemy adapter and in-memory fake (SQL projects)
+    Uow,
 }
@@ -3,6 +3,6 @@ pub mod parser;
 pub mod options;
 pub mod update_checker;
 
-pub use commands::{Commands, MakeItem};
+pub use commands::{AddItem, ApiCommand, CheckTarget, ClientLanguage, Commands, DbCommand, ExportTarget, ImportSource, MakeItem};
 pub use parser::Cli;
 pub use options::NewOptions;
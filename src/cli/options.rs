@@ -1,9 +1,14 @@
 use clap::Args;
+use crate::utils::config::{DatabaseConfig, DATABASE_BACKENDS};
 
 #[derive(Args)]
 pub struct NewOptions {
     #[arg(long, help = "Include SQLAlchemy ORM support")]
     pub sql: bool,
+    #[arg(long, value_parser = DATABASE_BACKENDS, help = "Database backend for SQLAlchemy, implies --sql [default: postgres]")]
+    pub db: Option<String>,
+    #[arg(long, help = "Use async SQLAlchemy sessions and drivers, implies --sql")]
+    pub async_db: bool,
     #[arg(long, help = "Include JWT authentication support")]
     pub auth: bool,
     #[arg(long, help = "Include CORS middleware")]
@@ -14,4 +19,34 @@ pub struct NewOptions {
     pub tasks: bool,
     #[arg(long, help = "Include Supabase integration")]
     pub supabase: bool,
+    #[arg(long, help = "Include MongoDB support with Motor and Beanie")]
+    pub mongo: bool,
+    #[arg(long, help = "Skip the import-linter contracts (.importlinter)")]
+    pub no_lint: bool,
+}
+
+impl NewOptions {
+    /// The database setup, `None` unless one of the SQL options is given.
+    pub fn database(&self) -> Option<DatabaseConfig> {
+        (self.sql || self.db.is_some() || self.async_db).then(|| DatabaseConfig {
+            backend: self.db.clone().unwrap_or_else(|| "postgres".to_string()),
+            is_async: self.async_db,
+        })
+    }
+
+    pub fn enabled_features(&self) -> Vec<String> {
+        [
+            ("sql", self.database().is_some()),
+            ("auth", self.auth),
+            ("cors", self.cors),
+            ("cache", self.cache),
+            ("tasks", self.tasks),
+            ("supabase", self.supabase),
+            ("mongo", self.mongo),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name.to_string())
+        .collect()
+    }
 }
@@ -0,0 +1,109 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde::Serialize;
+use crate::utils::openapi::build_document;
+use crate::utils::spec::Spec;
+use crate::utils::spec_diff::{diff, Change};
+
+#[derive(Serialize)]
+struct Report<'a> {
+    old: &'a str,
+    new: &'a str,
+    breaking: usize,
+    changes: &'a [Change],
+}
+
+fn git(args: &[&str]) -> Option<Vec<u8>> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output.status.success().then_some(output.stdout)
+}
+
+/// Directory deleted when dropped, so early returns clean up too.
+struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Generates the document of the project as it was at a git revision, by
+/// checking its `app` package out into a temporary directory.
+fn document_at(revision: &str) -> Result<Spec, Box<dyn Error>> {
+    let files = git(&["ls-tree", "-r", "--name-only", revision, "--", "app"])
+        .ok_or_else(|| format!("'{}' is neither a file nor a git revision", revision))?;
+    let files = String::from_utf8_lossy(&files).to_string();
+    if !files.lines().any(|f| f.ends_with(".py")) {
+        return Err(format!("No 'app' package found at revision '{}'", revision).into());
+    }
+
+    let sanitized: String = revision.chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect();
+    let checkout = TempDir(std::env::temp_dir().join(format!("faspi-api-{}-{}", std::process::id(), sanitized)));
+    let root = &checkout.0;
+    for file in files.lines().filter(|f| f.ends_with(".py")) {
+        let content = git(&["show", &format!("{}:./{}", revision, file)])
+            .ok_or_else(|| format!("Cannot read '{}' at revision '{}'", file, revision))?;
+        let path = root.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+    Ok(Spec { value: build_document(root).value })
+}
+
+/// Loads one side of the comparison: a spec file, a spec at a git revision
+/// (`main:openapi.json`), a project directory or a git revision of this project.
+fn load(source: &str) -> Result<Spec, Box<dyn Error>> {
+    let path = Path::new(source);
+    if path.is_file() {
+        return Spec::load(path);
+    }
+    if path.join("app").is_dir() {
+        return Ok(Spec { value: build_document(path).value });
+    }
+    if let Some((revision, file)) = source.split_once(':').filter(|(_, file)| !file.is_empty()) {
+        let content = git(&["show", &format!("{}:./{}", revision, file)])
+            .ok_or_else(|| format!("Cannot read '{}' at revision '{}'", file, revision))?;
+        return Spec::parse(&String::from_utf8_lossy(&content), file);
+    }
+    document_at(source)
+}
+
+/// Prints the changes between two API versions and returns how many are breaking.
+pub fn diff_api(old: &str, new: Option<&str>, format: &str) -> Result<usize, Box<dyn Error>> {
+    let new = new.unwrap_or(".");
+    let changes = diff(&load(old)?, &load(new)?);
+    let breaking = changes.iter().filter(|c| c.breaking).count();
+
+    if format == "json" {
+        let report = Report {
+            old,
+            new,
+            breaking,
+            changes: &changes,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(breaking);
+    }
+
+    println!("🔀 Comparing '{}' → '{}'", old, if new == "." { "working tree" } else { new });
+    for change in &changes {
+        println!(
+            "{} {} {}  {}",
+            if change.breaking { "❌ breaking" } else { "✅ compatible" },
+            change.method,
+            change.path,
+            change.message
+        );
+    }
+    let compatible = changes.len() - breaking;
+    if breaking == 0 {
+        println!("✅ No breaking changes ({} compatible change(s))", compatible);
+    } else {
+        println!("❌ {} breaking change(s), {} compatible change(s)", breaking, compatible);
+    }
+    Ok(breaking)
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::path::Path;
+use serde::Serialize;
+use crate::utils::config::Config;
+use crate::utils::imports::ModuleGraph;
+
+#[derive(Debug, Serialize)]
+pub struct Violation {
+    pub file: String,
+    pub line: usize,
+    pub module: String,
+    pub layer: String,
+    pub imports: String,
+    /// The forbidden layer or external package that was imported.
+    pub forbidden: String,
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    modules: usize,
+    imports: usize,
+    rules: usize,
+    violations: &'a [Violation],
+}
+
+pub fn scan_project(root: &Path, config: &Config) -> ModuleGraph {
+    let packages: BTreeSet<&str> = config
+        .layout
+        .values()
+        .filter_map(|module| module.split('.').next())
+        .collect();
+    ModuleGraph::scan(root, &packages.into_iter().collect::<Vec<_>>())
+}
+
+pub fn find_violations(root: &Path, config: &Config, graph: &ModuleGraph) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for edge in &graph.edges {
+        let Some(layer) = config.layer_of(&edge.from) else {
+            continue;
+        };
+        let path = &graph.modules[&edge.from];
+        let file = path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        if config.is_excluded(&file) {
+            continue;
+        }
+
+        let target = if graph.is_internal(&edge.to) {
+            config.layer_of(&edge.to).map(str::to_string)
+        } else {
+            edge.to.split('.').next().map(str::to_string)
+        };
+        let Some(target) = target.filter(|t| t != layer) else {
+            continue;
+        };
+
+        let forbidden = config
+            .architecture
+            .rules
+            .iter()
+            .filter(|rule| rule.layer == layer)
+            .any(|rule| rule.forbid.contains(&target));
+        if forbidden {
+            violations.push(Violation {
+                file,
+                line: edge.line,
+                module: edge.from.clone(),
+                layer: layer.to_string(),
+                imports: edge.to.clone(),
+                forbidden: target,
+            });
+        }
+    }
+    violations
+}
+
+/// Returns the number of violations found.
+pub fn check_architecture(format: &str) -> Result<usize, Box<dyn Error>> {
+    let root = Path::new(".");
+    let config = Config::load(root)?;
+    let graph = scan_project(root, &config);
+    if graph.modules.is_empty() {
+        return Err("No Python modules found, run this command from the project root".into());
+    }
+    let violations = find_violations(root, &config, &graph);
+
+    if format == "json" {
+        let report = Report {
+            modules: graph.modules.len(),
+            imports: graph.edges.len(),
+            rules: config.architecture.rules.len(),
+            violations: &violations,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(violations.len());
+    }
+
+    println!(
+        "🏛️  Checked {} modules and {} imports against {} rules",
+        graph.modules.len(),
+        graph.edges.len(),
+        config.architecture.rules.len()
+    );
+    for v in &violations {
+        println!(
+            "❌ {}:{}  {} → {} (imports '{}')",
+            v.file, v.line, v.layer, v.forbidden, v.imports
+        );
+    }
+    if violations.is_empty() {
+        println!("✅ No architecture violations found");
+    } else {
+        println!("❌ {} architecture violation(s) found", violations.len());
+    }
+    Ok(violations.len())
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use crate::utils::openapi::{build_document, operation_id};
+use crate::utils::routes::collect_endpoints;
+use crate::utils::spec::Spec;
+use crate::utils::typescript::{render_client, render_types, to_lower_camel_case, GENERATED_HEADER};
+
+/// Writes a typed TypeScript SDK for the routes: `types.ts` with the models,
+/// `client.ts` with a fetch-based client and `index.ts` re-exporting both.
+pub fn generate_ts_client(out: &str) -> Result<(), Box<dyn Error>> {
+    let root = Path::new(".");
+    if !root.join("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let document = build_document(root);
+    for warning in &document.warnings {
+        println!("⚠️  {}", warning);
+    }
+    // Methods are named after the handlers rather than FastAPI's long operation ids.
+    let names: BTreeMap<String, String> = collect_endpoints(root)
+        .iter()
+        .map(|endpoint| (operation_id(endpoint), to_lower_camel_case(&endpoint.handler)))
+        .collect();
+    let spec = Spec { value: document.value };
+    let operations = spec.operations().len();
+    if operations == 0 {
+        return Err("No routes found in app/routes".into());
+    }
+
+    let out = Path::new(out);
+    fs::create_dir_all(out)?;
+    let files = [
+        ("types.ts", render_types(&spec)),
+        ("client.ts", render_client(&spec, &names)),
+        ("index.ts", format!("{}\nexport * from \"./types\";\nexport * from \"./client\";\n", GENERATED_HEADER)),
+    ];
+    for (name, content) in &files {
+        fs::write(out.join(name), content)?;
+    }
+    println!(
+        "✅ TypeScript client with {} operation(s) and {} model(s) written to '{}'",
+        operations,
+        spec.schemas().len(),
+        out.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::utils::fs::python_files;
+use crate::utils::python::{class_methods, find_classes, find_port_class};
+use crate::utils::string::to_camel_case;
+use crate::utils::testing::add_fixture;
+
+struct Port {
+    module: String,
+    class: String,
+}
+
+struct Adapter {
+    module: String,
+    class: String,
+}
+
+struct Usecase {
+    module: String,
+    class: String,
+    params: Vec<(String, String)>,
+}
+
+fn module_name(path: &Path) -> String {
+    path.file_stem().unwrap().to_string_lossy().to_string()
+}
+
+fn scan_ports(app: &Path) -> Vec<Port> {
+    python_files(&app.join("ports"))
+        .into_iter()
+        .filter(|path| module_name(path) != "__init__")
+        .filter_map(|path| {
+            let class = find_port_class(&path)?;
+            Some(Port { module: module_name(&path), class })
+        })
+        .collect()
+}
+
+fn scan_adapters(app: &Path, ports: &[Port]) -> BTreeMap<String, Vec<Adapter>> {
+    let mut adapters: BTreeMap<String, Vec<Adapter>> = BTreeMap::new();
+    for path in python_files(&app.join("infrastructure")) {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for class in find_classes(&source) {
+            for port in ports.iter().filter(|p| class.bases.contains(&p.class)) {
+                adapters.entry(port.class.clone()).or_default().push(Adapter {
+                    module: module_name(&path),
+                    class: class.name.clone(),
+                });
+            }
+        }
+    }
+    adapters
+}
+
+fn scan_service_classes(app: &Path) -> BTreeMap<String, String> {
+    let mut classes = BTreeMap::new();
+    for path in python_files(&app.join("services")) {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for class in find_classes(&source) {
+            classes.insert(class.name, module_name(&path));
+        }
+    }
+    classes
+}
+
+fn scan_usecases(app: &Path) -> Vec<Usecase> {
+    python_files(&app.join("usecases"))
+        .into_iter()
+        .filter(|path| module_name(path) != "__init__")
+        .filter_map(|path| {
+            let source = fs::read_to_string(&path).ok()?;
+            let module = module_name(&path);
+            let expected = to_camel_case(&module);
+            let class = find_classes(&source).into_iter().find(|c| c.name == expected)?;
+            let params = class_methods(&source, &class.name)
+                .into_iter()
+                .find(|(_, m)| m.name == "__init__")
+                .map(|(_, m)| {
+                    m.params
+                        .into_iter()
+                        .filter(|p| p.default.is_none())
+                        .filter_map(|p| Some((p.name, p.annotation?)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(Usecase { module, class: class.name, params })
+        })
+        .collect()
+}
+
+/// Reads the `Port: Adapter` pairs from an existing `BINDINGS` dict so choices made
+/// by hand survive regeneration.
+fn existing_bindings(container: &Path) -> BTreeMap<String, String> {
+    let mut bindings = BTreeMap::new();
+    let Ok(source) = fs::read_to_string(container) else {
+        return bindings;
+    };
+    let mut inside = false;
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("BINDINGS") && trimmed.ends_with('{') {
+            inside = true;
+        } else if inside && trimmed.starts_with('}') {
+            break;
+        } else if let Some((port, adapter)) = trimmed.trim_end_matches(',').split_once(':').filter(|_| inside) {
+            bindings.insert(port.trim().to_string(), adapter.trim().to_string());
+        }
+    }
+    bindings
+}
+
+pub fn sync_container(root: &Path) {
+    let app = root.join("app");
+    let container = app.join("container.py");
+    let display = container.strip_prefix(".").unwrap_or(&container).display();
+    let ports = scan_ports(&app);
+    let adapters = scan_adapters(&app, &ports);
+    let usecases = scan_usecases(&app);
+    let services = scan_service_classes(&app);
+    let previous = existing_bindings(&container);
+
+    let mut imports = vec!["from fastapi import Depends".to_string(), String::new()];
+    let mut bindings = Vec::new();
+    let mut providers = String::new();
+
+    for port in &ports {
+        imports.push(format!("from app.ports.{} import {}", port.module, port.class));
+        let candidates = adapters.get(&port.class).map(Vec::as_slice).unwrap_or_default();
+        let bound = previous
+            .get(&port.class)
+            .and_then(|name| candidates.iter().find(|a| a.class == *name))
+            .or(candidates.first());
+
+        match bound {
+            Some(adapter) => {
+                imports.push(format!("from app.infrastructure.{} import {}", adapter.module, adapter.class));
+                bindings.push(format!("    {}: {},", port.class, adapter.class));
+                providers.push_str(&format!(
+                    r#"
+
+def get_{0}(adapter: {1} = Depends(BINDINGS[{1}])) -> {1}:
+    return adapter
+"#,
+                    port.module, port.class
+                ));
+            }
+            None => providers.push_str(&format!(
+                r#"
+
+def get_{0}() -> {1}:
+    raise NotImplementedError("No adapter bound to {1}, create one with 'faspi make adapter <name> --port {0}'")
+"#,
+                port.module, port.class
+            )),
+        }
+    }
+
+    for usecase in &usecases {
+        imports.push(format!("from app.usecases.{} import {}", usecase.module, usecase.class));
+        let mut params = Vec::new();
+        let mut args = Vec::new();
+        for (name, annotation) in &usecase.params {
+            if let Some(port) = ports.iter().find(|p| p.class == *annotation) {
+                params.push(format!("    {}: {} = Depends(get_{}),\n", name, annotation, port.module));
+                args.push(format!("{0}={0}", name));
+            } else if let Some(module) = services.get(annotation) {
+                let import = format!("from app.services.{} import {}", module, annotation);
+                if !imports.contains(&import) {
+                    imports.push(import);
+                }
+                params.push(format!("    {}: {} = Depends({}),\n", name, annotation, annotation));
+                args.push(format!("{0}={0}", name));
+            } else {
+                println!(
+                    "⚠️  '{}.{}' is not a port, wire it manually in '{}'",
+                    usecase.class,
+                    name,
+                    display
+                );
+            }
+        }
+        let signature = if params.is_empty() {
+            "()".to_string()
+        } else {
+            format!("(\n{})", params.concat())
+        };
+        providers.push_str(&format!(
+            r#"
+
+def get_{0}{1} -> {2}:
+    return {2}({3})
+"#,
+            usecase.module,
+            signature,
+            usecase.class,
+            args.join(", ")
+        ));
+    }
+
+    let provider_entries: String = ports
+        .iter()
+        .map(|p| format!("    {}: get_{},\n", p.class, p.module))
+        .collect();
+
+    let content = format!(
+        r#""""Dependency injection container: binds each port to its adapter.
+
+Generated by faspi and refreshed by `faspi make port`, `faspi make adapter` and
+`faspi usecase`. Pick another adapter by editing BINDINGS, other edits are lost.
+"""
+{imports}
+
+BINDINGS = {{
+{bindings}}}
+{providers}
+
+PROVIDERS = {{
+{provider_entries}}}
+
+
+def override_port(app, port, implementation):
+    """Serve `implementation` wherever `port` is injected, e.g. in tests."""
+    app.dependency_overrides[PROVIDERS[port]] = lambda: implementation
+"#,
+        imports = imports.join("\n"),
+        bindings = bindings.iter().map(|b| format!("{}\n", b)).collect::<String>(),
+        providers = providers,
+        provider_entries = provider_entries,
+    );
+
+    let previous_content = fs::read_to_string(&container).unwrap_or_default();
+    if previous_content != content {
+        fs::write(&container, content).expect("❌ Failed to write container");
+        println!("🔗 Updated '{}'", display);
+    }
+
+    add_fixture(root, "override_port", OVERRIDE_FIXTURE);
+}
+
+const OVERRIDE_FIXTURE: &str = r#"@pytest.fixture
+def override_port():
+    """Swap a port's adapter for the duration of a test."""
+    from app.main import app
+    from app.container import override_port as _override_port
+
+    yield lambda port, implementation: _override_port(app, port, implementation)
+    app.dependency_overrides.clear()
+"#;
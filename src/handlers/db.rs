@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::utils::migrations::{
+    children, heads, history, problems, read_revisions, render_revision, revision_id, slug, utc_now, versions_dir, Revision,
+};
+
+fn load() -> Result<(PathBuf, Vec<Revision>), Box<dyn Error>> {
+    let dir = versions_dir(Path::new(""));
+    if !dir.is_dir() {
+        return Err(format!(
+            "'{}' not found, create the project with --sql to get an Alembic environment",
+            dir.display()
+        )
+        .into());
+    }
+    let revisions = read_revisions(&dir);
+    Ok((dir, revisions))
+}
+
+fn ensure_consistent(revisions: &[Revision]) -> Result<(), Box<dyn Error>> {
+    let problems = problems(revisions);
+    if problems.is_empty() {
+        return Ok(());
+    }
+    for problem in &problems {
+        eprintln!("⚠️  {}", problem);
+    }
+    Err("The revision chain is broken, fix the revisions above first".into())
+}
+
+/// Creates an empty revision on top of the current head, or joining every
+/// head with `merge`.
+pub fn create_revision(message: &str, merge: bool) -> Result<(), Box<dyn Error>> {
+    let (dir, revisions) = load()?;
+    ensure_consistent(&revisions)?;
+    let heads = heads(&revisions);
+    let names: Vec<&str> = heads.iter().map(|h| h.id.as_str()).collect();
+    let down: Vec<String> = match (heads.len(), merge) {
+        (0, false) => Vec::new(),
+        (1, false) => vec![heads[0].id.clone()],
+        (_, false) => {
+            return Err(format!(
+                "Multiple heads ({}), join them first with `faspi db revision --merge -m \"merge heads\"`",
+                names.join(", ")
+            )
+            .into());
+        }
+        (0 | 1, true) => return Err("Nothing to merge, the history has a single head".into()),
+        (_, true) => heads.iter().map(|h| h.id.clone()).collect(),
+    };
+
+    let id = revision_id(&revisions);
+    let slug = slug(message);
+    let file = dir.join(if slug.is_empty() { format!("{}_.py", id) } else { format!("{}_{}.py", id, slug) });
+    fs::write(&file, render_revision(&id, &down, message.trim(), &utc_now()))?;
+    let revises = if down.is_empty() { "the base".to_string() } else { down.join(", ") };
+    println!("✅ Revision '{}' created at '{}' (revises {})", id, file.display(), revises);
+    Ok(())
+}
+
+fn describe(revision: &Revision, revisions: &[Revision]) -> String {
+    let mut markers = Vec::new();
+    let children = children(revisions, &revision.id);
+    if children.is_empty() {
+        markers.push(" (head)");
+    }
+    if revision.down.len() > 1 {
+        markers.push(" (mergepoint)");
+    }
+    if children.len() > 1 {
+        markers.push(" (branchpoint)");
+    }
+    if revision.message.is_empty() {
+        return format!("{}{}", revision.id, markers.concat());
+    }
+    format!("{}{}, {}", revision.id, markers.concat(), revision.message)
+}
+
+pub fn show_heads() -> Result<(), Box<dyn Error>> {
+    let (_, revisions) = load()?;
+    let heads = heads(&revisions);
+    if revisions.is_empty() {
+        println!("📭 No revisions yet, create one with `faspi db revision -m \"message\"`");
+        return Ok(());
+    }
+    for head in &heads {
+        println!("{}", describe(head, &revisions));
+    }
+    if heads.len() > 1 {
+        println!(
+            "⚠️  {} heads: the history has branches, join them with `faspi db revision --merge -m \"merge heads\"`",
+            heads.len()
+        );
+    }
+    for problem in problems(&revisions) {
+        println!("⚠️  {}", problem);
+    }
+    Ok(())
+}
+
+pub fn show_history() -> Result<(), Box<dyn Error>> {
+    let (_, revisions) = load()?;
+    if revisions.is_empty() {
+        println!("📭 No revisions yet, create one with `faspi db revision -m \"message\"`");
+        return Ok(());
+    }
+    for revision in history(&revisions) {
+        let parents = if revision.down.is_empty() { "<base>".to_string() } else { revision.down.join(", ") };
+        println!("{} -> {}", parents, describe(revision, &revisions));
+    }
+    for problem in problems(&revisions) {
+        println!("⚠️  {}", problem);
+    }
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use crate::handlers::container::sync_container;
+use crate::utils::fs::{python_files, remove_from_init};
+use crate::utils::python::{included_router, logical_lines};
+use crate::handlers::make::{fake_path, unregister_contract_adapter};
+use crate::utils::testing::{remove_fixture, test_dir, test_path};
+
+/// Component types `faspi destroy` removes.
+pub const COMPONENT_KINDS: [&str; 7] = ["route", "model", "service", "core", "usecase", "port", "adapter"];
+
+fn component_folder(kind: &str) -> Option<&'static str> {
+    match kind {
+        "route" => Some("routes"),
+        "model" => Some("models"),
+        "service" => Some("services"),
+        "core" => Some("core"),
+        "usecase" => Some("usecases"),
+        "port" => Some("ports"),
+        "adapter" => Some("infrastructure"),
+        _ => None,
+    }
+}
+
+pub fn destroy_component(kind: &str, name: &str) -> Result<(), Box<dyn Error>> {
+    let folder = component_folder(kind).ok_or_else(|| format!("Unknown component type: {}", kind))?;
+    let path = Path::new("app").join(folder).join(format!("{}.py", name));
+    if !path.exists() {
+        return Err(format!("{} '{}' not found at '{}'", kind, name, path.display()).into());
+    }
+
+    fs::remove_file(&path)?;
+    println!("🗑️  Removed '{}'", path.display());
+
+    let test = test_path(kind, name);
+    if test.exists() {
+        fs::remove_file(&test)?;
+        println!("🗑️  Removed '{}'", test.display());
+    }
+
+    remove_from_init(&format!("app/{}/__init__.py", folder), name);
+
+    if kind == "port" {
+        let fake = fake_path(name);
+        if fake.exists() {
+            fs::remove_file(&fake)?;
+            println!("🗑️  Removed '{}'", fake.display());
+        }
+        remove_fixture(Path::new("."), &format!("fake_{}", name));
+        let contract = test_path("contract", &format!("{}_contract", name));
+        if contract.exists() {
+            fs::remove_file(&contract)?;
+            println!("🗑️  Removed '{}'", contract.display());
+        }
+    }
+
+    if kind == "model" {
+        remove_model_samples(name)?;
+    }
+
+    if kind == "adapter" {
+        unregister_contract_adapter(name);
+    }
+
+    if kind == "route" {
+        remove_router_include(Path::new("app").join("main.py").as_path(), name)?;
+    }
+
+    sync_container(Path::new("."));
+    warn_remaining_references(folder, name);
+    Ok(())
+}
+
+/// Removes the JSON samples copied by `make model --from-json`.
+fn remove_model_samples(name: &str) -> Result<(), Box<dyn Error>> {
+    let Ok(entries) = fs::read_dir(Path::new(test_dir("model")).join("samples")) else {
+        return Ok(());
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let numbered = stem
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        if path.extension().is_some_and(|e| e == "json") && (stem == name || numbered) {
+            fs::remove_file(&path)?;
+            println!("🗑️  Removed '{}'", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// `main.py` without the import and `include_router` of `<name>_router`, or
+/// None when it never mentions that router. Other routers sharing the prefix stay.
+fn strip_router(content: &str, name: &str) -> Option<String> {
+    let router = format!("{}_router", name);
+    let module = format!("app.routes.{}", name);
+    let physical: Vec<&str> = content.lines().collect();
+    let statements = logical_lines(content);
+    let mut changed = false;
+    let mut lines = Vec::new();
+
+    for (index, (start, statement)) in statements.iter().enumerate() {
+        let end = statements.get(index + 1).map_or(physical.len(), |(next, _)| next - 1);
+        let original = &physical[start - 1..end];
+        let trimmed = statement.trim();
+
+        let imported_module = trimmed
+            .strip_prefix("from ")
+            .and_then(|rest| rest.split_once(" import "))
+            .map(|(from, _)| from.trim());
+        if included_router(trimmed).as_deref() == Some(router.as_str()) || imported_module == Some(module.as_str()) {
+            changed = true;
+            continue;
+        }
+
+        if let Some(names) = trimmed.strip_prefix("from app.routes import ") {
+            let imported: Vec<&str> = names
+                .trim_matches(|c| c == '(' || c == ')')
+                .split(',')
+                .map(str::trim)
+                .filter(|n| !n.is_empty())
+                .collect();
+            let is_router = |entry: &&str| entry.split(" as ").next().map(str::trim) == Some(router.as_str());
+            if imported.iter().any(is_router) {
+                changed = true;
+                let remaining: Vec<&str> = imported.into_iter().filter(|n| !is_router(n)).collect();
+                if !remaining.is_empty() {
+                    lines.push(format!("from app.routes import {}", remaining.join(", ")));
+                }
+                continue;
+            }
+        }
+
+        lines.extend(original.iter().map(|line| line.to_string()));
+    }
+
+    changed.then(|| lines.join("\n") + "\n")
+}
+
+fn remove_router_include(main_py: &Path, name: &str) -> Result<(), Box<dyn Error>> {
+    let Ok(content) = fs::read_to_string(main_py) else {
+        return Ok(());
+    };
+    if let Some(updated) = strip_router(&content, name) {
+        fs::write(main_py, updated)?;
+        println!("🔗 Removed '{}_router' include from '{}'", name, main_py.display());
+    }
+    Ok(())
+}
+
+fn warn_remaining_references(folder: &str, name: &str) {
+    let patterns = [
+        format!("app.{}.{}", folder, name),
+        format!("from .{} import", name),
+        format!("{}_router", name),
+    ];
+
+    for file in python_files(Path::new("app")) {
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+        for (number, line) in content.lines().enumerate() {
+            if patterns.iter().any(|p| line.contains(p.as_str())) {
+                println!(
+                    "⚠️  Remaining reference in '{}:{}': {}",
+                    file.display(),
+                    number + 1,
+                    line.trim()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN: &str = "from fastapi import FastAPI
+from app.routes import example_router, user_router, user_router_v2
+from app.routes.user_admin import router as user_admin_router
+
+app = FastAPI()
+
+app.include_router(example_router)
+app.include_router(user_router, prefix=\"/users\")
+app.include_router(user_router_v2)
+app.include_router(
+    user_admin_router,
+    prefix=\"/admin\",
+)
+";
+
+    #[test]
+    fn removes_only_the_exact_router() {
+        let updated = strip_router(MAIN, "user").unwrap();
+        assert!(updated.contains("from app.routes import example_router, user_router_v2\n"), "{}", updated);
+        assert!(!updated.contains("app.include_router(user_router,"));
+        assert!(updated.contains("app.include_router(user_router_v2)"));
+        assert!(updated.contains("    user_admin_router,"));
+        assert!(updated.contains("from app.routes.user_admin import"));
+    }
+
+    #[test]
+    fn removes_multiline_include_and_module_import() {
+        let updated = strip_router(MAIN, "user_admin").unwrap();
+        assert!(!updated.contains("user_admin"), "{}", updated);
+        assert!(updated.contains("app.include_router(user_router, prefix=\"/users\")\n"));
+        assert!(updated.ends_with("app.include_router(user_router_v2)\n"), "{}", updated);
+    }
+
+    #[test]
+    fn unknown_router_leaves_main_untouched() {
+        assert!(strip_router(MAIN, "use").is_none());
+        assert!(strip_router("app.include_router(router=user_router)\n", "user").is_some_and(|u| u.trim().is_empty()));
+    }
+}
@@ -0,0 +1,392 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::utils::openapi::build_document;
+use crate::utils::routes::collect_endpoints;
+use crate::utils::spec::{Spec, SpecOperation};
+
+const POSTMAN_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// A request ready to be sent, with sample values from the models.
+struct Request {
+    name: String,
+    operation_id: String,
+    tag: String,
+    method: String,
+    /// Path segments; path parameters are `(name, Some(sample))`.
+    segments: Vec<(String, Option<String>)>,
+    /// `/items/` and `/items` are different routes, FastAPI redirects one to the other.
+    trailing_slash: bool,
+    /// Query parameters, with whether they are required.
+    query: Vec<(String, String, bool)>,
+    headers: Vec<(String, String)>,
+    /// Media type and sample body.
+    body: Option<(String, Value)>,
+}
+
+fn text(value: &Value) -> String {
+    value.as_str().map_or_else(|| value.to_string(), str::to_string)
+}
+
+/// Header carrying the credentials of a security scheme, with a variable placeholder.
+fn auth_header(spec: &Spec, operation: &Value) -> Option<(String, String)> {
+    let requirement = operation.get("security")?.get(0)?.as_object()?;
+    let name = requirement.keys().next()?;
+    let scheme = spec.value.pointer(&format!("/components/securitySchemes/{}", name))?;
+    match (scheme.get("type").and_then(Value::as_str), scheme.get("scheme").and_then(Value::as_str)) {
+        (Some("http"), Some("basic")) => Some(("Authorization".to_string(), "Basic {{credentials}}".to_string())),
+        (Some("apiKey"), _) if scheme.get("in").and_then(Value::as_str) == Some("header") => Some((
+            scheme.get("name").and_then(Value::as_str).unwrap_or("X-API-Key").to_string(),
+            "{{api_key}}".to_string(),
+        )),
+        (Some("apiKey"), _) => None,
+        _ => Some((BEARER.0.to_string(), BEARER.1.to_string())),
+    }
+}
+
+const BEARER: (&str, &str) = ("Authorization", "Bearer {{token}}");
+
+/// `authenticated` marks routes with auth dependencies, which get a bearer
+/// placeholder when no security scheme could be resolved for them.
+fn build_request(spec: &Spec, operation: &SpecOperation, authenticated: bool) -> Request {
+    let sample = |parameter: &Value| text(&spec.sample(parameter.get("schema").unwrap_or(&Value::Null), 0));
+    let mut segments = Vec::new();
+    for segment in operation.path.split('/').filter(|s| !s.is_empty()) {
+        let name = segment.strip_prefix('{').and_then(|s| s.strip_suffix('}'));
+        let value = name.and_then(|name| {
+            operation
+                .parameters
+                .iter()
+                .find(|p| p.get("in").and_then(Value::as_str) == Some("path") && p.get("name").and_then(Value::as_str) == Some(name))
+                .map(|p| sample(p))
+        });
+        match name {
+            Some(name) => segments.push((name.to_string(), Some(value.unwrap_or_else(|| "1".to_string())))),
+            None => segments.push((segment.to_string(), None)),
+        }
+    }
+
+    let mut query = Vec::new();
+    let mut headers = Vec::new();
+    for parameter in &operation.parameters {
+        let name = parameter.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+        let required = parameter.get("required").and_then(Value::as_bool).unwrap_or(false);
+        match parameter.get("in").and_then(Value::as_str) {
+            Some("query") => query.push((name, sample(parameter), required)),
+            Some("header") if required => headers.push((name, sample(parameter))),
+            _ => {}
+        }
+    }
+    let auth = auth_header(spec, operation.operation)
+        .or_else(|| authenticated.then(|| (BEARER.0.to_string(), BEARER.1.to_string())));
+    headers.extend(auth);
+    let body = spec
+        .request_body(operation.operation)
+        .map(|(media, schema, _)| (media.to_string(), spec.sample(schema, 0)));
+
+    let operation_id = operation.operation.get("operationId").and_then(Value::as_str).unwrap_or_default();
+    Request {
+        name: operation
+            .operation
+            .get("summary")
+            .and_then(Value::as_str)
+            .map_or_else(|| format!("{} {}", operation.method.to_uppercase(), operation.path), str::to_string),
+        operation_id: operation_id.to_string(),
+        tag: operation
+            .operation
+            .get("tags")
+            .and_then(|t| t.get(0))
+            .and_then(Value::as_str)
+            .unwrap_or("default")
+            .to_string(),
+        method: operation.method.to_uppercase(),
+        trailing_slash: operation.path.len() > 1 && operation.path.ends_with('/'),
+        segments,
+        query,
+        headers,
+        body,
+    }
+}
+
+impl Request {
+    /// URL path from the rendered segments, keeping the route's trailing slash.
+    fn path(&self, segments: &[String]) -> String {
+        let slash = if self.trailing_slash { "/" } else { "" };
+        format!("/{}{}", segments.join("/"), slash)
+    }
+}
+
+fn form_encoded(body: &Value) -> Vec<(String, String)> {
+    body.as_object()
+        .map(|fields| fields.iter().map(|(k, v)| (k.clone(), text(v))).collect())
+        .unwrap_or_default()
+}
+
+/// Reads the routes statically and turns each operation into a request.
+fn collect_requests() -> Result<Vec<Request>, Box<dyn Error>> {
+    let root = Path::new(".");
+    if !root.join("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let document = build_document(root);
+    for warning in &document.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    let endpoints = collect_endpoints(root);
+    let spec = Spec { value: document.value };
+    let requests: Vec<Request> = spec
+        .operations()
+        .iter()
+        .map(|operation| {
+            let authenticated = endpoints.iter().any(|e| {
+                e.included && !e.auth.is_empty() && e.path == operation.path && e.method.eq_ignore_ascii_case(operation.method)
+            });
+            build_request(&spec, operation, authenticated)
+        })
+        .collect();
+    if requests.is_empty() {
+        return Err("No routes found in app/routes".into());
+    }
+    Ok(requests)
+}
+
+fn write_output(rendered: &str, out: Option<&str>, count: usize, kind: &str) -> Result<(), Box<dyn Error>> {
+    match out {
+        Some(path) => {
+            fs::write(path, rendered)?;
+            println!("✅ {} with {} request(s) written to '{}'", kind, count, path);
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+fn render_http(requests: &[Request], base_url: &str) -> String {
+    let mut content = format!("@baseUrl = {}\n", base_url);
+    if requests.iter().any(|r| r.headers.iter().any(|(_, v)| v.contains("{{token}}"))) {
+        content.push_str("@token = <paste a token from the login endpoint>\n");
+    }
+    for (variable, hint) in [("credentials", "<base64 user:password>"), ("api_key", "<your API key>")] {
+        if requests.iter().any(|r| r.headers.iter().any(|(_, v)| v.contains(&format!("{{{{{}}}}}", variable)))) {
+            content.push_str(&format!("@{} = {}\n", variable, hint));
+        }
+    }
+    for request in requests {
+        let path: Vec<String> = request
+            .segments
+            .iter()
+            .map(|(name, value)| value.clone().unwrap_or_else(|| name.clone()))
+            .collect();
+        let query: Vec<String> = request
+            .query
+            .iter()
+            .filter(|(_, _, required)| *required)
+            .map(|(name, value, _)| format!("{}={}", name, value))
+            .collect();
+        let query = if query.is_empty() { String::new() } else { format!("?{}", query.join("&")) };
+        content.push_str(&format!("\n### {}\n", request.name));
+        if !request.operation_id.is_empty() {
+            content.push_str(&format!("# @name {}\n", request.operation_id));
+        }
+        content.push_str(&format!("{} {{{{baseUrl}}}}{}{}\n", request.method, request.path(&path), query));
+        for (name, value) in &request.headers {
+            content.push_str(&format!("{}: {}\n", name, value));
+        }
+        if let Some((media, body)) = &request.body {
+            content.push_str(&format!("Content-Type: {}\n\n", media));
+            if media.contains("json") {
+                content.push_str(&serde_json::to_string_pretty(body).unwrap_or_default());
+            } else {
+                let fields: Vec<String> = form_encoded(body).iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                content.push_str(&fields.join("&"));
+            }
+            content.push('\n');
+        }
+    }
+    content
+}
+
+pub fn export_http(out: Option<&str>, base_url: &str) -> Result<(), Box<dyn Error>> {
+    let requests = collect_requests()?;
+    write_output(&render_http(&requests, base_url), out, requests.len(), "REST Client file")
+}
+
+fn postman_item(request: &Request) -> Value {
+    let mut path: Vec<String> = request
+        .segments
+        .iter()
+        .map(|(name, value)| if value.is_some() { format!(":{}", name) } else { name.clone() })
+        .collect();
+    let query: Vec<Value> = request
+        .query
+        .iter()
+        .map(|(name, value, required)| json!({"key": name, "value": value, "disabled": !required}))
+        .collect();
+    let enabled: Vec<String> = request
+        .query
+        .iter()
+        .filter(|(_, _, required)| *required)
+        .map(|(name, value, _)| format!("{}={}", name, value))
+        .collect();
+    let raw = format!(
+        "{{{{baseUrl}}}}{}{}",
+        request.path(&path),
+        if enabled.is_empty() { String::new() } else { format!("?{}", enabled.join("&")) }
+    );
+    // Postman keeps a trailing slash as an empty last segment.
+    if request.trailing_slash {
+        path.push(String::new());
+    }
+    let mut url = json!({"raw": raw, "host": ["{{baseUrl}}"], "path": path});
+    if !query.is_empty() {
+        url["query"] = Value::Array(query);
+    }
+    let variables: Vec<Value> = request
+        .segments
+        .iter()
+        .filter_map(|(name, value)| value.as_ref().map(|value| json!({"key": name, "value": value})))
+        .collect();
+    if !variables.is_empty() {
+        url["variable"] = Value::Array(variables);
+    }
+
+    let mut headers: Vec<Value> = request.headers.iter().map(|(k, v)| json!({"key": k, "value": v})).collect();
+    let mut item = json!({"name": request.name, "request": {"method": request.method, "url": url}});
+    if let Some((media, body)) = &request.body {
+        headers.push(json!({"key": "Content-Type", "value": media}));
+        item["request"]["body"] = if media.contains("json") {
+            json!({
+                "mode": "raw",
+                "raw": serde_json::to_string_pretty(body).unwrap_or_default(),
+                "options": {"raw": {"language": "json"}},
+            })
+        } else {
+            let fields: Vec<Value> = form_encoded(body).iter().map(|(k, v)| json!({"key": k, "value": v})).collect();
+            json!({"mode": "urlencoded", "urlencoded": fields})
+        };
+    }
+    item["request"]["header"] = Value::Array(headers);
+    item
+}
+
+fn render_postman(requests: &[Request], base_url: &str) -> Value {
+    let mut folders: Vec<(String, Vec<Value>)> = Vec::new();
+    for request in requests {
+        let item = postman_item(request);
+        match folders.iter_mut().find(|(tag, _)| *tag == request.tag) {
+            Some((_, items)) => items.push(item),
+            None => folders.push((request.tag.clone(), vec![item])),
+        }
+    }
+    let title = std::env::current_dir()
+        .ok()
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "API".to_string());
+    let mut variables = vec![json!({"key": "baseUrl", "value": base_url})];
+    for variable in ["token", "credentials", "api_key"] {
+        let placeholder = format!("{{{{{}}}}}", variable);
+        if requests.iter().any(|r| r.headers.iter().any(|(_, v)| v.contains(&placeholder))) {
+            variables.push(json!({"key": variable, "value": ""}));
+        }
+    }
+    json!({
+        "info": {"name": title, "schema": POSTMAN_SCHEMA},
+        "item": folders
+            .into_iter()
+            .map(|(tag, items)| json!({"name": tag, "item": items}))
+            .collect::<Vec<_>>(),
+        "variable": variables,
+    })
+}
+
+pub fn export_postman(out: Option<&str>, base_url: &str) -> Result<(), Box<dyn Error>> {
+    let requests = collect_requests()?;
+    let rendered = format!("{}\n", serde_json::to_string_pretty(&render_postman(&requests, base_url))?);
+    write_output(&rendered, out, requests.len(), "Postman collection")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> Spec {
+        Spec {
+            value: json!({
+                "openapi": "3.1.0",
+                "paths": {
+                    "/items/": {
+                        "post": {
+                            "operationId": "create_item",
+                            "requestBody": {"content": {"application/json": {"schema": {
+                                "type": "object", "properties": {"name": {"type": "string"}}
+                            }}}}
+                        }
+                    },
+                    "/items/{item_id}": {
+                        "get": {
+                            "parameters": [
+                                {"in": "path", "name": "item_id", "required": true, "schema": {"type": "integer"}},
+                                {"in": "query", "name": "verbose", "required": true, "schema": {"type": "boolean"}}
+                            ]
+                        }
+                    },
+                    "/": {"get": {}}
+                }
+            }),
+        }
+    }
+
+    fn requests(authenticated: bool) -> Vec<Request> {
+        let spec = spec();
+        spec.operations().iter().map(|operation| build_request(&spec, operation, authenticated)).collect()
+    }
+
+    fn find<'a>(requests: &'a [Request], method: &str, first: &str) -> &'a Request {
+        requests
+            .iter()
+            .find(|r| r.method == method && r.segments.first().map(|(name, _)| name.as_str()).unwrap_or("") == first)
+            .unwrap()
+    }
+
+    #[test]
+    fn http_keeps_trailing_slash() {
+        let requests = requests(false);
+        let rendered = render_http(&requests, "http://localhost:8000");
+        assert!(rendered.contains("POST {{baseUrl}}/items/\n"), "{}", rendered);
+        assert!(rendered.contains("GET {{baseUrl}}/items/1?verbose=true\n"), "{}", rendered);
+        assert!(rendered.contains("GET {{baseUrl}}/\n"), "{}", rendered);
+    }
+
+    #[test]
+    fn postman_keeps_trailing_slash() {
+        let requests = requests(false);
+        let create = postman_item(find(&requests, "POST", "items"));
+        assert_eq!(create["request"]["url"]["raw"], "{{baseUrl}}/items/");
+        assert_eq!(create["request"]["url"]["path"], json!(["items", ""]));
+        let get = postman_item(find(&requests, "GET", "items"));
+        assert_eq!(get["request"]["url"]["raw"], "{{baseUrl}}/items/:item_id?verbose=true");
+        assert_eq!(get["request"]["url"]["path"], json!(["items", ":item_id"]));
+    }
+
+    #[test]
+    fn auth_dependencies_without_scheme_get_bearer_placeholder() {
+        let anonymous = requests(false);
+        assert!(anonymous.iter().all(|r| r.headers.is_empty()));
+        let authenticated = requests(true);
+        let get = find(&authenticated, "GET", "items");
+        assert_eq!(get.headers, [("Authorization".to_string(), "Bearer {{token}}".to_string())]);
+        assert!(render_http(&authenticated, "http://x").contains("@token = "));
+    }
+
+    #[test]
+    fn security_scheme_wins_over_fallback() {
+        let mut spec = spec();
+        spec.value["components"] = json!({"securitySchemes": {"APIKeyHeader": {"type": "apiKey", "in": "header", "name": "X-Key"}}});
+        spec.value["paths"]["/"]["get"]["security"] = json!([{"APIKeyHeader": []}]);
+        let operations = spec.operations();
+        let root = operations.iter().find(|o| o.path == "/").unwrap();
+        let request = build_request(&spec, root, true);
+        assert_eq!(request.headers, [("X-Key".to_string(), "{{api_key}}".to_string())]);
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use crate::handlers::check::{find_violations, scan_project};
+use crate::utils::config::Config;
+
+const VIOLATION_COLOR: &str = "#d62728";
+
+fn layer_color(layer: &str) -> &'static str {
+    match layer {
+        "routes" => "#4e79a7",
+        "usecases" => "#f28e2b",
+        "services" => "#76b7b2",
+        "core" => "#59a14f",
+        "ports" => "#edc948",
+        "infrastructure" => "#b07aa1",
+        "models" => "#ff9da7",
+        "container" => "#9c755f",
+        _ => "#bab0ac",
+    }
+}
+
+struct Graph {
+    /// node id -> layer ("other" outside every layer, None for external packages)
+    nodes: BTreeMap<String, Option<String>>,
+    /// (from, to) -> violates a rule
+    edges: BTreeMap<(String, String), bool>,
+}
+
+fn build(root: &Path, config: &Config, level: &str) -> Graph {
+    let modules = scan_project(root, config);
+    let violations = find_violations(root, config, &modules);
+    let violating: BTreeSet<(&str, &str)> = violations
+        .iter()
+        .map(|v| (v.module.as_str(), v.imports.as_str()))
+        .collect();
+
+    let mut graph = Graph {
+        nodes: BTreeMap::new(),
+        edges: BTreeMap::new(),
+    };
+    // Modules outside every layer (like app.main) stay as their own node.
+    let node_for = |module: &str| -> (String, Option<String>) {
+        match config.layer_of(module) {
+            Some(layer) if level == "layer" => (layer.to_string(), Some(layer.to_string())),
+            Some(layer) => (module.to_string(), Some(layer.to_string())),
+            None => (module.to_string(), Some("other".to_string())),
+        }
+    };
+
+    for module in modules.modules.keys() {
+        let (id, layer) = node_for(module);
+        graph.nodes.insert(id, layer);
+    }
+
+    for edge in &modules.edges {
+        let violates = violating.contains(&(edge.from.as_str(), edge.to.as_str()));
+        let (from, _) = node_for(&edge.from);
+        let to = if modules.modules.contains_key(&edge.to) || (level == "layer" && modules.is_internal(&edge.to)) {
+            Some(node_for(&edge.to))
+        } else if violates {
+            // External packages only show up when importing them breaks a rule.
+            let package = edge.to.split('.').next().unwrap_or(&edge.to).to_string();
+            Some((package, None))
+        } else {
+            None
+        };
+        let Some((to, layer)) = to else {
+            continue;
+        };
+        if from == to {
+            continue;
+        }
+        graph.nodes.entry(to.clone()).or_insert(layer);
+        *graph.edges.entry((from, to)).or_insert(false) |= violates;
+    }
+    graph
+}
+
+fn node_id(name: &str) -> String {
+    name.replace(['.', '-'], "_")
+}
+
+fn render_mermaid(graph: &Graph) -> String {
+    let mut out = String::from("flowchart LR\n");
+    let layers: BTreeSet<&str> = graph.nodes.values().flatten().map(String::as_str).collect();
+    for layer in &layers {
+        out.push_str(&format!(
+            "    classDef {} fill:{},stroke:#333,color:#000;\n",
+            layer,
+            layer_color(layer)
+        ));
+    }
+    out.push_str("    classDef external fill:#ffffff,stroke:#d62728,stroke-dasharray:4;\n");
+
+    for (name, layer) in &graph.nodes {
+        let class = layer.as_deref().unwrap_or("external");
+        out.push_str(&format!("    {}[\"{}\"]:::{}\n", node_id(name), name, class));
+    }
+
+    let mut violating_links = Vec::new();
+    for (index, ((from, to), violates)) in graph.edges.iter().enumerate() {
+        out.push_str(&format!("    {} --> {}\n", node_id(from), node_id(to)));
+        if *violates {
+            violating_links.push(index.to_string());
+        }
+    }
+    if !violating_links.is_empty() {
+        out.push_str(&format!(
+            "    linkStyle {} stroke:{},stroke-width:3px;\n",
+            violating_links.join(","),
+            VIOLATION_COLOR
+        ));
+    }
+    out
+}
+
+fn render_dot(graph: &Graph) -> String {
+    let mut out = String::from(
+        "digraph faspi {\n    rankdir=LR;\n    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n",
+    );
+    for (name, layer) in &graph.nodes {
+        match layer {
+            Some(layer) => out.push_str(&format!(
+                "    \"{}\" [fillcolor=\"{}\", tooltip=\"{}\"];\n",
+                name,
+                layer_color(layer),
+                layer
+            )),
+            None => out.push_str(&format!(
+                "    \"{}\" [fillcolor=\"#ffffff\", color=\"{}\", style=\"rounded,dashed\"];\n",
+                name, VIOLATION_COLOR
+            )),
+        }
+    }
+    for ((from, to), violates) in &graph.edges {
+        if *violates {
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [color=\"{}\", penwidth=2.5, label=\"violation\"];\n",
+                from, to, VIOLATION_COLOR
+            ));
+        } else {
+            out.push_str(&format!("    \"{}\" -> \"{}\";\n", from, to));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+pub fn export_graph(format: &str, level: &str, out: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let root = Path::new(".");
+    let config = Config::load(root)?;
+    let graph = build(root, &config, level);
+    if graph.nodes.is_empty() {
+        return Err("No Python modules found, run this command from the project root".into());
+    }
+
+    let rendered = match format {
+        "dot" => render_dot(&graph),
+        _ => render_mermaid(&graph),
+    };
+    match out {
+        Some(path) => {
+            fs::write(path, rendered)?;
+            println!("✅ Graph written to '{}'", path);
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
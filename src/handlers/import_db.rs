@@ -0,0 +1,750 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use rusqlite::{Connection, OpenFlags};
+use crate::handlers::container::sync_container;
+use crate::handlers::make::{create_port, register_contract_adapter};
+use crate::handlers::unit_of_work::{has_unit_of_work, PORT as UNIT_OF_WORK};
+use crate::utils::config::Config;
+use crate::utils::ddl::parse_ddl;
+use crate::utils::fs::{append_import, append_to_init, include_router};
+use crate::utils::spec::python_literal;
+use crate::utils::string::{to_camel_case, to_singular};
+use crate::utils::tables::{attribute, column_type, render_table, Column, ForeignKey, Index, Table};
+use crate::utils::testing::{create_test_file, generate_port_route_test, RouteTestCase};
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Reads tables, columns, keys and indexes through SQLite's pragmas.
+fn introspect_sqlite(path: &Path) -> Result<Vec<Table>, Box<dyn Error>> {
+    if !path.is_file() {
+        return Err(format!("Database '{}' not found", path.display()).into());
+    }
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let names: Vec<String> = connection
+        .prepare(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' \
+             AND name != 'alembic_version' ORDER BY name",
+        )?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    let mut tables = Vec::new();
+    for name in names {
+        let quoted = quote_identifier(&name);
+        let mut columns: Vec<Column> = connection
+            .prepare(&format!("PRAGMA table_info({})", quoted))?
+            .query_map([], |row| {
+                Ok(Column {
+                    name: row.get(1)?,
+                    data_type: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                    nullable: row.get::<_, i64>(3)? == 0,
+                    default: row.get(4)?,
+                    primary_key: row.get::<_, i64>(5)? > 0,
+                    unique: false,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        for column in columns.iter_mut().filter(|c| c.primary_key) {
+            column.nullable = false;
+        }
+
+        let mut foreign_keys: Vec<(i64, ForeignKey)> = Vec::new();
+        let rows: Vec<(i64, String, String, Option<String>, String)> = connection
+            .prepare(&format!("PRAGMA foreign_key_list({})", quoted))?
+            .query_map([], |row| Ok((row.get(0)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(6)?)))?
+            .collect::<Result<_, _>>()?;
+        for (id, table, from, to, on_delete) in rows {
+            let on_delete = (on_delete != "NO ACTION").then_some(on_delete);
+            match foreign_keys.iter_mut().find(|(key, _)| *key == id) {
+                Some((_, foreign_key)) => {
+                    foreign_key.columns.push(from);
+                    foreign_key.references.extend(to);
+                }
+                None => foreign_keys.push((
+                    id,
+                    ForeignKey {
+                        columns: vec![from],
+                        table,
+                        references: to.into_iter().collect(),
+                        on_delete,
+                    },
+                )),
+            }
+        }
+
+        let index_rows: Vec<(String, bool, String)> = connection
+            .prepare(&format!("PRAGMA index_list({})", quoted))?
+            .query_map([], |row| Ok((row.get(1)?, row.get::<_, i64>(2)? == 1, row.get(3)?)))?
+            .collect::<Result<_, _>>()?;
+        let mut indexes = Vec::new();
+        for (index_name, unique, origin) in index_rows {
+            if origin == "pk" {
+                continue;
+            }
+            let index_columns: Vec<String> = connection
+                .prepare(&format!("PRAGMA index_info({})", quote_identifier(&index_name)))?
+                .query_map([], |row| row.get::<_, Option<String>>(2))?
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect();
+            if let Some(column) = columns
+                .iter_mut()
+                .find(|c| unique && index_columns.len() == 1 && c.name == index_columns[0])
+            {
+                column.unique = true;
+            }
+            let index_name = if index_name.starts_with("sqlite_autoindex_") {
+                format!("uq_{}_{}", name, index_columns.join("_"))
+            } else {
+                index_name
+            };
+            indexes.push(Index {
+                name: index_name,
+                columns: index_columns,
+                unique,
+            });
+        }
+
+        tables.push(Table {
+            name,
+            columns,
+            foreign_keys: foreign_keys.into_iter().map(|(_, key)| key).collect(),
+            indexes,
+        });
+    }
+
+    // A foreign key without target columns references the parent's primary key.
+    let keys: Vec<(String, Vec<String>)> = tables
+        .iter()
+        .map(|t| (t.name.clone(), t.primary_key().iter().map(|c| c.name.clone()).collect()))
+        .collect();
+    for table in &mut tables {
+        for foreign_key in table.foreign_keys.iter_mut().filter(|f| f.references.is_empty()) {
+            if let Some((_, key)) = keys.iter().find(|(name, _)| *name == foreign_key.table) {
+                foreign_key.references = key.clone();
+            }
+        }
+    }
+    Ok(tables)
+}
+
+fn generate_table_test(table: &Table) -> String {
+    let class = table.class();
+    // A table without a primary key is a plain `Table`, there is no model class to check.
+    let (model, name, columns_of) = if table.is_mapped() {
+        (class.clone(), format!("{}.__tablename__", class), format!("{}.__table__", class))
+    } else {
+        let object = table.table_object();
+        (object.clone(), format!("{}.name", object), object)
+    };
+    let columns: Vec<String> = table.columns.iter().map(|c| python_literal(&c.name.clone().into())).collect();
+    let fields: Vec<String> = table
+        .columns
+        .iter()
+        .filter(|c| table.generated_key().is_none_or(|key| key.name != c.name))
+        .map(|c| python_literal(&attribute(&c.name).into()))
+        .collect();
+    format!(
+        r#"# 🧪 Test for model: {0}
+import pytest
+from app.models.{0} import {6}, {1}Create, {1}Read
+
+def test_{0}_table():
+    assert {7} == {2}
+    assert {{column.name for column in {8}.columns}} == {{{3}}}
+
+def test_{0}_create_schema():
+    assert set({1}Create.model_fields) == {4}
+    assert {1}Create.model_validate({5})
+
+def test_{0}_read_schema_reads_attributes():
+    assert {1}Read.model_config["from_attributes"] is True
+"#,
+        table.module(),
+        class,
+        python_literal(&table.name.clone().into()),
+        columns.join(", "),
+        if fields.is_empty() { "set()".to_string() } else { format!("{{{}}}", fields.join(", ")) },
+        python_literal(&table.sample()),
+        model,
+        name,
+        columns_of
+    )
+}
+
+pub fn ensure_writable(paths: &[PathBuf], force: bool) -> Result<(), Box<dyn Error>> {
+    let existing: Vec<String> = paths.iter().filter(|p| p.exists()).map(|p| p.display().to_string()).collect();
+    if !existing.is_empty() && !force {
+        return Err(format!("These files already exist, use --force to overwrite: {}", existing.join(", ")).into());
+    }
+    Ok(())
+}
+
+/// Writes one model module per table, refusing to overwrite unless `force`;
+/// `origin` ends the header comment, e.g. "imported from shop.db".
+pub fn write_tables(tables: &[Table], origin: &str, force: bool) -> Result<(), Box<dyn Error>> {
+    if !Path::new("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    if !Path::new("app/core/database.py").exists() {
+        println!("⚠️  'app/core/database.py' not found, create the project with --sql to get the Base the models use");
+    }
+    let paths: Vec<PathBuf> = tables
+        .iter()
+        .map(|t| Path::new("app").join("models").join(format!("{}.py", t.module())))
+        .collect();
+    ensure_writable(&paths, force)?;
+
+    fs::create_dir_all("app/models")?;
+    for (table, path) in tables.iter().zip(&paths) {
+        for column in table.columns.iter().filter(|c| !column_type(&c.data_type).known) {
+            println!(
+                "⚠️  {}.{}: unknown type '{}' mapped to {}",
+                table.name,
+                column.name,
+                column.data_type,
+                column_type(&column.data_type).sqlalchemy
+            );
+        }
+        if !table.is_mapped() {
+            println!(
+                "⚠️  {}: no primary key, written as the Table '{}' without a mapped class",
+                table.name,
+                table.table_object()
+            );
+        }
+        for foreign_key in table.foreign_keys.iter().filter(|f| !tables.iter().any(|t| t.name == f.table)) {
+            println!("⚠️  {}: foreign key to '{}', which was not imported", table.name, foreign_key.table);
+        }
+        let header = format!("# Model: {}, {}", table.module(), origin);
+        fs::write(path, render_table(&header, table, tables))?;
+        append_import("app/models/__init__.py", &format!("from . import {}", table.module()), &table.module());
+        println!("✅ Model '{}' created at '{}'", table.class(), path.display());
+        create_test_file("model", &table.module(), &generate_table_test(table));
+    }
+    Ok(())
+}
+
+/// With a `unit_of_work` the writes only flush, the unit of work commits them.
+fn render_adapter(table: &Table, port: &str, port_class: &str, class: &str, key: &Column, is_async: bool, unit_of_work: bool) -> String {
+    let model = table.class();
+    let module = table.module();
+    let single = to_singular(&module);
+    let key_name = attribute(&key.name);
+    let key_type = column_type(&key.data_type).python;
+    let (session_import, session, def, wait) = if is_async {
+        ("from sqlalchemy import select\nfrom sqlalchemy.ext.asyncio import AsyncSession", "AsyncSession", "async def", "await ")
+    } else {
+        ("from sqlalchemy.orm import Session", "Session", "def", "")
+    };
+    let save = if unit_of_work { "flush" } else { "commit" };
+    let rows = if is_async {
+        format!("(await self.db.scalars(select({model}).offset(offset).limit(limit))).all()")
+    } else {
+        format!("self.db.query({model}).offset(offset).limit(limit).all()")
+    };
+    format!(
+        r#"from __future__ import annotations
+
+from typing import List, Optional
+
+from fastapi import Depends
+{session_import}
+
+from app.core.database import get_db
+from app.models.{module} import {model}, {model}Create, {model}Read, {model}Update
+from app.ports.{port} import {port_class}
+
+
+class {class}({port_class}):
+    def __init__(self, db: {session} = Depends(get_db)):
+        self.db = db
+
+    {def} list_{module}(self, offset: int = 0, limit: int = 100) -> List[{model}Read]:
+        rows = {rows}
+        return [{model}Read.model_validate(row) for row in rows]
+
+    {def} get_{single}(self, {key_name}: {key_type}) -> Optional[{model}Read]:
+        row = {wait}self.db.get({model}, {key_name})
+        return {model}Read.model_validate(row) if row is not None else None
+
+    {def} create_{single}(self, data: {model}Create) -> {model}Read:
+        row = {model}(**data.model_dump(exclude_unset=True))
+        self.db.add(row)
+        {wait}self.db.{save}()
+        {wait}self.db.refresh(row)
+        return {model}Read.model_validate(row)
+
+    {def} update_{single}(self, {key_name}: {key_type}, data: {model}Update) -> Optional[{model}Read]:
+        row = {wait}self.db.get({model}, {key_name})
+        if row is None:
+            return None
+        for field, value in data.model_dump(exclude_unset=True).items():
+            setattr(row, field, value)
+        {wait}self.db.{save}()
+        {wait}self.db.refresh(row)
+        return {model}Read.model_validate(row)
+
+    {def} delete_{single}(self, {key_name}: {key_type}) -> bool:
+        row = {wait}self.db.get({model}, {key_name})
+        if row is None:
+            return False
+        {wait}self.db.delete(row)
+        {wait}self.db.{save}()
+        return True
+"#
+    )
+}
+
+fn generate_adapter_crud_test(table: &Table, name: &str, class: &str, port: &str, port_class: &str, key: &Column) -> String {
+    let model = table.class();
+    let module = table.module();
+    let single = to_singular(&module);
+    let key_name = attribute(&key.name);
+    format!(
+        r#"# 🧪 Test for adapter: {name}
+import pytest
+from sqlalchemy import create_engine
+from sqlalchemy.orm import sessionmaker
+
+import app.models
+from app.core.database import Base
+from app.infrastructure.{name} import {class}
+from app.models.{module} import {model}, {model}Create, {model}Update
+from app.ports.{port} import {port_class}
+
+@pytest.fixture
+def repository():
+    engine = create_engine("sqlite://")
+    Base.metadata.create_all(engine, tables=[{model}.__table__])
+    session = sessionmaker(bind=engine)()
+    yield {class}(session)
+    session.close()
+
+def test_{name}_implements_{port}(repository):
+    assert isinstance(repository, {port_class})
+
+def test_{name}_crud(repository):
+    created = repository.create_{single}({model}Create.model_validate({sample}))
+    assert repository.get_{single}(created.{key_name}) == created
+    assert repository.list_{module}() == [created]
+    assert repository.update_{single}(created.{key_name}, {model}Update()) == created
+    assert repository.delete_{single}(created.{key_name}) is True
+    assert repository.get_{single}(created.{key_name}) is None
+"#,
+        sample = python_literal(&table.sample())
+    )
+}
+
+fn render_mongo_adapter(table: &Table, port: &str, port_class: &str, class: &str) -> String {
+    let model = table.class();
+    let module = table.module();
+    let single = to_singular(&module);
+    format!(
+        r#"from __future__ import annotations
+
+from typing import List, Optional
+
+from beanie import PydanticObjectId
+
+from app.models.{module} import {model}, {model}Create, {model}Read, {model}Update
+from app.ports.{port} import {port_class}
+
+
+def _read(document: {model}) -> {model}Read:
+    return {model}Read(id=str(document.id), **document.model_dump(exclude={{"id", "revision_id"}}))
+
+
+class {class}({port_class}):
+    async def list_{module}(self, offset: int = 0, limit: int = 100) -> List[{model}Read]:
+        documents = await {model}.find_all(skip=offset, limit=limit).to_list()
+        return [_read(document) for document in documents]
+
+    async def get_{single}(self, id: str) -> Optional[{model}Read]:
+        document = await self._find(id)
+        return _read(document) if document is not None else None
+
+    async def create_{single}(self, data: {model}Create) -> {model}Read:
+        document = {model}(**data.model_dump())
+        await document.insert()
+        return _read(document)
+
+    async def update_{single}(self, id: str, data: {model}Update) -> Optional[{model}Read]:
+        document = await self._find(id)
+        if document is None:
+            return None
+        changes = data.model_dump(exclude_unset=True)
+        if changes:
+            await document.set(changes)
+        return _read(document)
+
+    async def delete_{single}(self, id: str) -> bool:
+        document = await self._find(id)
+        if document is None:
+            return False
+        await document.delete()
+        return True
+
+    async def _find(self, id: str) -> Optional[{model}]:
+        if not PydanticObjectId.is_valid(id):
+            return None
+        return await {model}.get(PydanticObjectId(id))
+"#
+    )
+}
+
+/// CRUD scenario against mongomock, skipped when `mongomock-motor` is not installed.
+fn generate_mongo_adapter_crud_test(table: &Table, name: &str, class: &str, port: &str, port_class: &str) -> String {
+    let model = table.class();
+    let module = table.module();
+    let single = to_singular(&module);
+    format!(
+        r#"# 🧪 Test for adapter: {name}
+import asyncio
+
+import pytest
+
+mongomock_motor = pytest.importorskip("mongomock_motor")
+from beanie import init_beanie
+
+from app.infrastructure.{name} import {class}
+from app.models.{module} import {model}, {model}Create, {model}Update
+from app.ports.{port} import {port_class}
+
+def test_{name}_implements_{port}():
+    assert issubclass({class}, {port_class})
+
+def test_{name}_crud():
+    async def scenario():
+        client = mongomock_motor.AsyncMongoMockClient()
+        await init_beanie(database=client["test"], document_models=[{model}])
+        repository = {class}()
+        created = await repository.create_{single}({model}Create.model_validate({sample}))
+        assert await repository.get_{single}(created.id) == created
+        assert await repository.list_{module}() == [created]
+        assert await repository.update_{single}(created.id, {model}Update()) == created
+        assert await repository.delete_{single}(created.id) is True
+        assert await repository.get_{single}(created.id) is None
+
+    asyncio.run(scenario())
+"#,
+        sample = python_literal(&table.sample())
+    )
+}
+
+/// Same scenario as `generate_adapter_crud_test`, run in one event loop on aiosqlite.
+fn generate_async_adapter_crud_test(table: &Table, name: &str, class: &str, port: &str, port_class: &str, key: &Column) -> String {
+    let model = table.class();
+    let module = table.module();
+    let single = to_singular(&module);
+    let key_name = attribute(&key.name);
+    format!(
+        r#"# 🧪 Test for adapter: {name}
+import asyncio
+
+import pytest
+
+pytest.importorskip("aiosqlite")
+from sqlalchemy.ext.asyncio import async_sessionmaker, create_async_engine
+
+import app.models
+from app.core.database import Base
+from app.infrastructure.{name} import {class}
+from app.models.{module} import {model}, {model}Create, {model}Update
+from app.ports.{port} import {port_class}
+
+def test_{name}_implements_{port}():
+    assert issubclass({class}, {port_class})
+
+def test_{name}_crud():
+    async def scenario():
+        engine = create_async_engine("sqlite+aiosqlite://")
+        async with engine.begin() as connection:
+            await connection.run_sync(Base.metadata.create_all, tables=[{model}.__table__])
+        async with async_sessionmaker(engine, expire_on_commit=False)() as session:
+            repository = {class}(session)
+            created = await repository.create_{single}({model}Create.model_validate({sample}))
+            assert await repository.get_{single}(created.{key_name}) == created
+            assert await repository.list_{module}() == [created]
+            assert await repository.update_{single}(created.{key_name}, {model}Update()) == created
+            assert await repository.delete_{single}(created.{key_name}) is True
+            assert await repository.get_{single}(created.{key_name}) is None
+        await engine.dispose()
+
+    asyncio.run(scenario())
+"#,
+        sample = python_literal(&table.sample())
+    )
+}
+
+fn render_crud_router(table: &Table, port: &str, port_class: &str, key: &Column, is_async: bool, unit_of_work: bool) -> String {
+    let (def, wait) = if is_async { ("async def", "await ") } else { ("def", "") };
+    let model = table.class();
+    let module = table.module();
+    let single = to_singular(&module);
+    let key_name = attribute(&key.name);
+    let key_type = column_type(&key.data_type).python;
+    let collection = format!("/{}", table.name.replace('_', "-"));
+    let dependency = format!("repository: {} = Depends(get_{})", port_class, port);
+    let not_found = python_literal(&format!("{} not found", model).into());
+    let key_import = match key_type.as_str() {
+        "UUID" => "from uuid import UUID\n",
+        _ => "",
+    };
+    // Writes run in the unit of work, which commits them before the response.
+    let (writes, container_imports, uow_import) = if unit_of_work {
+        (
+            format!("{}, uow: UnitOfWork = Depends(get_{})", dependency, UNIT_OF_WORK),
+            format!("get_{}, get_{}", port, UNIT_OF_WORK),
+            format!("\nfrom app.ports.{} import UnitOfWork", UNIT_OF_WORK),
+        )
+    } else {
+        (dependency.clone(), format!("get_{}", port), String::new())
+    };
+    let transaction = |result: &str, call: String| {
+        if unit_of_work {
+            let with = if is_async { "async with" } else { "with" };
+            format!("    {with} uow:\n        {result} = {wait}{call}\n        {wait}uow.commit()")
+        } else {
+            format!("    {result} = {wait}{call}")
+        }
+    };
+    let create = if unit_of_work {
+        format!("{}\n    return {}", transaction(&single, format!("repository.create_{}(data)", single)), single)
+    } else {
+        format!("    return {wait}repository.create_{single}(data)")
+    };
+    let update = transaction(&single, format!("repository.update_{}({}, data)", single, key_name));
+    let delete = if unit_of_work {
+        format!("{}\n    if not deleted:", transaction("deleted", format!("repository.delete_{}({})", single, key_name)))
+    } else {
+        format!("    if not {wait}repository.delete_{single}({key_name}):")
+    };
+    format!(
+        r#"from typing import List
+{key_import}
+from fastapi import APIRouter, Depends, HTTPException
+from app.container import {container_imports}
+from app.models.{module} import {model}Create, {model}Read, {model}Update
+from app.ports.{port} import {port_class}{uow_import}
+
+router = APIRouter(tags=[{tag}])
+
+
+@router.get("{collection}", response_model=List[{model}Read])
+{def} list_{module}(offset: int = 0, limit: int = 100, {dependency}):
+    return {wait}repository.list_{module}(offset=offset, limit=limit)
+
+
+@router.get("{collection}/{{{key_name}}}", response_model={model}Read)
+{def} get_{single}({key_name}: {key_type}, {dependency}):
+    {single} = {wait}repository.get_{single}({key_name})
+    if {single} is None:
+        raise HTTPException(status_code=404, detail={not_found})
+    return {single}
+
+
+@router.post("{collection}", response_model={model}Read, status_code=201)
+{def} create_{single}(data: {model}Create, {writes}):
+{create}
+
+
+@router.patch("{collection}/{{{key_name}}}", response_model={model}Read)
+{def} update_{single}({key_name}: {key_type}, data: {model}Update, {writes}):
+{update}
+    if {single} is None:
+        raise HTTPException(status_code=404, detail={not_found})
+    return {single}
+
+
+@router.delete("{collection}/{{{key_name}}}", status_code=204)
+{def} delete_{single}({key_name}: {key_type}, {writes}):
+{delete}
+        raise HTTPException(status_code=404, detail={not_found})
+"#,
+        tag = python_literal(&table.name.clone().into()),
+    )
+}
+
+/// Where a generated repository keeps its records.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Store {
+    Sql,
+    Mongo,
+}
+
+impl Store {
+    fn prefix(self) -> &'static str {
+        match self {
+            Store::Sql => "sql",
+            Store::Mongo => "mongo",
+        }
+    }
+}
+
+/// Repository port with its fake, adapter and router for a table.
+pub fn create_crud(table: &Table, store: Store) -> Result<(), Box<dyn Error>> {
+    // Documents are addressed by their ObjectId, exposed as a string.
+    let document_key = Column {
+        name: "id".to_string(),
+        data_type: "VARCHAR".to_string(),
+        nullable: false,
+        default: None,
+        primary_key: true,
+        unique: false,
+    };
+    let key = match store {
+        Store::Mongo => &document_key,
+        Store::Sql => match table.primary_key().as_slice() {
+            [key] => *key,
+            _ => {
+                println!("⚠️  {}: CRUD needs a single-column primary key, skipped", table.name);
+                return Ok(());
+            }
+        },
+    };
+    let model = table.class();
+    let module = table.module();
+    let single = to_singular(&module);
+    let key_spec = format!("{}:{}", attribute(&key.name), column_type(&key.data_type).python);
+    let port = format!("{}_repository", module);
+    let port_class = to_camel_case(&port);
+    let is_async = store == Store::Mongo || Config::async_database(Path::new("."));
+    let unit_of_work = store == Store::Sql && has_unit_of_work(Path::new("."));
+    let methods = [
+        format!("list_{}(offset:int = 0, limit:int = 100)->List[{}Read]", module, model),
+        format!("get_{}({})->Optional[{}Read]", single, key_spec, model),
+        format!("create_{}(data:{}Create)->{}Read", single, model, model),
+        format!("update_{}({}, data:{}Update)->Optional[{}Read]", single, key_spec, model, model),
+        format!("delete_{}({})->bool", single, key_spec),
+    ];
+    let methods: Vec<String> = methods
+        .into_iter()
+        .map(|method| if is_async { format!("async {}", method) } else { method })
+        .collect();
+    create_port(&port, &methods)?;
+
+    let adapter = format!("{}_{}", store.prefix(), port);
+    let adapter_class = to_camel_case(&adapter);
+    let path = Path::new("app").join("infrastructure").join(format!("{}.py", adapter));
+    fs::create_dir_all("app/infrastructure")?;
+    let source = match store {
+        Store::Sql => render_adapter(table, &port, &port_class, &adapter_class, key, is_async, unit_of_work),
+        Store::Mongo => render_mongo_adapter(table, &port, &port_class, &adapter_class),
+    };
+    fs::write(&path, source)?;
+    append_import(
+        "app/infrastructure/__init__.py",
+        &format!("from .{} import {}", adapter, adapter_class),
+        &adapter,
+    );
+    println!("✅ Adapter created at '{}'", path.display());
+    let test = match store {
+        Store::Mongo => generate_mongo_adapter_crud_test(table, &adapter, &adapter_class, &port, &port_class),
+        Store::Sql if is_async => generate_async_adapter_crud_test(table, &adapter, &adapter_class, &port, &port_class, key),
+        Store::Sql => generate_adapter_crud_test(table, &adapter, &adapter_class, &port, &port_class, key),
+    };
+    create_test_file("adapter", &adapter, &test);
+    register_contract_adapter(&port, &format!("from app.infrastructure.{} import {}", adapter, adapter_class), &adapter_class);
+    sync_container(Path::new("."));
+
+    let path = Path::new("app").join("routes").join(format!("{}.py", module));
+    fs::write(&path, render_crud_router(table, &port, &port_class, key, is_async, unit_of_work))?;
+    append_to_init("app/routes/__init__.py", &module);
+    include_router(&Path::new("app").join("main.py"), &module)?;
+    println!("✅ Route created at '{}'", path.display());
+
+    let collection = format!("/{}", table.name.replace('_', "-"));
+    let item = format!("{}/{}", collection, if column_type(&key.data_type).python == "int" { "1" } else { "key" });
+    // What the repository hands back: the sample as stored, with its key.
+    let mut read = table.sample();
+    match (store, table.generated_key()) {
+        (Store::Mongo, _) => read["id"] = "key".into(),
+        (Store::Sql, Some(key)) => read[attribute(&key.name)] = 1.into(),
+        (Store::Sql, None) => {}
+    }
+    let read = python_literal(&read);
+    let case = |name: String, method: &str, url: &str, json: Option<String>, status: &str, answer: String| RouteTestCase {
+        name,
+        method: method.to_string(),
+        url: url.to_string(),
+        params: None,
+        headers: None,
+        json,
+        status: status.to_string(),
+        answer: Some(answer),
+    };
+    let cases = [
+        case(format!("list_{}", module), "GET", &collection, None, "200", format!("[{}]", read)),
+        case(format!("get_{}", single), "GET", &item, None, "200", read.clone()),
+        case(format!("create_{}", single), "POST", &collection, Some(python_literal(&table.sample())), "201", read.clone()),
+        case(format!("update_{}", single), "PATCH", &item, Some("{}".to_string()), "200", read.clone()),
+        case(format!("delete_{}", single), "DELETE", &item, None, "204", "True".to_string()),
+    ];
+    let cases: Vec<&RouteTestCase> = cases.iter().collect();
+    let overrides: &[(&str, &str)] = if unit_of_work { &[(UNIT_OF_WORK, "UnitOfWork")] } else { &[] };
+    create_test_file("route", &module, &generate_port_route_test(&module, &port, &port_class, &cases, overrides));
+    Ok(())
+}
+
+/// Router, port and adapter modules `create_crud` writes for a table.
+pub fn crud_paths(table: &Table, store: Store) -> Vec<PathBuf> {
+    let module = table.module();
+    vec![
+        Path::new("app").join("routes").join(format!("{}.py", module)),
+        Path::new("app").join("ports").join(format!("{}_repository.py", module)),
+        Path::new("app").join("infrastructure").join(format!("{}_{}_repository.py", store.prefix(), module)),
+    ]
+}
+
+/// Writes the models of the tables and, with `crud`, their repositories and routers.
+pub fn import_tables(tables: &[Table], origin: &str, crud: bool, force: bool) -> Result<(), Box<dyn Error>> {
+    if crud {
+        let paths: Vec<PathBuf> = tables.iter().flat_map(|table| crud_paths(table, Store::Sql)).collect();
+        ensure_writable(&paths, force)?;
+    }
+    write_tables(tables, origin, force)?;
+    if crud {
+        for table in tables {
+            create_crud(table, Store::Sql)?;
+        }
+    }
+    Ok(())
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path).file_name().map_or(path.to_string(), |n| n.to_string_lossy().to_string())
+}
+
+pub fn import_sqlite(database: &str, crud: bool, force: bool) -> Result<(), Box<dyn Error>> {
+    let tables = introspect_sqlite(Path::new(database))?;
+    if tables.is_empty() {
+        return Err(format!("No tables found in '{}'", database).into());
+    }
+    import_tables(&tables, &format!("imported from {}", file_name(database)), crud, force)?;
+    println!("✅ Imported {} table(s) from '{}'", tables.len(), database);
+    Ok(())
+}
+
+pub fn import_ddl(file: &str, crud: bool, force: bool) -> Result<(), Box<dyn Error>> {
+    let sql = fs::read_to_string(file).map_err(|e| format!("Cannot read '{}': {}", file, e))?;
+    let (tables, warnings) = parse_ddl(&sql);
+    for warning in &warnings {
+        println!("⚠️  {}", warning);
+    }
+    if tables.is_empty() {
+        return Err(format!("No CREATE TABLE statements found in '{}'", file).into());
+    }
+    import_tables(&tables, &format!("imported from {}", file_name(file)), crud, force)?;
+    println!(
+        "✅ Imported {} table(s) from '{}' ({} unsupported construct(s) reported)",
+        tables.len(),
+        file,
+        warnings.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,628 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use serde_json::Value;
+use crate::handlers::make::create_port;
+use crate::handlers::usecase::create_usecase;
+use crate::utils::fs::{append_import, append_to_init, include_router};
+use crate::utils::python::typing_names;
+use crate::utils::spec::{class_name, python_literal, ref_name, Spec, SpecOperation};
+use crate::utils::string::{to_camel_case, to_field_name, to_python_identifier, unique_name};
+use crate::utils::testing::{create_test_file, generate_port_route_test, RouteTestCase};
+
+struct Parameter {
+    name: String,
+    annotation: String,
+    /// Default in the route signature, e.g. `Query(None, alias="pageSize")`.
+    route_default: Option<String>,
+    /// Plain default in the port signature.
+    port_default: Option<String>,
+}
+
+struct Handler {
+    name: String,
+    method: String,
+    path: String,
+    parameters: Vec<Parameter>,
+    returns: Option<String>,
+    decorator_options: Vec<String>,
+    description: Option<String>,
+    test: RouteTestCase,
+}
+
+struct Tag {
+    module: String,
+    name: String,
+    handlers: Vec<Handler>,
+}
+
+fn optional(annotation: &str) -> String {
+    if annotation.starts_with("Optional[") || annotation == "Any" || annotation == "None" {
+        annotation.to_string()
+    } else {
+        format!("Optional[{}]", annotation)
+    }
+}
+
+/// Renders `components/schemas` as Pydantic models; enums become `Enum` classes
+/// and other schemas type aliases.
+fn render_models(spec: &Spec, source: &str, warnings: &mut Vec<String>) -> (String, Vec<String>) {
+    let schemas = spec.schemas();
+    let mut enums = Vec::new();
+    let mut classes: Vec<(String, Vec<String>, String)> = Vec::new();
+    let mut aliases = Vec::new();
+    let mut names = Vec::new();
+
+    for (name, schema) in &schemas {
+        let class = class_name(name);
+        names.push(class.clone());
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            let is_string = values.iter().all(Value::is_string);
+            let mut members = String::new();
+            for value in values {
+                let member = match value {
+                    Value::String(text) => to_python_identifier(text).trim_start_matches('_').to_uppercase(),
+                    other => format!("VALUE_{}", other.to_string().replace(['-', '.'], "_")),
+                };
+                let member = if member.is_empty() { "EMPTY".to_string() } else { member };
+                members.push_str(&format!("    {} = {}\n", member, python_literal(value)));
+            }
+            let base = if is_string { "str, Enum" } else { "int, Enum" };
+            enums.push(format!("class {}({}):\n{}", class, base, members));
+            continue;
+        }
+
+        let parts: Vec<&Value> = match schema.get("allOf").and_then(Value::as_array) {
+            Some(parts) => parts.iter().collect(),
+            None => vec![*schema],
+        };
+        let is_object = parts.iter().any(|p| {
+            ref_name(p).is_some() || p.get("properties").is_some() || p.get("type").and_then(Value::as_str) == Some("object")
+        });
+        if !is_object || schema.get("additionalProperties").is_some_and(|a| a.is_object()) && schema.get("properties").is_none() {
+            aliases.push(format!("{} = {}\n", class, spec.python_type(schema)));
+            continue;
+        }
+
+        let mut bases = Vec::new();
+        let mut body = String::new();
+        if let Some(description) = schema.get("description").and_then(Value::as_str) {
+            body.push_str(&format!("    \"\"\"{}\"\"\"\n\n", description.trim().replace("\"\"\"", "'''")));
+        }
+        let mut taken = Vec::new();
+        for part in parts {
+            if let Some(base) = ref_name(part) {
+                bases.push(class_name(base));
+                continue;
+            }
+            let required: BTreeSet<&str> = part
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|r| r.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let Some(properties) = part.get("properties").and_then(Value::as_object) else {
+                continue;
+            };
+            for (wire, property) in properties {
+                let name = unique_name(to_field_name(wire), &taken);
+                body.push_str(&format!("    {}\n", render_field(spec, &name, wire, property, required.contains(wire.as_str()))));
+                taken.push(name);
+            }
+        }
+        if body.contains("alias=") {
+            body.push_str("\n    model_config = ConfigDict(populate_by_name=True)\n");
+        }
+        if body.is_empty() {
+            body.push_str("    pass\n");
+        }
+        if bases.is_empty() {
+            bases.push("BaseModel".to_string());
+        }
+        classes.push((class, bases, body));
+    }
+
+    // Subclasses must come after the models they extend.
+    let mut ordered: Vec<(String, Vec<String>, String)> = Vec::new();
+    while !classes.is_empty() {
+        let known: BTreeSet<String> = classes.iter().map(|(name, _, _)| name.clone()).collect();
+        let ready = classes
+            .iter()
+            .position(|(_, bases, _)| bases.iter().all(|b| !known.contains(b)))
+            .unwrap_or(0);
+        ordered.push(classes.remove(ready));
+    }
+    if ordered.is_empty() && enums.is_empty() && aliases.is_empty() {
+        warnings.push("No components/schemas found, no models generated".to_string());
+    }
+
+    let mut blocks: Vec<String> = enums;
+    blocks.extend(ordered.iter().map(|(class, bases, body)| format!("class {}({}):\n{}", class, bases.join(", "), body)));
+    let mut content = blocks.join("\n\n");
+    if !aliases.is_empty() {
+        content.push_str(&format!("\n\n{}", aliases.concat()));
+    }
+    if !ordered.is_empty() {
+        content.push_str(&format!(
+            "\n\n{}",
+            ordered.iter().map(|(class, _, _)| format!("{}.model_rebuild()\n", class)).collect::<String>()
+        ));
+    }
+
+    let mut imports = vec!["from __future__ import annotations\n".to_string()];
+    let mut standard = Vec::new();
+    let word = |w: &str| content.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|x| x == w);
+    let datetime: Vec<&str> = ["date", "datetime", "time"].into_iter().filter(|w| word(w)).collect();
+    if !datetime.is_empty() {
+        standard.push(format!("from datetime import {}", datetime.join(", ")));
+    }
+    if content.contains(", Enum)") {
+        standard.push("from enum import Enum".to_string());
+    }
+    let typing = typing_names(&[content.as_str()]);
+    if !typing.is_empty() {
+        standard.push(format!("from typing import {}", typing.join(", ")));
+    }
+    if word("UUID") {
+        standard.push("from uuid import UUID".to_string());
+    }
+    if !standard.is_empty() {
+        imports.push(standard.join("\n") + "\n");
+    }
+    let pydantic: Vec<&str> = ["BaseModel", "ConfigDict", "Field"].into_iter().filter(|w| word(w)).collect();
+    if !pydantic.is_empty() {
+        imports.push(format!("from pydantic import {}\n", pydantic.join(", ")));
+    }
+
+    (
+        format!("# 📄 Generated by faspi from {}\n{}\n\n{}", source, imports.join("\n"), content.trim_end()) + "\n",
+        names,
+    )
+}
+
+fn render_field(spec: &Spec, name: &str, wire: &str, property: &Value, required: bool) -> String {
+    let mut annotation = spec.python_type(property);
+    let schema = spec.resolve(property);
+    let mut options = Vec::new();
+    if name != wire {
+        options.push(format!("alias={}", python_literal(&Value::String(wire.to_string()))));
+    }
+    for (key, option) in [
+        ("description", "description"),
+        ("minLength", "min_length"),
+        ("maxLength", "max_length"),
+        ("minItems", "min_length"),
+        ("maxItems", "max_length"),
+        ("minimum", "ge"),
+        ("maximum", "le"),
+        ("pattern", "pattern"),
+    ] {
+        if let Some(value) = property.get(key).or_else(|| (key != "description").then(|| schema.get(key)).flatten()) {
+            options.push(format!("{}={}", option, python_literal(value)));
+        }
+    }
+    for (key, option) in [("exclusiveMinimum", "gt"), ("exclusiveMaximum", "lt")] {
+        if let Some(value) = property.get(key).filter(|v| v.is_number()) {
+            options.push(format!("{}={}", option, python_literal(value)));
+        }
+    }
+
+    let default = match property.get("default") {
+        Some(value) => Some(python_literal(value)),
+        None if required => None,
+        None => {
+            annotation = optional(&annotation);
+            Some("None".to_string())
+        }
+    };
+    if default.as_deref() == Some("None") {
+        annotation = optional(&annotation);
+    }
+    match (default, options.is_empty()) {
+        (None, true) => format!("{}: {}", name, annotation),
+        (Some(default), true) => format!("{}: {} = {}", name, annotation, default),
+        (default, false) => format!(
+            "{}: {} = Field({}, {})",
+            name,
+            annotation,
+            default.unwrap_or_else(|| "...".to_string()),
+            options.join(", ")
+        ),
+    }
+}
+
+fn handler_name(operation: &SpecOperation) -> String {
+    match operation.operation.get("operationId").and_then(Value::as_str) {
+        Some(id) => to_python_identifier(id),
+        None => to_python_identifier(&format!("{} {}", operation.method, operation.path.replace(['{', '}'], ""))),
+    }
+}
+
+fn build_handler(spec: &Spec, operation: &SpecOperation, warnings: &mut Vec<String>) -> Handler {
+    let name = handler_name(operation);
+    let mut path = operation.path.to_string();
+    let mut url = operation.path.to_string();
+    let mut required = Vec::new();
+    let mut defaulted = Vec::new();
+    let mut query = Vec::new();
+    let mut headers = Vec::new();
+    let mut taken = Vec::new();
+
+    for parameter in &operation.parameters {
+        let Some(wire) = parameter.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let location = parameter.get("in").and_then(Value::as_str).unwrap_or("query");
+        let schema = parameter.get("schema").unwrap_or(&Value::Null);
+        let mut annotation = spec.python_type(schema);
+        let is_required = location == "path" || parameter.get("required").and_then(Value::as_bool).unwrap_or(false);
+        let ident = if location == "path" { to_python_identifier(wire) } else { to_field_name(wire) };
+        let ident = unique_name(ident, &taken);
+        taken.push(ident.clone());
+        let sample = spec.sample(schema, 0);
+
+        let default = schema.get("default").map(python_literal).or_else(|| (!is_required).then(|| "None".to_string()));
+        if default.as_deref() == Some("None") {
+            annotation = optional(&annotation);
+        }
+        let alias = (ident != wire).then(|| python_literal(&Value::String(wire.to_string())));
+        let route_default = match location {
+            "path" => {
+                // Path parameter names never reach the wire, so rename them in the template.
+                path = path.replace(&format!("{{{}}}", wire), &format!("{{{}}}", ident));
+                let value = sample.as_str().map(str::to_string).unwrap_or_else(|| sample.to_string());
+                url = url.replace(&format!("{{{}}}", wire), &value);
+                None
+            }
+            "header" | "cookie" => {
+                let marker = if location == "header" { "Header" } else { "Cookie" };
+                if location == "header" && is_required {
+                    headers.push((wire.to_string(), sample.clone()));
+                }
+                let alias = alias.filter(|_| location == "cookie").map(|a| format!(", alias={}", a)).unwrap_or_default();
+                Some(format!("{}({}{})", marker, default.clone().unwrap_or_else(|| "...".to_string()), alias))
+            }
+            _ => {
+                if is_required {
+                    query.push((wire.to_string(), sample.clone()));
+                }
+                match (&alias, &default) {
+                    (Some(alias), default) => Some(format!(
+                        "Query({}, alias={})",
+                        default.clone().unwrap_or_else(|| "...".to_string()),
+                        alias
+                    )),
+                    (None, default) => default.clone(),
+                }
+            }
+        };
+        let parameter = Parameter {
+            name: ident,
+            annotation,
+            port_default: default.clone(),
+            route_default,
+        };
+        if parameter.route_default.is_some() {
+            defaulted.push(parameter);
+        } else {
+            required.push(parameter);
+        }
+    }
+
+    let mut body_sample = None;
+    if let Some((media, schema, is_required)) = spec.request_body(operation.operation) {
+        if !media.contains("json") {
+            warnings.push(format!(
+                "{} {}: '{}' request body generated as JSON",
+                operation.method.to_uppercase(),
+                operation.path,
+                media
+            ));
+        }
+        let name = ref_name(schema).map_or("body".to_string(), to_python_identifier);
+        let annotation = spec.python_type(schema);
+        body_sample = Some(spec.sample(schema, 0));
+        if is_required {
+            required.push(Parameter {
+                name,
+                annotation,
+                route_default: None,
+                port_default: None,
+            });
+        } else {
+            defaulted.push(Parameter {
+                name,
+                annotation: optional(&annotation),
+                route_default: Some("None".to_string()),
+                port_default: Some("None".to_string()),
+            });
+        }
+    }
+    required.extend(defaulted);
+
+    let mut decorator_options = Vec::new();
+    let mut returns = None;
+    let mut status = None;
+    let mut answer = None;
+    match spec.success_response(operation.operation) {
+        Some((code, schema)) => {
+            if code != "200" && code != "default" {
+                status = Some(code.to_string());
+            }
+            if let Some(schema) = schema.filter(|_| code != "204") {
+                let annotation = spec.python_type(schema);
+                decorator_options.push(format!("response_model={}", annotation));
+                returns = Some(annotation);
+                answer = Some(python_literal(&spec.sample(schema, 0)));
+            }
+        }
+        None => warnings.push(format!(
+            "{} {}: no success response declared",
+            operation.method.to_uppercase(),
+            operation.path
+        )),
+    }
+    if let Some(status) = &status {
+        decorator_options.push(format!("status_code={}", status));
+    }
+    if let Some(summary) = operation.operation.get("summary").and_then(Value::as_str) {
+        decorator_options.push(format!("summary={}", python_literal(&Value::String(summary.to_string()))));
+    }
+    if let Some(id) = operation.operation.get("operationId").and_then(Value::as_str) {
+        decorator_options.push(format!("operation_id={}", python_literal(&Value::String(id.to_string()))));
+    }
+    if operation.operation.get("deprecated").and_then(Value::as_bool) == Some(true) {
+        decorator_options.push("deprecated=True".to_string());
+    }
+
+    let literal_pairs = |pairs: &[(String, Value)]| {
+        (!pairs.is_empty()).then(|| {
+            format!(
+                "{{{}}}",
+                pairs
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", python_literal(&Value::String(k.clone())), python_literal(v)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+    };
+    let mut test = RouteTestCase {
+        name: name.clone(),
+        method: operation.method.to_uppercase(),
+        url,
+        params: literal_pairs(&query),
+        headers: literal_pairs(&headers),
+        json: body_sample.as_ref().map(python_literal),
+        status: status.clone().unwrap_or_else(|| "200".to_string()),
+        answer,
+    };
+    // Specs built from recordings carry the first recorded request, replayed as sent.
+    if let Some(recording) = operation.operation.get("x-recording") {
+        let recorded = |key: &str| -> Vec<(String, Value)> {
+            recording
+                .get(key)
+                .and_then(Value::as_array)
+                .map(|pairs| {
+                    pairs
+                        .iter()
+                        .filter_map(|pair| Some((pair.get(0)?.as_str()?.to_string(), pair.get(1)?.clone())))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        if let Some(url) = recording.get("url").and_then(Value::as_str) {
+            test.url = url.to_string();
+        }
+        let query = recorded("query");
+        let keys: BTreeSet<&str> = query.iter().map(|(k, _)| k.as_str()).collect();
+        test.params = if keys.len() < query.len() {
+            // Repeated keys (`?tag=a&tag=b`) only survive as a list of pairs.
+            let pairs: Vec<String> = query
+                .iter()
+                .map(|(k, v)| format!("({}, {})", python_literal(&Value::String(k.clone())), python_literal(v)))
+                .collect();
+            Some(format!("[{}]", pairs.join(", ")))
+        } else {
+            literal_pairs(&query)
+        };
+        test.headers = literal_pairs(&recorded("headers"));
+    }
+
+    Handler {
+        name,
+        method: operation.method.to_string(),
+        path,
+        parameters: required,
+        returns,
+        decorator_options,
+        description: operation
+            .operation
+            .get("description")
+            .and_then(Value::as_str)
+            .map(|d| d.trim().replace("\"\"\"", "'''")),
+        test,
+    }
+}
+
+fn render_router(tag: &Tag, port: &str, port_class: &str, models_module: &str, models: &[String]) -> String {
+    let dependency = format!("{0}: {1} = Depends(get_{0})", port, port_class);
+    let mut functions = Vec::new();
+    for handler in &tag.handlers {
+        let mut signature: Vec<String> = handler
+            .parameters
+            .iter()
+            .map(|p| match &p.route_default {
+                Some(default) => format!("{}: {} = {}", p.name, p.annotation, default),
+                None => format!("{}: {}", p.name, p.annotation),
+            })
+            .collect();
+        signature.push(dependency.clone());
+        let arguments: Vec<String> = handler.parameters.iter().map(|p| format!("{0}={0}", p.name)).collect();
+        let mut options = vec![python_literal(&Value::String(handler.path.clone()))];
+        options.extend(handler.decorator_options.iter().cloned());
+        let call = format!("{}.{}({})", port, handler.name, arguments.join(", "));
+        let body = if handler.returns.is_some() { format!("return {}", call) } else { call };
+        let docstring = handler
+            .description
+            .as_ref()
+            .map(|d| format!("    \"\"\"{}\"\"\"\n", d))
+            .unwrap_or_default();
+        functions.push(format!(
+            "@router.{}({})\ndef {}({}):\n{}    {}\n",
+            handler.method,
+            options.join(", "),
+            handler.name,
+            signature.join(", "),
+            docstring,
+            body
+        ));
+    }
+    let code = functions.join("\n\n");
+
+    let word = |w: &str| code.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|x| x == w);
+    let mut imports = Vec::new();
+    let datetime: Vec<&str> = ["date", "datetime", "time"].into_iter().filter(|w| word(w)).collect();
+    if !datetime.is_empty() {
+        imports.push(format!("from datetime import {}", datetime.join(", ")));
+    }
+    let typing = typing_names(&[code.as_str()]);
+    if !typing.is_empty() {
+        imports.push(format!("from typing import {}", typing.join(", ")));
+    }
+    if word("UUID") {
+        imports.push("from uuid import UUID".to_string());
+    }
+    let fastapi: Vec<&str> = ["APIRouter", "Cookie", "Depends", "Header", "Query"]
+        .into_iter()
+        .filter(|w| *w == "APIRouter" || word(w))
+        .collect();
+    imports.push(format!("from fastapi import {}", fastapi.join(", ")));
+    imports.push(format!("from app.container import get_{}", port));
+    let used: Vec<&str> = models.iter().map(String::as_str).filter(|m| word(m)).collect();
+    if !used.is_empty() {
+        imports.push(format!("from app.models.{} import {}", models_module, used.join(", ")));
+    }
+    imports.push(format!("from app.ports.{} import {}", port, port_class));
+
+    format!(
+        "{}\n\nrouter = APIRouter(tags=[{}])\n\n\n{}",
+        imports.join("\n"),
+        python_literal(&Value::String(tag.name.clone())),
+        code
+    )
+}
+
+pub fn import_openapi(spec_path: &str, models_module: &str, force: bool) -> Result<(), Box<dyn Error>> {
+    if !Path::new("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let spec = Spec::load(Path::new(spec_path))?;
+    import_spec(&spec, spec_path, models_module, Vec::new(), force)
+}
+
+/// Generates models, routers, ports, use cases and route tests from a spec;
+/// `warnings` are the ones already collected while building it.
+pub fn import_spec(
+    spec: &Spec,
+    spec_path: &str,
+    models_module: &str,
+    mut warnings: Vec<String>,
+    force: bool,
+) -> Result<(), Box<dyn Error>> {
+
+    let mut tags: Vec<Tag> = Vec::new();
+    for operation in spec.operations() {
+        let tag_name = operation
+            .operation
+            .get("tags")
+            .and_then(|t| t.get(0))
+            .and_then(Value::as_str)
+            .unwrap_or("default")
+            .to_string();
+        let handler = build_handler(spec, &operation, &mut warnings);
+        let module = to_python_identifier(&tag_name);
+        match tags.iter_mut().find(|t| t.module == module) {
+            Some(tag) => {
+                let mut handler = handler;
+                let base = handler.name.clone();
+                let mut counter = 2;
+                while tag.handlers.iter().any(|h| h.name == handler.name) {
+                    handler.name = format!("{}_{}", base, counter);
+                    counter += 1;
+                }
+                handler.test.name = handler.name.clone();
+                tag.handlers.push(handler);
+            }
+            None => tags.push(Tag {
+                module,
+                name: tag_name,
+                handlers: vec![handler],
+            }),
+        }
+    }
+    if tags.is_empty() {
+        return Err(format!("No operations found in '{}'", spec_path).into());
+    }
+
+    let models_path = Path::new("app").join("models").join(format!("{}.py", models_module));
+    let mut targets = vec![models_path.clone()];
+    for tag in &tags {
+        targets.push(Path::new("app").join("routes").join(format!("{}.py", tag.module)));
+        targets.push(Path::new("app").join("ports").join(format!("{}_port.py", tag.module)));
+        targets.push(Path::new("app").join("usecases").join(format!("{}_usecase.py", tag.module)));
+    }
+    let existing: Vec<String> = targets.iter().filter(|p| p.exists()).map(|p| p.display().to_string()).collect();
+    if !existing.is_empty() && !force {
+        return Err(format!("These files already exist, use --force to overwrite: {}", existing.join(", ")).into());
+    }
+
+    let source = Path::new(spec_path).file_name().map_or(spec_path.to_string(), |n| n.to_string_lossy().to_string());
+    let (models, classes) = render_models(spec, &source, &mut warnings);
+    fs::create_dir_all("app/models")?;
+    fs::write(&models_path, models)?;
+    append_import("app/models/__init__.py", &format!("from . import {}", models_module), models_module);
+    println!("✅ {} schema(s) created at '{}'", classes.len(), models_path.display());
+
+    for tag in &tags {
+        let port = format!("{}_port", tag.module);
+        let port_class = to_camel_case(&port);
+        let methods: Vec<String> = tag
+            .handlers
+            .iter()
+            .map(|handler| {
+                let params: Vec<String> = handler
+                    .parameters
+                    .iter()
+                    .map(|p| match &p.port_default {
+                        Some(default) => format!("{}: {} = {}", p.name, p.annotation, default),
+                        None => format!("{}: {}", p.name, p.annotation),
+                    })
+                    .collect();
+                format!("{}({}) -> {}", handler.name, params.join(", "), handler.returns.as_deref().unwrap_or("None"))
+            })
+            .collect();
+        create_port(&port, &methods)?;
+
+        let path = Path::new("app").join("routes").join(format!("{}.py", tag.module));
+        fs::write(&path, render_router(tag, &port, &port_class, models_module, &classes))?;
+        append_to_init("app/routes/__init__.py", &tag.module);
+        include_router(&Path::new("app").join("main.py"), &tag.module)?;
+        println!("✅ Route created at '{}' with {} operation(s)", path.display(), tag.handlers.len());
+
+        let cases: Vec<&RouteTestCase> = tag.handlers.iter().map(|h| &h.test).collect();
+        create_test_file("route", &tag.module, &generate_port_route_test(&tag.module, &port, &port_class, &cases, &[]));
+        create_usecase(&format!("{}_usecase", tag.module), std::slice::from_ref(&port), &[])?;
+    }
+
+    for warning in &warnings {
+        println!("⚠️  {}", warning);
+    }
+    println!(
+        "✅ Imported {} operation(s) in {} router(s) from '{}'",
+        tags.iter().map(|t| t.handlers.len()).sum::<usize>(),
+        tags.len(),
+        spec_path
+    );
+    Ok(())
+}
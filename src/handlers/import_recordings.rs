@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use serde_json::Value;
+use crate::handlers::import_openapi::import_spec;
+use crate::utils::recordings::{read_har, read_postman, recordings_spec, Recording};
+
+fn read_json(path: &str) -> Result<Value, Box<dyn Error>> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Cannot read '{}': {}", path, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("'{}' is not valid JSON: {}", path, e).into())
+}
+
+fn import_recordings(
+    path: &str,
+    title: &str,
+    recordings: &[Recording],
+    warnings: Vec<String>,
+    models_module: &str,
+    force: bool,
+) -> Result<(), Box<dyn Error>> {
+    if recordings.is_empty() {
+        return Err(format!("No API requests found in '{}'", path).into());
+    }
+    println!("📼 Read {} recorded request(s) from '{}'", recordings.len(), path);
+    let mut warnings = warnings;
+    let spec = recordings_spec(title, recordings, &mut warnings);
+    import_spec(&spec, path, models_module, warnings, force)
+}
+
+pub fn import_postman(collection: &str, models_module: &str, force: bool) -> Result<(), Box<dyn Error>> {
+    if !Path::new("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let value = read_json(collection)?;
+    if value.get("item").is_none() {
+        return Err(format!("'{}' is not a Postman collection (no 'item' field)", collection).into());
+    }
+    let mut warnings = Vec::new();
+    let recordings = read_postman(&value, &mut warnings);
+    let title = value.pointer("/info/name").and_then(Value::as_str).unwrap_or(collection);
+    import_recordings(collection, title, &recordings, warnings, models_module, force)
+}
+
+pub fn import_har(capture: &str, models_module: &str, force: bool) -> Result<(), Box<dyn Error>> {
+    if !Path::new("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let value = read_json(capture)?;
+    if value.pointer("/log/entries").is_none() {
+        return Err(format!("'{}' is not a HAR file (no 'log.entries' field)", capture).into());
+    }
+    let mut warnings = Vec::new();
+    let recordings = read_har(&value, &mut warnings);
+    import_recordings(capture, capture, &recordings, warnings, models_module, force)
+}
@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+use crate::utils::config::{Config, CONFIG_FILE};
+use crate::utils::fs::python_files;
+use crate::utils::python::{call_arguments, logical_lines, split_call, string_literal};
+use crate::utils::testing::test_path;
+
+/// Optional features of `faspi new` and the file each one generates.
+const FEATURES: [(&str, &str); 7] = [
+    ("sql", "app/core/database.py"),
+    ("auth", "app/core/auth"),
+    ("cors", "app/core/cors.py"),
+    ("cache", "app/core/cache.py"),
+    ("tasks", "app/core/tasks"),
+    ("supabase", "app/core/supabase.py"),
+    ("mongo", "app/core/mongo.py"),
+];
+
+/// Component kinds with a generated test, and the layer they live in.
+const COMPONENTS: [(&str, &str); 7] = [
+    ("route", "routes"),
+    ("model", "models"),
+    ("service", "services"),
+    ("core", "core"),
+    ("usecase", "usecases"),
+    ("port", "ports"),
+    ("adapter", "infrastructure"),
+];
+
+#[derive(Tabled)]
+struct FeatureRow {
+    #[tabled(rename = "Feature")]
+    name: String,
+    #[tabled(rename = "Enabled")]
+    enabled: String,
+    #[tabled(rename = "Files")]
+    files: String,
+}
+
+#[derive(Tabled)]
+struct LayerRow {
+    #[tabled(rename = "Layer")]
+    layer: String,
+    #[tabled(rename = "Package")]
+    package: String,
+    #[tabled(rename = "Count")]
+    count: usize,
+    #[tabled(rename = "Components")]
+    components: String,
+}
+
+#[derive(Tabled)]
+struct TestRow {
+    #[tabled(rename = "Kind")]
+    kind: String,
+    #[tabled(rename = "Component")]
+    name: String,
+    #[tabled(rename = "Test")]
+    test: String,
+}
+
+#[derive(Tabled)]
+struct DependencyRow {
+    #[tabled(rename = "Package")]
+    package: String,
+    #[tabled(rename = "Version")]
+    version: String,
+    #[tabled(rename = "Pinned")]
+    pinned: String,
+}
+
+#[derive(Tabled)]
+struct EnvRow {
+    #[tabled(rename = "Variable")]
+    name: String,
+    #[tabled(rename = "Default")]
+    default: String,
+    #[tabled(rename = ".env.example")]
+    example: String,
+    #[tabled(rename = ".env")]
+    env: String,
+    #[tabled(rename = "Used in")]
+    used_in: String,
+}
+
+fn mark(present: bool) -> String {
+    if present { "✅".to_string() } else { "—".to_string() }
+}
+
+fn package_dir(root: &Path, package: &str) -> PathBuf {
+    root.join(package.replace('.', "/"))
+}
+
+/// Module names directly inside a layer package (or the module itself when the
+/// layer is a single file like `app.container`).
+fn layer_modules(root: &Path, package: &str) -> Vec<String> {
+    let dir = package_dir(root, package);
+    if !dir.is_dir() {
+        return if dir.with_extension("py").exists() {
+            vec![package.rsplit('.').next().unwrap_or(package).to_string()]
+        } else {
+            Vec::new()
+        };
+    }
+    let mut modules: Vec<String> = fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    if path.is_dir() {
+                        let has_modules = !python_files(&path).is_empty();
+                        has_modules.then(|| path.file_name()?.to_str().map(str::to_string))?
+                    } else if path.extension().is_some_and(|e| e == "py") {
+                        path.file_stem()?.to_str().filter(|s| *s != "__init__").map(str::to_string)
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    modules.sort();
+    modules
+}
+
+/// Splits a requirements line into `(package, version specifier)`.
+fn parse_requirement(line: &str) -> Option<(String, String)> {
+    let line = line.split('#').next().unwrap_or("").trim();
+    if line.is_empty() || line.starts_with('-') {
+        return None;
+    }
+    let line = line.split(';').next().unwrap_or(line).trim();
+    match line.find(['=', '>', '<', '~', '!']) {
+        Some(index) => Some((line[..index].trim().to_string(), line[index..].trim().to_string())),
+        None => Some((line.to_string(), String::new())),
+    }
+}
+
+fn env_file_keys(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.trim_start_matches("export ").split_once('='))
+        .map(|(key, _)| key.trim().to_string())
+        .collect()
+}
+
+/// Finds `os.getenv("X", default)`, `os.environ.get(...)` and `os.environ["X"]`
+/// reads, returning variable -> (default, files).
+fn env_usages(root: &Path) -> BTreeMap<String, (Option<String>, Vec<String>)> {
+    let mut usages: BTreeMap<String, (Option<String>, Vec<String>)> = BTreeMap::new();
+    for path in python_files(&root.join("app")).into_iter().chain(python_files(&root.join("migrations"))) {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let file = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+        for (_, line) in logical_lines(&source) {
+            let mut found: Vec<(String, Option<String>)> = Vec::new();
+            for marker in ["getenv(", "environ.get("] {
+                for (index, _) in line.match_indices(marker) {
+                    let Some((_, args)) = split_call(&line[index..]) else {
+                        continue;
+                    };
+                    let (positional, _) = call_arguments(args);
+                    if let Some(name) = positional.first().and_then(|p| string_literal(p)) {
+                        let default = positional.get(1).map(|d| string_literal(d).unwrap_or_else(|| d.clone()));
+                        found.push((name, default));
+                    }
+                }
+            }
+            for (index, _) in line.match_indices("environ[") {
+                let rest = &line[index + "environ[".len()..];
+                if let Some(name) = rest.split(']').next().and_then(string_literal) {
+                    found.push((name, None));
+                }
+            }
+            for (name, default) in found {
+                let entry = usages.entry(name).or_default();
+                if entry.0.is_none() {
+                    entry.0 = default;
+                }
+                if !entry.1.contains(&file) {
+                    entry.1.push(file.clone());
+                }
+            }
+        }
+    }
+    usages
+}
+
+fn print_table<T: Tabled>(title: &str, rows: Vec<T>, empty: &str) {
+    println!("\n{}", title);
+    if rows.is_empty() {
+        println!("   {}", empty);
+    } else {
+        println!("{}", Table::new(rows).with(Style::rounded()));
+    }
+}
+
+pub fn inspect_project() -> Result<(), Box<dyn Error>> {
+    let root = Path::new(".");
+    if !root.join("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let config = Config::load(root)?;
+    let name = std::env::current_dir()?
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let configured = root.join(CONFIG_FILE).exists();
+    println!(
+        "🔍 Inspecting '{}'{}",
+        name,
+        if configured { format!(" (with {})", CONFIG_FILE) } else { String::new() }
+    );
+
+    let features = FEATURES
+        .iter()
+        .map(|(feature, file)| {
+            let listed = config.features.iter().any(|f| f == feature);
+            let exists = root.join(file).exists();
+            let enabled = match (listed, exists) {
+                (true, true) => "✅ faspi.toml".to_string(),
+                (true, false) => "⚠️  listed, files missing".to_string(),
+                (false, true) => "✅ detected".to_string(),
+                (false, false) => "—".to_string(),
+            };
+            FeatureRow {
+                name: feature.to_string(),
+                enabled,
+                files: if exists { file.to_string() } else { String::new() },
+            }
+        })
+        .collect();
+    print_table("✨ Features", features, "");
+
+    let layers = config
+        .layout
+        .iter()
+        .map(|(layer, package)| {
+            let modules = layer_modules(root, package);
+            LayerRow {
+                layer: layer.clone(),
+                package: package.clone(),
+                count: modules.len(),
+                components: modules.join(", "),
+            }
+        })
+        .collect();
+    print_table("🧩 Components per layer", layers, "");
+
+    let mut tests = Vec::new();
+    for (kind, layer) in COMPONENTS {
+        let Some(package) = config.layout.get(layer) else {
+            continue;
+        };
+        if package_dir(root, package).is_file() {
+            continue;
+        }
+        for module in layer_modules(root, package) {
+            let path = test_path(kind, &module);
+            tests.push(TestRow {
+                kind: kind.to_string(),
+                test: if root.join(&path).exists() {
+                    format!("✅ {}", path.display())
+                } else {
+                    "❌ missing".to_string()
+                },
+                name: module,
+            });
+        }
+    }
+    let covered = tests.iter().filter(|t| t.test.starts_with('✅')).count();
+    let total = tests.len();
+    print_table("🧪 Tests per component", tests, "No components found");
+    if total > 0 {
+        println!("   {}/{} components have a test file", covered, total);
+    }
+
+    let dependencies: Vec<DependencyRow> = fs::read_to_string(root.join("requirements.txt"))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(parse_requirement)
+        .map(|(package, version)| DependencyRow {
+            pinned: if version.starts_with("==") { "📌".to_string() } else { "⚠️  no".to_string() },
+            version: version.trim_start_matches("==").to_string(),
+            package,
+        })
+        .collect();
+    print_table("📦 Dependencies (requirements.txt)", dependencies, "No requirements.txt found");
+
+    let example = env_file_keys(&root.join(".env.example"));
+    let local = env_file_keys(&root.join(".env"));
+    let mut variables = env_usages(root);
+    for key in example.iter().chain(&local) {
+        variables.entry(key.clone()).or_default();
+    }
+    let env = variables
+        .into_iter()
+        .map(|(name, (default, files))| EnvRow {
+            default: default.unwrap_or_default(),
+            example: mark(example.contains(&name)),
+            env: mark(local.contains(&name)),
+            used_in: if files.is_empty() { "⚠️  unused".to_string() } else { files.join(", ") },
+            name,
+        })
+        .collect();
+    print_table("🔐 Environment variables", env, "No environment variables found");
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use crate::utils::config::{Config, CONFIG_FILE};
+
+pub const IMPORT_LINTER_FILE: &str = ".importlinter";
+pub const IMPORT_LINTER_REQUIREMENT: &str = "import-linter==2.1";
+
+/// Renders one `forbidden` contract per layer rule in `faspi.toml`, so import-linter
+/// enforces the same rules as `faspi check architecture`: direct imports only, since
+/// routes reach the adapters through `app.container`, and ports name their models
+/// under `TYPE_CHECKING`.
+pub fn render_import_linter(root: &Path, config: &Config) -> String {
+    let root_packages: BTreeSet<&str> = config
+        .layout
+        .values()
+        .filter_map(|module| module.split('.').next())
+        .collect();
+
+    let mut content = format!(
+        "# Generated by faspi from {}, refreshed when [layout] or [architecture] change\n[importlinter]\nroot_packages =\n{}include_external_packages = True\nexclude_type_checking_imports = True\n",
+        CONFIG_FILE,
+        root_packages.iter().map(|p| format!("    {}\n", p)).collect::<String>()
+    );
+
+    for rule in &config.architecture.rules {
+        let Some(source) = config.layout.get(&rule.layer) else {
+            continue;
+        };
+        let forbidden: Vec<&str> = rule
+            .forbid
+            .iter()
+            .map(|f| config.layout.get(f).map_or(f.as_str(), String::as_str))
+            .collect();
+        let mut ignored = Vec::new();
+        for path in &config.architecture.exclude {
+            let path = path.trim_end_matches('/');
+            let module = path.trim_end_matches(".py").replace('/', ".");
+            if module != *source && !module.starts_with(&format!("{}.", source)) {
+                continue;
+            }
+            let mut modules = vec![module.clone()];
+            if root.join(path).is_dir() {
+                modules.push(format!("{}.*", module));
+            }
+            for module in modules {
+                ignored.extend(forbidden.iter().map(|f| format!("{} -> {}", module, f)));
+            }
+        }
+
+        content.push_str(&format!(
+            "\n[importlinter:contract:{0}]\nname = {0} must not import {1}\ntype = forbidden\nsource_modules =\n    {2}\nforbidden_modules =\n{3}allow_indirect_imports = True\n",
+            rule.layer,
+            rule.forbid.join(", "),
+            source,
+            forbidden.iter().map(|f| format!("    {}\n", f)).collect::<String>()
+        ));
+        if !ignored.is_empty() {
+            content.push_str(&format!(
+                "ignore_imports =\n{}unmatched_ignore_imports_alerting = none\n",
+                ignored.iter().map(|i| format!("    {}\n", i)).collect::<String>()
+            ));
+        }
+    }
+    content
+}
+
+pub fn add_lint(root: &Path) -> Result<(), Box<dyn Error>> {
+    let config = Config::load(root)?;
+    if !root.join(CONFIG_FILE).exists() {
+        config.save(root)?;
+        println!("⚙️  Created '{}'", root.join(CONFIG_FILE).display());
+    }
+    create_import_linter(root, &config)?;
+
+    let requirements = root.join("requirements.txt");
+    let content = fs::read_to_string(&requirements).unwrap_or_default();
+    if !content.lines().any(|l| l.starts_with("import-linter")) {
+        let separator = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
+        fs::write(&requirements, format!("{}{}{}\n", content, separator, IMPORT_LINTER_REQUIREMENT))?;
+        println!("📦 Added '{}' to '{}'", IMPORT_LINTER_REQUIREMENT, requirements.display());
+    }
+    println!("ℹ️  Run 'lint-imports' to enforce the contracts in CI");
+    Ok(())
+}
+
+fn write_import_linter(root: &Path, config: &Config) -> Result<bool, Box<dyn Error>> {
+    let path = root.join(IMPORT_LINTER_FILE);
+    let content = render_import_linter(root, config);
+    if fs::read_to_string(&path).ok().as_deref() == Some(content.as_str()) {
+        return Ok(false);
+    }
+    fs::write(&path, content)?;
+    Ok(true)
+}
+
+pub fn create_import_linter(root: &Path, config: &Config) -> Result<(), Box<dyn Error>> {
+    if write_import_linter(root, config)? {
+        let path = root.join(IMPORT_LINTER_FILE);
+        println!("🔗 Updated '{}'", path.strip_prefix(".").unwrap_or(&path).display());
+    }
+    Ok(())
+}
+
+/// Regenerates `.importlinter` when the project opted in and `faspi.toml` changed
+/// since it was written. Reports on stderr to keep machine-readable output clean.
+pub fn refresh_import_linter(root: &Path) {
+    if !root.join(IMPORT_LINTER_FILE).exists() || !root.join(CONFIG_FILE).exists() {
+        return;
+    }
+    match Config::load(root).and_then(|config| write_import_linter(root, &config)) {
+        Ok(true) => eprintln!("🔗 Updated '{}' from '{}'", IMPORT_LINTER_FILE, CONFIG_FILE),
+        Ok(false) => {}
+        Err(e) => eprintln!("⚠️  Could not refresh '{}': {}", IMPORT_LINTER_FILE, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_project_contracts_only_forbid_direct_imports() {
+        let rendered = render_import_linter(Path::new("missing"), &Config::with_exclude(Vec::new()));
+        assert!(rendered.contains("root_packages =\n    app\n"), "{}", rendered);
+        assert!(rendered.contains("exclude_type_checking_imports = True\n"), "{}", rendered);
+
+        let contracts: Vec<&str> = rendered.split("\n[importlinter:contract:").skip(1).collect();
+        assert_eq!(contracts.len(), Config::default().architecture.rules.len());
+        for contract in &contracts {
+            assert!(contract.contains("type = forbidden\n"), "{}", contract);
+            assert!(contract.ends_with("allow_indirect_imports = True\n"), "{}", contract);
+        }
+        assert!(
+            rendered.contains("[importlinter:contract:routes]\nname = routes must not import infrastructure"),
+            "{}",
+            rendered
+        );
+    }
+
+    #[test]
+    fn excluded_modules_become_ignored_imports() {
+        let rendered = render_import_linter(Path::new("missing"), &Config::with_exclude(vec!["app/core/cors.py".into()]));
+        let core = rendered.split("[importlinter:contract:core]").nth(1).unwrap();
+        assert!(core.contains("ignore_imports =\n    app.core.cors -> "), "{}", core);
+        assert!(core.contains("allow_indirect_imports = True\nignore_imports"), "{}", core);
+    }
+}
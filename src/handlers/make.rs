@@ -1,6 +1,17 @@
-use std::path::Path;
+use std::error::Error;
+use std::path::{Path, PathBuf};
 use std::fs;
-use crate::utils::fs::append_to_init;
+use serde_json::Value;
+use crate::handlers::container::sync_container;
+use crate::handlers::import_db::{create_crud, crud_paths, ensure_writable, import_tables, Store};
+use crate::utils::config::Config;
+use crate::utils::fs::{append_import, append_to_init, python_files};
+use crate::utils::infer::{infer_models, render_models};
+use crate::utils::python::{
+    find_classes, find_port_class, is_identifier, parse_abstract_methods, referenced_types, typing_names, MethodSpec,
+};
+use crate::utils::tables::{render_document, Column, Table};
+use crate::utils::string::to_camel_case;
 use crate::utils::testing::*;
 
 pub fn create_route(name: &str, method: Option<String>) {
@@ -9,32 +20,44 @@ pub fn create_route(name: &str, method: Option<String>) {
 
     let http_method = method.unwrap_or_else(|| "GET".to_string());
 
-    fs::write(
-        &path,
-        format!(
-            r#"from fastapi import APIRouter, Depends
-from app.infrastructure.example_impl import ExampleImpl
+    fs::write(&path, route_template(name, &http_method)).expect("❌ Failed to write route file");
+
+    append_to_init("app/routes/__init__.py", name);
+    println!("✅ Route created at '{}'", path.display());
+
+    let test_content = generate_route_test(name, &http_method);
+    create_test_file("route", name, &test_content);
+    sync_container(Path::new("."));
+}
+
+pub fn route_template(name: &str, http_method: &str) -> String {
+    format!(
+        r#"from fastapi import APIRouter, Depends
+from app.container import get_example_port
+from app.ports.example_port import ExamplePort
 
 router = APIRouter()
 
 @router.{0}("/{1}")
-def read_{1}(service: ExampleImpl = Depends()):
+def read_{1}(service: ExamplePort = Depends(get_example_port)):
     return {{"message": service.get_data()}}
 "#,
-            http_method.to_lowercase(),
-            name
-        ),
+        http_method.to_lowercase(),
+        name
     )
-    .expect("❌ Failed to write route file");
-
-    append_to_init("app/routes/__init__.py", name);
-    println!("✅ Route created at '{}'", path.display());
-
-    let test_content = generate_route_test(name, &http_method);
-    create_test_file("route", name, &test_content);
 }
 
 pub fn create_model(name: &str) {
+    if project_stores().contains(&Store::Mongo) {
+        let table = Table {
+            name: name.to_string(),
+            columns: Vec::new(),
+            foreign_keys: Vec::new(),
+            indexes: Vec::new(),
+        };
+        write_document(&table, &format!("# Model: {}", name)).expect("❌ Failed to write model file");
+        return;
+    }
     let path = Path::new("app").join("models").join(format!("{}.py", name));
     fs::create_dir_all("app/models").ok();
 
@@ -51,13 +74,173 @@ def {0}_example():
     )
     .expect("❌ Failed to write model file");
 
-    append_to_init("app/models/__init__.py", name);
+    append_import("app/models/__init__.py", &format!("from . import {}", name), name);
     println!("✅ Model created at '{}'", path.display());
 
     let test_content = generate_model_test(name);
     create_test_file("model", name, &test_content);
 }
 
+/// Data stores of the project, recorded in faspi.toml or detected from their modules.
+fn project_stores() -> Vec<Store> {
+    let features = Config::load(Path::new(".")).map(|config| config.features).unwrap_or_default();
+    [(Store::Sql, "sql", "app/core/database.py"), (Store::Mongo, "mongo", "app/core/mongo.py")]
+        .into_iter()
+        .filter(|(_, feature, file)| features.iter().any(|f| f == feature) || Path::new(file).exists())
+        .map(|(store, _, _)| store)
+        .collect()
+}
+
+/// Beanie document with its schemas, registered in app/models and tested.
+fn write_document(table: &Table, header: &str) -> Result<(), Box<dyn Error>> {
+    let module = table.module();
+    let path = Path::new("app").join("models").join(format!("{}.py", module));
+    fs::create_dir_all("app/models")?;
+    fs::write(&path, render_document(header, table))?;
+    append_import("app/models/__init__.py", &format!("from . import {}", module), &module);
+    println!("✅ Document '{}' created at '{}'", table.class(), path.display());
+    create_test_file("model", &module, &generate_document_test(&module, &table.class(), &table.name));
+    Ok(())
+}
+
+/// Column for the `name:type` shorthand of `make resource`, optional with a trailing `?`.
+fn parse_field(spec: &str) -> Result<Column, String> {
+    let (name, annotation) = spec
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid field '{}', expected name:type", spec))?;
+    let name = name.trim();
+    if !is_identifier(name) {
+        return Err(format!("Invalid field name '{}'", name));
+    }
+    let (annotation, nullable) = match annotation.trim().strip_suffix('?') {
+        Some(annotation) => (annotation.trim(), true),
+        None => (annotation.trim(), false),
+    };
+    let data_type = match annotation.to_lowercase().as_str() {
+        "str" => "VARCHAR",
+        "text" => "TEXT",
+        "int" => "INTEGER",
+        "float" => "FLOAT",
+        "bool" => "BOOLEAN",
+        "decimal" => "NUMERIC",
+        "date" => "DATE",
+        "datetime" => "TIMESTAMP",
+        "uuid" => "UUID",
+        "dict" | "json" => "JSON",
+        "bytes" => "BLOB",
+        _ => {
+            return Err(format!(
+                "Unknown type '{}' for field '{}', use str, text, int, float, bool, decimal, date, datetime, uuid, json or bytes",
+                annotation, name
+            ));
+        }
+    };
+    Ok(Column {
+        name: name.to_string(),
+        data_type: data_type.to_string(),
+        nullable,
+        default: None,
+        primary_key: false,
+        unique: false,
+    })
+}
+
+/// Model, repository port, adapter, router and tests for a collection, stored
+/// with SQLAlchemy or Beanie depending on the project.
+pub fn create_resource(name: &str, fields: &[String], store: Option<&str>, force: bool) -> Result<(), Box<dyn Error>> {
+    if !Path::new("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let stores = project_stores();
+    let store = match (store, stores.as_slice()) {
+        (Some("mongo"), _) => Store::Mongo,
+        (Some(_), _) => Store::Sql,
+        (None, [store]) => *store,
+        (None, []) => return Err("No database found, create the project with --sql or --mongo".into()),
+        (None, _) => return Err("The project uses both SQL and MongoDB, pick one with --store".into()),
+    };
+    let mut columns = fields.iter().map(|f| parse_field(f)).collect::<Result<Vec<_>, _>>()?;
+    if columns.iter().any(|c| c.name == "id") {
+        return Err("The 'id' field is added automatically, leave it out of --field".into());
+    }
+
+    let origin = "generated by faspi make resource";
+    match store {
+        Store::Sql => {
+            columns.insert(
+                0,
+                Column {
+                    name: "id".to_string(),
+                    data_type: "INTEGER".to_string(),
+                    nullable: false,
+                    default: None,
+                    primary_key: true,
+                    unique: false,
+                },
+            );
+            let table = Table {
+                name: name.to_string(),
+                columns,
+                foreign_keys: Vec::new(),
+                indexes: Vec::new(),
+            };
+            import_tables(&[table], origin, true, force)?;
+        }
+        Store::Mongo => {
+            if !stores.contains(&Store::Mongo) {
+                println!("⚠️  'app/core/mongo.py' not found, create the project with --mongo to initialize Beanie");
+            }
+            let table = Table {
+                name: name.to_string(),
+                columns,
+                foreign_keys: Vec::new(),
+                indexes: Vec::new(),
+            };
+            let mut paths = crud_paths(&table, Store::Mongo);
+            paths.push(Path::new("app").join("models").join(format!("{}.py", table.module())));
+            ensure_writable(&paths, force)?;
+            write_document(&table, &format!("# Model: {}, {}", table.module(), origin))?;
+            create_crud(&table, Store::Mongo)?;
+        }
+    }
+    println!("✅ Resource '{}' created", name);
+    Ok(())
+}
+
+/// Model inferred from JSON samples; the samples are copied next to the test,
+/// which checks that each one still parses.
+pub fn create_model_from_json(name: &str, samples: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut values = Vec::new();
+    for sample in samples {
+        let content = fs::read_to_string(sample).map_err(|e| format!("Cannot read '{}': {}", sample, e))?;
+        let value: Value = serde_json::from_str(&content).map_err(|e| format!("Invalid JSON in '{}': {}", sample, e))?;
+        values.push(value);
+    }
+    let class = to_camel_case(name);
+    let models = infer_models(&class, &values)?;
+
+    let path = Path::new("app").join("models").join(format!("{}.py", name));
+    fs::create_dir_all("app/models")?;
+    let header = format!("# Model: {}, inferred from {}", name, samples.join(", "));
+    fs::write(&path, render_models(&header, &models))?;
+    append_import("app/models/__init__.py", &format!("from . import {}", name), name);
+    println!("✅ Model created at '{}' with {} class(es)", path.display(), models.len());
+
+    let sample_dir = Path::new(test_dir("model")).join("samples");
+    fs::create_dir_all(&sample_dir)?;
+    let mut files = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        let file = if index == 0 { format!("{}.json", name) } else { format!("{}_{}.json", name, index + 1) };
+        fs::write(sample_dir.join(&file), format!("{}\n", serde_json::to_string_pretty(value)?))?;
+        files.push(file);
+    }
+    println!("📄 Sample(s) copied to '{}'", sample_dir.display());
+
+    let test_content = generate_model_sample_test(name, &class, &files);
+    create_test_file("model", name, &test_content);
+    Ok(())
+}
+
 pub fn create_component(folder: &str, name: &str, comment: &str, suffix: &str) {
     let dir = Path::new("app").join(folder);
     let path = dir.join(format!("{}.py", name));
@@ -75,9 +258,9 @@ def {0}_{3}():
             name, folder, comment, suffix
         ),
     )
-    .expect(&format!("❌ Failed to write {}", suffix));
+    .unwrap_or_else(|_| panic!("❌ Failed to write {}", suffix));
 
-    append_to_init(&format!("app/{}/__init__.py", folder), name);
+    append_import(&format!("app/{}/__init__.py", folder), &format!("from . import {}", name), name);
     println!("✅ {} created at '{}'", suffix.to_uppercase(), path.display());
 
     let test_content = match suffix {
@@ -87,3 +270,380 @@ def {0}_{3}():
     };
     create_test_file(suffix, name, &test_content);
 }
+
+pub fn create_port(name: &str, methods: &[String]) -> Result<(), Box<dyn Error>> {
+    let methods = methods
+        .iter()
+        .map(|m| MethodSpec::parse(m))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let class = to_camel_case(name);
+    let path = Path::new("app").join("ports").join(format!("{}.py", name));
+    fs::create_dir_all("app/ports")?;
+
+    let mut body = String::new();
+    for method in &methods {
+        body.push_str(&format!("    @abstractmethod\n    {}\n        pass\n\n", method.signature()));
+    }
+    if body.is_empty() {
+        body.push_str("    pass\n");
+        println!("ℹ️  No methods given, add them with --method 'name(arg:type)->Type'");
+    }
+
+    let annotations: Vec<&str> = methods.iter().flat_map(|m| m.annotations()).collect();
+    let (typing, guarded) = type_checking_imports(&annotations);
+    fs::write(
+        &path,
+        format!(
+            "from __future__ import annotations\n\nfrom abc import ABC, abstractmethod\n{}{}\nclass {}(ABC):\n{}",
+            typing,
+            guarded,
+            class,
+            body.trim_end_matches('\n').to_string() + "\n"
+        ),
+    )?;
+
+    append_import(
+        "app/ports/__init__.py",
+        &format!("from .{} import {}", name, class),
+        name,
+    );
+    println!("✅ Port created at '{}'", path.display());
+
+    let test_content = generate_port_test(name, &class, &methods);
+    create_test_file("port", name, &test_content);
+    create_fake(name)?;
+    sync_container(Path::new("."));
+    Ok(())
+}
+
+pub fn create_adapter(name: &str, port: &str) -> Result<(), Box<dyn Error>> {
+    let port_path = Path::new("app").join("ports").join(format!("{}.py", port));
+    let port_source = fs::read_to_string(&port_path)
+        .map_err(|_| format!("Port '{}' not found at '{}'", port, port_path.display()))?;
+    let port_class = find_port_class(&port_path)
+        .ok_or_else(|| format!("No port class found in '{}'", port_path.display()))?;
+    let methods = parse_abstract_methods(&port_source, &port_class);
+
+    let class = to_camel_case(name);
+    let path = Path::new("app").join("infrastructure").join(format!("{}.py", name));
+    fs::create_dir_all("app/infrastructure")?;
+
+    let mut body = String::new();
+    for method in &methods {
+        body.push_str(&format!(
+            "    {}\n        raise NotImplementedError(\"{}.{} is not implemented yet\")\n\n",
+            method.signature(),
+            class,
+            method.name
+        ));
+    }
+    if body.is_empty() {
+        body.push_str("    pass\n");
+    }
+
+    let annotations: Vec<&str> = methods.iter().flat_map(|m| m.annotations()).collect();
+    let (typing, guarded) = type_checking_imports(&annotations);
+    fs::write(
+        &path,
+        format!(
+            "from __future__ import annotations\n\n{}from app.ports.{} import {}\n{}\nclass {}({}):\n{}",
+            typing,
+            port,
+            port_class,
+            guarded,
+            class,
+            port_class,
+            body.trim_end_matches('\n').to_string() + "\n"
+        ),
+    )?;
+
+    append_import(
+        "app/infrastructure/__init__.py",
+        &format!("from .{} import {}", name, class),
+        name,
+    );
+    println!("✅ Adapter created at '{}'", path.display());
+
+    let test_content = generate_adapter_test(name, &class, port, &port_class, &methods);
+    create_test_file("adapter", name, &test_content);
+    register_contract_adapter(port, &format!("from app.infrastructure.{} import {}", name, class), &class);
+    sync_container(Path::new("."));
+    Ok(())
+}
+
+/// Imports for annotation types: `typing` helpers plus classes defined in
+/// `app/models`, guarded by `TYPE_CHECKING` since generated modules postpone
+/// annotation evaluation.
+fn type_checking_imports(annotations: &[&str]) -> (String, String) {
+    let mut imports = Vec::new();
+    for model in python_files(Path::new("app/models")) {
+        let Ok(source) = fs::read_to_string(&model) else {
+            continue;
+        };
+        let module = model.file_stem().unwrap().to_string_lossy().to_string();
+        let classes = find_classes(&source);
+        for ty in referenced_types(annotations) {
+            if classes.iter().any(|c| c.name == ty) {
+                imports.push(format!("    from app.models.{} import {}\n", module, ty));
+            }
+        }
+    }
+
+    let mut typing = typing_names(annotations);
+    if !imports.is_empty() {
+        typing.insert(0, "TYPE_CHECKING");
+    }
+    let typing_line = if typing.is_empty() {
+        String::new()
+    } else {
+        format!("from typing import {}\n", typing.join(", "))
+    };
+    let guarded = if imports.is_empty() {
+        String::new()
+    } else {
+        format!("\nif TYPE_CHECKING:\n{}", imports.concat())
+    };
+    (typing_line, guarded)
+}
+
+pub fn create_fake(port: &str) -> Result<(), Box<dyn Error>> {
+    let port_path = Path::new("app").join("ports").join(format!("{}.py", port));
+    let port_source = fs::read_to_string(&port_path)
+        .map_err(|_| format!("Port '{}' not found at '{}'", port, port_path.display()))?;
+    let port_class = find_port_class(&port_path)
+        .ok_or_else(|| format!("No port class found in '{}'", port_path.display()))?;
+    let methods = parse_abstract_methods(&port_source, &port_class);
+
+    let class = format!("Fake{}", port_class);
+    let path = fake_path(port);
+    fs::create_dir_all("tests/fakes")?;
+    for init in ["tests/__init__.py", "tests/fakes/__init__.py"] {
+        if !Path::new(init).exists() {
+            fs::write(init, "")?;
+        }
+    }
+
+    let mut body = String::from(
+        r#"    """In-memory implementation for tests: records every call in `calls`, keeps
+    saved values in `items` and answers other methods from `returns`."""
+
+    def __init__(self):
+        self.items = {}
+        self.calls = []
+        self.returns = {}
+
+    def _key(self, value):
+        if isinstance(value, dict) and "id" in value:
+            return value["id"]
+        return getattr(value, "id", None) or len(self.items) + 1
+"#,
+    );
+    for method in &methods {
+        body.push_str(&format!("\n    {}\n{}", method.signature(), fake_method_body(method)));
+    }
+
+    let annotations: Vec<&str> = methods.iter().flat_map(|m| m.annotations()).collect();
+    let (typing, guarded) = type_checking_imports(&annotations);
+    fs::write(
+        &path,
+        format!(
+            "from __future__ import annotations\n\n{}from app.ports.{} import {}\n{}\n\nclass {}({}):\n{}",
+            typing, port, port_class, guarded, class, port_class, body
+        ),
+    )?;
+    println!("🧪 Fake created at '{}'", path.display());
+    register_contract_adapter(port, &format!("from tests.fakes.fake_{} import {}", port, class), &class);
+
+    add_fixture(
+        Path::new("."),
+        &format!("fake_{}", port),
+        &format!(
+            "@pytest.fixture\ndef fake_{0}():\n    from tests.fakes.fake_{0} import {1}\n\n    return {1}()\n",
+            port, class
+        ),
+    );
+    Ok(())
+}
+
+pub fn fake_path(port: &str) -> PathBuf {
+    Path::new("tests").join("fakes").join(format!("fake_{}.py", port))
+}
+
+/// Picks an in-memory behaviour from the method name: reads look values up in
+/// `items`, writes store them (`update(key, data)` stores `data` under `key`),
+/// anything else returns whatever was put in `returns`.
+fn fake_method_body(method: &MethodSpec) -> String {
+    let args: Vec<&str> = method
+        .params
+        .iter()
+        .map(|p| p.name.trim_start_matches('*'))
+        .filter(|n| !n.is_empty())
+        .collect();
+    let first = args.first().copied();
+    let returns = method.returns.as_deref().unwrap_or("None").trim();
+    let returns_list = ["list", "List", "Sequence", "Iterable"]
+        .iter()
+        .any(|t| returns.starts_with(t));
+    let verb = method.name.split('_').next().unwrap_or("");
+
+    let mut body = format!(
+        "        self.calls.append((\"{}\", ({})))\n",
+        method.name,
+        args.iter().map(|a| format!("{},", a)).collect::<String>()
+    );
+    let logic = match (verb, first) {
+        ("list" | "all" | "search" | "query", _) => "        return list(self.items.values())\n".to_string(),
+        ("count", _) => "        return len(self.items)\n".to_string(),
+        ("get" | "find" | "fetch" | "load" | "read", _) if returns_list => {
+            "        return list(self.items.values())\n".to_string()
+        }
+        ("get" | "find" | "fetch" | "load" | "read", Some(key)) => format!("        return self.items.get({})\n", key),
+        ("exists" | "has" | "contains", Some(key)) => format!("        return {} in self.items\n", key),
+        ("save" | "add" | "create" | "insert" | "put" | "store" | "update" | "upsert", Some(first)) => {
+            let value = args.last().copied().unwrap_or(first);
+            let result = match returns {
+                "None" => String::new(),
+                "int" | "str" => "        return key\n".to_string(),
+                "bool" => "        return True\n".to_string(),
+                _ => format!("        return {}\n", value),
+            };
+            let key = if args.len() == 1 { format!("self._key({})", value) } else { first.to_string() };
+            format!("        key = {}\n        self.items[key] = {}\n{}", key, value, result)
+        }
+        ("delete" | "remove", Some(key)) => {
+            let result = if returns == "bool" { "        return removed is not None\n" } else { "" };
+            format!("        removed = self.items.pop({}, None)\n{}", key, result)
+        }
+        _ if returns == "None" => String::new(),
+        _ => format!("        return self.returns.get(\"{}\")\n", method.name),
+    };
+    body.push_str(&logic);
+    body
+}
+
+pub fn create_contract(port: &str) -> Result<(), Box<dyn Error>> {
+    let port_path = Path::new("app").join("ports").join(format!("{}.py", port));
+    let port_source = fs::read_to_string(&port_path)
+        .map_err(|_| format!("Port '{}' not found at '{}'", port, port_path.display()))?;
+    let port_class = find_port_class(&port_path)
+        .ok_or_else(|| format!("No port class found in '{}'", port_path.display()))?;
+    let methods = parse_abstract_methods(&port_source, &port_class);
+
+    let test_content = generate_contract_test(port, &port_class, &methods);
+    create_test_file("contract", &format!("{}_contract", port), &test_content);
+
+    for path in python_files(Path::new("app/infrastructure")) {
+        let source = fs::read_to_string(&path).unwrap_or_default();
+        let module = path.file_stem().unwrap().to_string_lossy().to_string();
+        for class in find_classes(&source).into_iter().filter(|c| c.bases.contains(&port_class)) {
+            register_contract_adapter(port, &format!("from app.infrastructure.{} import {}", module, class.name), &class.name);
+        }
+    }
+    if fake_path(port).exists() {
+        let fake = format!("Fake{}", port_class);
+        register_contract_adapter(port, &format!("from tests.fakes.fake_{} import {}", port, fake), &fake);
+    }
+    Ok(())
+}
+
+fn contract_path(port: &str) -> PathBuf {
+    test_path("contract", &format!("{}_contract", port))
+}
+
+/// Adds an adapter class to the `ADAPTERS` list of the port's contract suite, if the
+/// suite exists.
+pub fn register_contract_adapter(port: &str, import_line: &str, class: &str) {
+    let path = contract_path(port);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return;
+    };
+    if content.lines().any(|l| l.trim() == format!("{},", class)) {
+        return;
+    }
+
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let Some(list_start) = lines.iter().position(|l| l.starts_with("ADAPTERS = [")) else {
+        return;
+    };
+    let Some(list_end) = lines.iter().skip(list_start).position(|l| l.starts_with(']')) else {
+        return;
+    };
+    lines.insert(list_start + list_end, format!("    {},", class));
+    let import_at = lines[..list_start]
+        .iter()
+        .rposition(|l| l.starts_with("from ") || l.starts_with("import "))
+        .map_or(0, |i| i + 1);
+    lines.insert(import_at, import_line.to_string());
+
+    fs::write(&path, lines.join("\n") + "\n").expect("❌ Failed to write contract suite");
+    println!("🤝 Registered '{}' in '{}'", class, path.display());
+}
+
+pub fn unregister_contract_adapter(module: &str) {
+    let prefix = format!("from app.infrastructure.{} import ", module);
+    for path in python_files(Path::new("tests/contracts")) {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let Some(class) = content
+            .lines()
+            .find_map(|l| l.strip_prefix(&prefix))
+            .map(|c| c.trim().to_string())
+        else {
+            continue;
+        };
+        let entry = format!("{},", class);
+        let kept: Vec<&str> = content
+            .lines()
+            .filter(|l| !l.starts_with(&prefix) && l.trim() != entry)
+            .collect();
+        fs::write(&path, kept.join("\n") + "\n").expect("❌ Failed to write contract suite");
+        println!("🤝 Unregistered '{}' from '{}'", class, path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::python::Param;
+
+    fn method(name: &str, params: &[&str], returns: &str) -> MethodSpec {
+        MethodSpec {
+            name: name.to_string(),
+            params: params
+                .iter()
+                .map(|p| Param { name: p.to_string(), annotation: None, default: None })
+                .collect(),
+            returns: Some(returns.to_string()),
+            is_async: false,
+        }
+    }
+
+    #[test]
+    fn update_stores_the_value_under_the_key() {
+        let body = fake_method_body(&method("update_product", &["id", "data"], "Optional[Product]"));
+        assert_eq!(
+            body,
+            "        self.calls.append((\"update_product\", (id,data,)))\n        key = id\n        self.items[key] = data\n        return data\n"
+        );
+    }
+
+    #[test]
+    fn single_argument_save_keys_the_item() {
+        let body = fake_method_body(&method("save_product", &["product"], "int"));
+        assert!(body.ends_with("        key = self._key(product)\n        self.items[key] = product\n        return key\n"), "{}", body);
+    }
+
+    #[test]
+    fn reads_and_deletes_use_the_key() {
+        let body = fake_method_body(&method("get_product", &["id"], "Optional[Product]"));
+        assert!(body.ends_with("        return self.items.get(id)\n"), "{}", body);
+        let body = fake_method_body(&method("list_products", &[], "List[Product]"));
+        assert!(body.ends_with("        return list(self.items.values())\n"), "{}", body);
+        let body = fake_method_body(&method("delete_product", &["id"], "bool"));
+        assert!(body.ends_with("        removed = self.items.pop(id, None)\n        return removed is not None\n"), "{}", body);
+        let body = fake_method_body(&method("notify", &["user"], "str"));
+        assert!(body.ends_with("        return self.returns.get(\"notify\")\n"), "{}", body);
+    }
+}
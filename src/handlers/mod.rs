@@ -2,3 +2,19 @@ pub mod make;
 pub mod new;
 pub mod usecase;
 pub mod features;
+pub mod destroy;
+pub mod container;
+pub mod check;
+pub mod lint;
+pub mod graph;
+pub mod routes;
+pub mod inspect;
+pub mod openapi;
+pub mod import_openapi;
+pub mod import_db;
+pub mod api;
+pub mod import_recordings;
+pub mod export;
+pub mod client;
+pub mod db;
+pub mod unit_of_work;
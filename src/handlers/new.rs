@@ -1,8 +1,13 @@
 use std::path::Path;
 use std::fs;
 use crate::cli::NewOptions;
+use crate::handlers::container::sync_container;
+use crate::handlers::unit_of_work::write_unit_of_work;
 use crate::handlers::features::Features;
-use dialoguer::Confirm;
+use crate::handlers::make::route_template;
+use crate::handlers::lint::{create_import_linter, IMPORT_LINTER_REQUIREMENT};
+use crate::utils::config::{Config, DATABASE_BACKENDS};
+use dialoguer::{Confirm, Select};
 use dialoguer::theme::ColorfulTheme;
 
 pub fn scaffold_project(
@@ -37,7 +42,9 @@ pub fn scaffold_project(
     create_main_app(&app);
     create_example_port(&app);
     create_example_impl(&app);
+    create_example_route(&app);
     create_project_files(root, dependencies, options);
+    sync_container(root);
 
     println!("✅ Project '{}' created with hexagonal architecture!", name);
 }
@@ -85,6 +92,17 @@ class ExampleImpl(ExamplePort):
     ).ok();
 }
 
+fn create_example_route(app: &Path) {
+    fs::write(
+        app.join("routes").join("example.py"),
+        route_template("example", "GET"),
+    ).ok();
+    let init = app.join("routes").join("__init__.py");
+    let mut content = fs::read_to_string(&init).unwrap_or_default();
+    content.push_str("from .example import router as example_router\n");
+    fs::write(init, content).ok();
+}
+
 fn create_project_files(root: &Path, dependencies: Option<String>, options: &NewOptions) {
     fs::write(root.join("README.md"), "# 🚀 FastAPI App\n").ok();
     fs::write(root.join(".env.example"), 
@@ -104,9 +122,16 @@ venv/
     ).ok();
     
     let mut requirements = String::from("fastapi==0.115.12\nuvicorn==0.34.2\npydantic==2.11.4\npython-dotenv==1.1.0\n");
+    if !options.no_lint {
+        requirements.push_str(&format!("{}\n", IMPORT_LINTER_REQUIREMENT));
+    }
 
-    if options.sql {
-        requirements.push_str("sqlalchemy==2.0.0\nalembic==1.13.0\npsycopg2-binary==2.9.9\n");
+    if let Some(database) = options.database() {
+        requirements.push_str(if database.is_async { "sqlalchemy[asyncio]==2.0.30\n" } else { "sqlalchemy==2.0.0\n" });
+        requirements.push_str("alembic==1.13.0\n");
+        if let Some(driver) = database.driver() {
+            requirements.push_str(&format!("{}\n", driver));
+        }
     }
 
     if options.supabase {
@@ -128,6 +153,10 @@ venv/
     if options.tasks {
         requirements.push_str("celery==5.3.6\nflower==2.0.1\n");
     }
+
+    if options.mongo {
+        requirements.push_str("motor==3.4.0\nbeanie==1.26.0\n");
+    }
     if let Some(deps) = dependencies {
         requirements.push_str(&deps);
     }
@@ -136,22 +165,30 @@ venv/
 
 pub struct NewCommand {
     sql: bool,
+    db: Option<String>,
+    async_db: bool,
     supabase: bool,
     auth: bool,
     cors: bool,
     cache: bool,
     tasks: bool,
+    mongo: bool,
+    no_lint: bool,
 }
 
 impl NewCommand {
     pub fn new(options: &NewOptions) -> Self {
         NewCommand {
             sql: options.sql,
+            db: options.db.clone(),
+            async_db: options.async_db,
             supabase: options.supabase,
             auth: options.auth,
             cors: options.cors,
             cache: options.cache,
             tasks: options.tasks,
+            mongo: options.mongo,
+            no_lint: options.no_lint,
         }
     }
 
@@ -163,11 +200,15 @@ impl NewCommand {
         
         let mut options = NewOptions {
             sql: self.sql,
+            db: self.db.clone(),
+            async_db: self.async_db,
             supabase: self.supabase,
             auth: self.auth,
             cors: self.cors,
             cache: self.cache,
             tasks: self.tasks,
+            mongo: self.mongo,
+            no_lint: self.no_lint,
         };
     
         if !no_interactive {
@@ -178,6 +219,23 @@ impl NewCommand {
                 .default(false)
                 .interact()?;
 
+            if options.sql {
+                let current = options.db.as_deref().unwrap_or("postgres");
+                let backend = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Which database?")
+                    .items(&DATABASE_BACKENDS)
+                    .default(DATABASE_BACKENDS.iter().position(|b| *b == current).unwrap_or(0))
+                    .interact()?;
+                options.db = Some(DATABASE_BACKENDS[backend].to_string());
+                options.async_db = Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Do you want async sessions (AsyncSession and async drivers)?")
+                    .default(options.async_db)
+                    .interact()?;
+            } else {
+                options.db = None;
+                options.async_db = false;
+            }
+
             options.supabase = Confirm::with_theme(&ColorfulTheme::default())
                 .with_prompt("Do you want to include Supabase integration?")
                 .default(false)
@@ -202,35 +260,71 @@ impl NewCommand {
                 .with_prompt("Do you want to include Celery task queue and Flower monitoring?")
                 .default(false)
                 .interact()?;
+
+            options.mongo = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Do you want to include MongoDB support (Motor and Beanie)?")
+                .default(false)
+                .interact()?;
+
+            options.no_lint = !Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Do you want import-linter contracts for the layer rules?")
+                .default(!options.no_lint)
+                .interact()?;
         }
         
         scaffold_project(name, dependencies, &options);
     
-        let features = Features::new(&root, &app);  // Ahora root y app están definidas
+        let features = Features::new(root, &app);  // Ahora root y app están definidas
     
-        if self.sql {
-            features.setup_database()?;
+        let database = options.database();
+        if let Some(database) = &database {
+            features.setup_database(database)?;
+            write_unit_of_work(root, database.is_async)?;
         }
     
-        if self.supabase {
+        if options.supabase {
             features.setup_supabase()?;
         }
     
-        if self.auth {
+        if options.auth {
             features.setup_auth()?;
         }
     
-        if self.cors {
+        if options.cors {
             features.setup_cors()?;
         }
     
-        if self.cache {
+        if options.cache {
             features.setup_cache()?;
         }
     
-        if self.tasks {
+        if options.tasks {
             features.setup_tasks()?;
         }
+
+        // After CORS, which rewrites main.py.
+        if options.mongo {
+            features.setup_mongo()?;
+        }
+
+        // Feature modules living in core are framework glue, keep them out of the rules.
+        let mut exclude = Vec::new();
+        if options.auth {
+            exclude.push("app/core/auth".to_string());
+        }
+        if options.cors {
+            exclude.push("app/core/cors.py".to_string());
+        }
+        if options.mongo {
+            exclude.push("app/core/mongo.py".to_string());
+        }
+        let mut config = Config::with_exclude(exclude);
+        config.features = options.enabled_features();
+        config.database = database;
+        config.save(root)?;
+        if !options.no_lint {
+            create_import_linter(root, &config)?;
+        }
     
         Ok(())
     }
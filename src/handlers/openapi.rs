@@ -0,0 +1,32 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use crate::utils::openapi::build_document;
+
+pub fn export_openapi(format: &str, out: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let root = Path::new(".");
+    if !root.join("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let document = build_document(root);
+    let rendered = match format {
+        "yaml" => serde_yaml::to_string(&document.value)?,
+        _ => format!("{}\n", serde_json::to_string_pretty(&document.value)?),
+    };
+
+    // Warnings go to stderr so stdout stays a valid document.
+    for warning in &document.warnings {
+        eprintln!("⚠️  {}", warning);
+    }
+    match out {
+        Some(path) => {
+            fs::write(path, rendered)?;
+            let operations = document.value["paths"]
+                .as_object()
+                .map_or(0, |paths| paths.values().filter_map(|p| p.as_object()).map(|p| p.len()).sum());
+            println!("✅ OpenAPI document with {} operation(s) written to '{}'", operations, path);
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
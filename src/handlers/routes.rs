@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::path::Path;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+use crate::utils::routes::collect_endpoints;
+
+#[derive(Tabled)]
+struct RouteRow {
+    #[tabled(rename = "Method")]
+    method: String,
+    #[tabled(rename = "Path")]
+    path: String,
+    #[tabled(rename = "Handler")]
+    handler: String,
+    #[tabled(rename = "Module")]
+    module: String,
+    #[tabled(rename = "Auth")]
+    auth: String,
+}
+
+pub fn list_routes(json: bool) -> Result<(), Box<dyn Error>> {
+    let root = Path::new(".");
+    if !root.join("app").is_dir() {
+        return Err("No 'app' package found, run this command from the project root".into());
+    }
+    let endpoints = collect_endpoints(root);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&endpoints)?);
+        return Ok(());
+    }
+    if endpoints.is_empty() {
+        println!("ℹ️  No routes found in 'app'");
+        return Ok(());
+    }
+
+    let rows = endpoints.iter().map(|e| RouteRow {
+        method: e.method.clone(),
+        path: e.path.clone(),
+        handler: e.handler.clone(),
+        module: if e.included { e.module.clone() } else { format!("{} ⚠️", e.module) },
+        auth: if e.auth.is_empty() { "-".to_string() } else { e.auth.join(", ") },
+    });
+    println!("{}", Table::new(rows).with(Style::rounded()));
+
+    let protected = endpoints.iter().filter(|e| !e.auth.is_empty()).count();
+    println!("🛣️  {} route(s), {} behind an auth dependency", endpoints.len(), protected);
+    let mut orphans: Vec<&str> = endpoints.iter().filter(|e| !e.included).map(|e| e.file.as_str()).collect();
+    orphans.dedup();
+    for file in orphans {
+        println!("⚠️  '{}' defines a router the app never includes", file);
+    }
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::handlers::container::sync_container;
+use crate::utils::config::Config;
+use crate::utils::python::parse_abstract_methods;
+use crate::utils::testing::add_fixture;
+
+/// Module of the port, shared by its adapter, fake and `get_unit_of_work` provider.
+pub const PORT: &str = "unit_of_work";
+
+fn port_path(root: &Path) -> PathBuf {
+    root.join("app").join("ports").join(format!("{}.py", PORT))
+}
+
+pub fn has_unit_of_work(root: &Path) -> bool {
+    port_path(root).exists()
+}
+
+/// Whether the port's `commit` is a coroutine, as generated for `--async-db` projects.
+pub fn async_unit_of_work(root: &Path) -> bool {
+    let source = fs::read_to_string(port_path(root)).unwrap_or_default();
+    parse_abstract_methods(&source, "UnitOfWork")
+        .iter()
+        .any(|m| m.name == "commit" && m.is_async)
+}
+
+fn render_port(is_async: bool) -> String {
+    let context = if is_async {
+        r#"    async def __aenter__(self) -> UnitOfWork:
+        return self
+
+    async def __aexit__(self, *exc_info) -> None:
+        await self.rollback()"#
+    } else {
+        r#"    def __enter__(self) -> UnitOfWork:
+        return self
+
+    def __exit__(self, *exc_info) -> None:
+        self.rollback()"#
+    };
+    let def = if is_async { "async def" } else { "def" };
+    format!(
+        r#"from __future__ import annotations
+
+from abc import ABC, abstractmethod
+
+
+class UnitOfWork(ABC):
+    """Transaction of a use case: what the repositories change inside the `with`
+    block is kept by `commit`, anything left uncommitted is rolled back on exit."""
+
+{context}
+
+    @abstractmethod
+    {def} commit(self) -> None:
+        pass
+
+    @abstractmethod
+    {def} rollback(self) -> None:
+        pass
+"#
+    )
+}
+
+fn render_adapter(is_async: bool) -> String {
+    let (session_import, session, def, wait) = if is_async {
+        ("from sqlalchemy.ext.asyncio import AsyncSession", "AsyncSession", "async def", "await ")
+    } else {
+        ("from sqlalchemy.orm import Session", "Session", "def", "")
+    };
+    format!(
+        r#"from fastapi import Depends
+{session_import}
+
+from app.core.database import get_db
+from app.ports.{PORT} import UnitOfWork
+
+
+class SqlUnitOfWork(UnitOfWork):
+    """Commits the request's session, the same one `get_db` hands to the SQL repositories."""
+
+    def __init__(self, db: {session} = Depends(get_db)):
+        self.db = db
+
+    {def} commit(self) -> None:
+        {wait}self.db.commit()
+
+    {def} rollback(self) -> None:
+        {wait}self.db.rollback()
+"#
+    )
+}
+
+fn render_fake(is_async: bool) -> String {
+    let def = if is_async { "async def" } else { "def" };
+    format!(
+        r#"from __future__ import annotations
+
+from app.ports.{PORT} import UnitOfWork
+
+
+class FakeUnitOfWork(UnitOfWork):
+    """In-memory unit of work for tests: `committed` tells whether the use case
+    committed, `rolled_back` whether it left without committing."""
+
+    def __init__(self):
+        self.committed = False
+        self.rolled_back = False
+
+    {def} commit(self) -> None:
+        self.committed = True
+
+    {def} rollback(self) -> None:
+        self.rolled_back = not self.committed
+"#
+    )
+}
+
+/// Writes the `UnitOfWork` port, its SQLAlchemy adapter and the in-memory fake
+/// with its `fake_unit_of_work` fixture, then binds the port in the container.
+pub fn write_unit_of_work(root: &Path, is_async: bool) -> Result<(), Box<dyn Error>> {
+    let port = port_path(root);
+    let adapter = root.join("app").join("infrastructure").join("sql_unit_of_work.py");
+    let fakes = root.join("tests").join("fakes");
+    let fake = fakes.join(format!("fake_{}.py", PORT));
+    fs::create_dir_all(&fakes)?;
+    for init in [root.join("tests").join("__init__.py"), fakes.join("__init__.py")] {
+        if !init.exists() {
+            fs::write(init, "")?;
+        }
+    }
+
+    for (path, content) in [(&port, render_port(is_async)), (&adapter, render_adapter(is_async)), (&fake, render_fake(is_async))] {
+        fs::write(path, content)?;
+        println!("✅ Created '{}'", path.strip_prefix(".").unwrap_or(path).display());
+    }
+    add_fixture(
+        root,
+        &format!("fake_{}", PORT),
+        &format!(
+            "@pytest.fixture\ndef fake_{0}():\n    from tests.fakes.fake_{0} import FakeUnitOfWork\n\n    return FakeUnitOfWork()\n",
+            PORT
+        ),
+    );
+    sync_container(root);
+    Ok(())
+}
+
+/// `faspi add uow`: the unit of work for an existing SQL project.
+pub fn add_unit_of_work(root: &Path) -> Result<(), Box<dyn Error>> {
+    if !root.join("app").join("core").join("database.py").exists() {
+        return Err("'app/core/database.py' not found, the unit of work needs a project created with --sql".into());
+    }
+    let port = port_path(root);
+    if port.exists() {
+        return Err(format!("'{}' already exists", port.strip_prefix(".").unwrap_or(&port).display()).into());
+    }
+    write_unit_of_work(root, Config::async_database(root))?;
+    println!("ℹ️  Repositories generated from now on leave commits to the unit of work, regenerate existing ones with --force");
+    Ok(())
+}
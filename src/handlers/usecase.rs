@@ -1,35 +1,207 @@
+use std::error::Error;
 use std::path::Path;
 use std::fs;
-use crate::utils::fs::append_to_init;
+use crate::handlers::container::sync_container;
+use crate::handlers::make::fake_path;
+use crate::handlers::unit_of_work::{async_unit_of_work, has_unit_of_work, PORT as UNIT_OF_WORK};
+use crate::utils::fs::append_import;
+use crate::utils::python::{find_classes, find_port_class};
+use crate::utils::string::to_camel_case;
 use crate::utils::testing::*;
 
-pub fn create_usecase(name: &str) {
+struct Dependency {
+    name: String,
+    import: String,
+    class: Option<String>,
+}
+
+impl Dependency {
+    fn port(name: &str) -> Result<Dependency, Box<dyn Error>> {
+        let path = Path::new("app").join("ports").join(format!("{}.py", name));
+        if !path.exists() {
+            return Err(format!(
+                "Port '{}' not found at '{}', create it with 'faspi make port {}'",
+                name,
+                path.display(),
+                name
+            )
+            .into());
+        }
+        let class = find_port_class(&path).unwrap_or_else(|| to_camel_case(name));
+        Ok(Dependency {
+            name: name.to_string(),
+            import: format!("from app.ports.{} import {}", name, class),
+            class: Some(class),
+        })
+    }
+
+    fn service(name: &str) -> Result<Dependency, Box<dyn Error>> {
+        let path = Path::new("app").join("services").join(format!("{}.py", name));
+        let source = fs::read_to_string(&path).map_err(|_| {
+            format!(
+                "Service '{}' not found at '{}', create it with 'faspi make service {}'",
+                name,
+                path.display(),
+                name
+            )
+        })?;
+
+        // Services generated by `make service` are plain functions, so the module
+        // itself is injected unless it defines the matching class.
+        let expected = to_camel_case(name);
+        let class = find_classes(&source)
+            .into_iter()
+            .map(|c| c.name)
+            .find(|c| *c == expected);
+        let import = match &class {
+            Some(class) => format!("from app.services.{} import {}", name, class),
+            None => format!("from app.services import {} as {}_module", name, name),
+        };
+        Ok(Dependency {
+            name: name.to_string(),
+            import,
+            class,
+        })
+    }
+
+    fn mock_spec(&self) -> String {
+        match &self.class {
+            Some(class) => class.clone(),
+            None => format!("{}_module", self.name),
+        }
+    }
+
+    fn fake_fixture(&self) -> Option<String> {
+        let is_port = self.import.starts_with("from app.ports.");
+        (is_port && fake_path(&self.name).exists()).then(|| format!("fake_{}", self.name))
+    }
+
+    fn param(&self) -> String {
+        match &self.class {
+            Some(class) => format!("{}: {}", self.name, class),
+            None => format!("{0}: ModuleType = {0}_module", self.name),
+        }
+    }
+}
+
+pub fn create_usecase(name: &str, ports: &[String], services: &[String]) -> Result<(), Box<dyn Error>> {
+    // SQL projects run every use case in a unit of work, injected first.
+    let transactional = has_unit_of_work(Path::new("."));
+    let is_async = transactional && async_unit_of_work(Path::new("."));
+    let mut ports: Vec<&str> = ports.iter().map(String::as_str).filter(|p| *p != UNIT_OF_WORK).collect();
+    if transactional {
+        ports.insert(0, UNIT_OF_WORK);
+    }
+    let ports = ports
+        .into_iter()
+        .map(Dependency::port)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut services = services
+        .iter()
+        .map(|s| Dependency::service(s))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Module services carry a default value and must follow the required params.
+    services.sort_by_key(|s| s.class.is_none());
+
+    let class = to_camel_case(name);
     let path = Path::new("app").join("usecases").join(format!("{}.py", name));
-    fs::create_dir_all("app/usecases").ok();
+    fs::create_dir_all("app/usecases")?;
+
+    let dependencies: Vec<&Dependency> = ports.iter().chain(services.iter()).collect();
+    let mut imports = String::from("from __future__ import annotations\n\nfrom dataclasses import dataclass\n");
+    if dependencies.iter().any(|d| d.class.is_none()) {
+        imports.push_str("from types import ModuleType\n");
+    }
+    if !dependencies.is_empty() {
+        imports.push('\n');
+    }
+    for dependency in &dependencies {
+        imports.push_str(&dependency.import);
+        imports.push('\n');
+    }
+
+    let mut params = vec!["self".to_string()];
+    params.extend(dependencies.iter().map(|d| d.param()));
+    let mut assignments: String = dependencies
+        .iter()
+        .map(|d| format!("        self.{0} = {0}\n", d.name))
+        .collect();
+    if assignments.is_empty() {
+        assignments.push_str("        pass\n");
+    }
+    let execute = match (transactional, is_async) {
+        (false, _) => format!("    def execute(self, data: {0}Input) -> {0}Output:\n", class),
+        (true, false) => format!(
+            "    def execute(self, data: {0}Input) -> {0}Output:\n        with self.{1}:\n            self.{1}.commit()\n",
+            class, UNIT_OF_WORK
+        ),
+        (true, true) => format!(
+            "    async def execute(self, data: {0}Input) -> {0}Output:\n        async with self.{1}:\n            await self.{1}.commit()\n",
+            class, UNIT_OF_WORK
+        ),
+    };
 
     fs::write(
         &path,
         format!(
-            r#"""Use case for connecting ports, services, and implementations.
+            r#"{imports}
+
 
-from app.services.example_service import ExampleService
-from app.ports.example_port import ExamplePort
+@dataclass(frozen=True)
+class {class}Input:
+    pass
 
-class {0}:
-    def __init__(self, port: ExamplePort):
-        self.port = port
 
-    def execute(self):
-        return self.port.get_data()
+@dataclass(frozen=True)
+class {class}Output:
+    pass
+
+
+class {class}:
+    """Use case for connecting ports, services, and implementations."""
+
+    def __init__({params}):
+{assignments}
+{execute}        return {class}Output()
 "#,
-            name
+            imports = imports.trim_end(),
+            class = class,
+            params = params.join(", "),
+            assignments = assignments,
+            execute = execute,
         ),
-    )
-    .expect("❌ Failed to write usecase file");
+    )?;
 
-    append_to_init("app/usecases/__init__.py", name);
+    append_import(
+        "app/usecases/__init__.py",
+        &format!("from .{} import {}", name, class),
+        name,
+    );
     println!("✅ Usecase created at '{}'", path.display());
 
-    let test_content = generate_usecase_test(name);
+    let test_dependencies: Vec<TestDependency> = dependencies
+        .iter()
+        .map(|d| TestDependency {
+            param: d.name.clone(),
+            import: d.import.clone(),
+            mock_spec: d.mock_spec(),
+            fixture: d.fake_fixture(),
+        })
+        .collect();
+    let mut test_content = generate_usecase_test(name, &class, &test_dependencies, is_async);
+    if transactional && ports[0].fake_fixture().is_some() {
+        test_content.push_str(&format!(
+            r#"
+def test_{0}_commits(usecase, fake_{1}):
+    {2}
+    assert fake_{1}.committed
+"#,
+            name,
+            UNIT_OF_WORK,
+            usecase_call(&class, is_async)
+        ));
+    }
     create_test_file("usecase", name, &test_content);
+    sync_container(Path::new("."));
+    Ok(())
 }
@@ -4,11 +4,15 @@ mod utils;
 
 use crate::cli::update_checker::UpdateChecker;
 use clap::Parser;
-use cli::{Cli, Commands, MakeItem};
+use std::path::Path;
+use cli::{AddItem, ApiCommand, CheckTarget, Cli, ClientLanguage, Commands, DbCommand, ExportTarget, ImportSource, MakeItem};
 use handlers::new::NewCommand;
 
 fn main() {
     let cli = Cli::parse();
+    if !matches!(cli.command, Commands::New { .. } | Commands::Add { .. }) {
+        handlers::lint::refresh_import_linter(Path::new("."));
+    }
     match cli.command {
         Commands::New {
             name,
@@ -24,15 +28,196 @@ fn main() {
         }
         Commands::Make { item } => match item {
             MakeItem::Route { name, method } => handlers::make::create_route(&name, method),
-            MakeItem::Model { name } => handlers::make::create_model(&name),
+            MakeItem::Model { name, from_json } if from_json.is_empty() => handlers::make::create_model(&name),
+            MakeItem::Model { name, from_json } => {
+                if let Err(e) = handlers::make::create_model_from_json(&name, &from_json) {
+                    eprintln!("❌ Error creating model: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            MakeItem::Resource { name, fields, store, force } => {
+                if let Err(e) = handlers::make::create_resource(&name, &fields, store.as_deref(), force) {
+                    eprintln!("❌ Error creating resource: {}", e);
+                    std::process::exit(1);
+                }
+            }
             MakeItem::Service { name } => {
                 handlers::make::create_component("services", &name, "💼 Service", "service")
             }
             MakeItem::Core { name } => {
                 handlers::make::create_component("core", &name, "🧠 Core logic", "core")
             }
+            MakeItem::Port { name, methods } => {
+                if let Err(e) = handlers::make::create_port(&name, &methods) {
+                    eprintln!("❌ Error creating port: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            MakeItem::Adapter { name, port } => {
+                if let Err(e) = handlers::make::create_adapter(&name, &port) {
+                    eprintln!("❌ Error creating adapter: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            MakeItem::Fake { port } => {
+                if let Err(e) = handlers::make::create_fake(&port) {
+                    eprintln!("❌ Error creating fake: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            MakeItem::Contract { port } => {
+                if let Err(e) = handlers::make::create_contract(&port) {
+                    eprintln!("❌ Error creating contract suite: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        },
+        Commands::Usecase { name, ports, services } => {
+            if let Err(e) = handlers::usecase::create_usecase(&name, &ports, &services) {
+                eprintln!("❌ Error creating usecase: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Add { item } => match item {
+            AddItem::Lint => {
+                if let Err(e) = handlers::lint::add_lint(Path::new(".")) {
+                    eprintln!("❌ Error adding lint contracts: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            AddItem::Uow => {
+                if let Err(e) = handlers::unit_of_work::add_unit_of_work(Path::new(".")) {
+                    eprintln!("❌ Error adding the unit of work: {}", e);
+                    std::process::exit(1);
+                }
+            }
         },
-        Commands::Usecase { name } => handlers::usecase::create_usecase(&name),
+        Commands::Check { target } => match target {
+            CheckTarget::Architecture { format } => {
+                // Exits straight away so CI gets the status code and clean output.
+                match handlers::check::check_architecture(&format) {
+                    Ok(0) => std::process::exit(0),
+                    Ok(_) => std::process::exit(1),
+                    Err(e) => {
+                        eprintln!("❌ Error checking architecture: {}", e);
+                        std::process::exit(2);
+                    }
+                }
+            }
+        },
+        Commands::Graph { format, level, out } => {
+            if let Err(e) = handlers::graph::export_graph(&format, &level, out.as_deref()) {
+                eprintln!("❌ Error exporting graph: {}", e);
+                std::process::exit(1);
+            }
+            if out.is_none() {
+                return;
+            }
+        }
+        Commands::Routes { json } => {
+            if let Err(e) = handlers::routes::list_routes(json) {
+                eprintln!("❌ Error listing routes: {}", e);
+                std::process::exit(1);
+            }
+            if json {
+                return;
+            }
+        }
+        Commands::Openapi { format, out } => {
+            if let Err(e) = handlers::openapi::export_openapi(&format, out.as_deref()) {
+                eprintln!("❌ Error generating OpenAPI document: {}", e);
+                std::process::exit(1);
+            }
+            if out.is_none() {
+                return;
+            }
+        }
+        Commands::Api { command } => match command {
+            ApiCommand::Diff { old, new, format } => match handlers::api::diff_api(&old, new.as_deref(), &format) {
+                Ok(0) => std::process::exit(0),
+                Ok(_) => std::process::exit(1),
+                Err(e) => {
+                    eprintln!("❌ Error comparing API versions: {}", e);
+                    std::process::exit(2);
+                }
+            },
+        },
+        Commands::Export { target } => {
+            let (result, out) = match &target {
+                ExportTarget::Http { out, base_url } => (handlers::export::export_http(out.as_deref(), base_url), out),
+                ExportTarget::Postman { out, base_url } => (handlers::export::export_postman(out.as_deref(), base_url), out),
+            };
+            if let Err(e) = result {
+                eprintln!("❌ Error exporting requests: {}", e);
+                std::process::exit(1);
+            }
+            if out.is_none() {
+                return;
+            }
+        }
+        Commands::Client { language } => match language {
+            ClientLanguage::Ts { out } => {
+                if let Err(e) = handlers::client::generate_ts_client(&out) {
+                    eprintln!("❌ Error generating TypeScript client: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        },
+        Commands::Db { command } => {
+            let result = match command {
+                DbCommand::Revision { message, merge } => handlers::db::create_revision(&message, merge),
+                DbCommand::Heads => handlers::db::show_heads(),
+                DbCommand::History => handlers::db::show_history(),
+            };
+            if let Err(e) = result {
+                eprintln!("❌ Error managing migrations: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Import { source } => match source {
+            ImportSource::Openapi { spec, models, force } => {
+                if let Err(e) = handlers::import_openapi::import_openapi(&spec, &models, force) {
+                    eprintln!("❌ Error importing OpenAPI spec: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            ImportSource::Postman { collection, models, force } => {
+                if let Err(e) = handlers::import_recordings::import_postman(&collection, &models, force) {
+                    eprintln!("❌ Error importing Postman collection: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            ImportSource::Har { capture, models, force } => {
+                if let Err(e) = handlers::import_recordings::import_har(&capture, &models, force) {
+                    eprintln!("❌ Error importing HAR capture: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            ImportSource::Db { sqlite, crud, force } => {
+                if let Err(e) = handlers::import_db::import_sqlite(&sqlite, crud, force) {
+                    eprintln!("❌ Error importing database: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            ImportSource::Ddl { file, crud, force } => {
+                if let Err(e) = handlers::import_db::import_ddl(&file, crud, force) {
+                    eprintln!("❌ Error importing DDL: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        },
+        Commands::Inspect => {
+            if let Err(e) = handlers::inspect::inspect_project() {
+                eprintln!("❌ Error inspecting project: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Destroy { kind, name } => {
+            if let Err(e) = handlers::destroy::destroy_component(&kind, &name) {
+                eprintln!("❌ Error destroying {}: {}", kind, e);
+                std::process::exit(1);
+            }
+        }
     }
     let checker = UpdateChecker::new();
     if let Err(e) = checker.check_for_updates() {
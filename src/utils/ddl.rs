@@ -0,0 +1,578 @@
+use sqlparser::ast::{
+    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, CreateIndex, CreateTable, Expr, ObjectName,
+    Statement, TableConstraint, UserDefinedTypeRepresentation,
+};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::parser::Parser;
+use crate::utils::tables::{Column, ForeignKey, Index, Table};
+
+/// Statements that only matter to the database itself, skipped without a warning.
+const IGNORED: [&str; 20] = [
+    "SET",
+    "COPY",
+    "SELECT",
+    "COMMENT",
+    "GRANT",
+    "REVOKE",
+    "BEGIN",
+    "COMMIT",
+    "START",
+    "END",
+    "DROP",
+    "INSERT",
+    "CREATE SEQUENCE",
+    "ALTER SEQUENCE",
+    "CREATE EXTENSION",
+    "CREATE SCHEMA",
+    "ALTER SCHEMA",
+    "ALTER TYPE",
+    "ALTER DEFAULT",
+    "CREATE STATISTICS",
+];
+
+/// A statement of the script and the line it starts on.
+struct Chunk {
+    line: usize,
+    sql: String,
+}
+
+/// Splits a SQL script into statements, honoring quotes, comments and
+/// `$tag$` bodies, and dropping psql meta-commands and `COPY ... FROM stdin` data.
+fn split_statements(sql: &str) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut start = 1;
+    let mut line = 1;
+    let mut in_copy = false;
+    let mut chars = sql.chars().peekable();
+    let mut at_line_start = true;
+
+    while let Some(c) = chars.next() {
+        if at_line_start && (in_copy || (c == '\\' && current.trim().is_empty())) {
+            // Skip the whole line: COPY data rows end with `\.`, psql commands with the line.
+            let mut rest = String::from(c);
+            for next in chars.by_ref() {
+                if next == '\n' {
+                    break;
+                }
+                rest.push(next);
+            }
+            if in_copy && rest.trim_end() == "\\." {
+                in_copy = false;
+            }
+            line += 1;
+            continue;
+        }
+        at_line_start = false;
+        if current.trim().is_empty() {
+            start = line;
+        }
+        match c {
+            '\n' => {
+                line += 1;
+                at_line_start = true;
+                current.push(c);
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        line += 1;
+                        at_line_start = true;
+                        current.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        line += 1;
+                    }
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+                current.push(' ');
+            }
+            '\'' | '"' => {
+                current.push(c);
+                for next in chars.by_ref() {
+                    current.push(next);
+                    if next == '\n' {
+                        line += 1;
+                    }
+                    if next == c {
+                        break;
+                    }
+                }
+            }
+            '$' => {
+                current.push(c);
+                let mut tag = String::from('$');
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' {
+                        tag.push(next);
+                        current.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if chars.peek() != Some(&'$') {
+                    continue;
+                }
+                chars.next();
+                current.push('$');
+                tag.push('$');
+                let mut body = String::new();
+                for next in chars.by_ref() {
+                    body.push(next);
+                    if next == '\n' {
+                        line += 1;
+                    }
+                    if body.ends_with(&tag) {
+                        break;
+                    }
+                }
+                current.push_str(&body);
+            }
+            ';' => {
+                let statement = current.trim().to_string();
+                if !statement.is_empty() {
+                    let upper = statement.to_uppercase();
+                    in_copy = upper.starts_with("COPY ") && upper.ends_with("FROM STDIN");
+                    chunks.push(Chunk { line: start, sql: statement });
+                }
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        chunks.push(Chunk {
+            line: start,
+            sql: current.trim().to_string(),
+        });
+    }
+    chunks
+}
+
+fn last_name(name: &ObjectName) -> String {
+    name.0.last().map(|ident| ident.value.clone()).unwrap_or_default()
+}
+
+fn column_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Identifier(ident) => Some(ident.value.clone()),
+        Expr::CompoundIdentifier(idents) => idents.last().map(|ident| ident.value.clone()),
+        _ => None,
+    }
+}
+
+struct Parsed {
+    tables: Vec<Table>,
+    enums: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Parsed {
+    fn table(&mut self, name: &ObjectName, line: usize) -> Option<&mut Table> {
+        let name = last_name(name);
+        match self.tables.iter().position(|t| t.name == name) {
+            Some(index) => Some(&mut self.tables[index]),
+            None => {
+                self.warnings.push(format!("line {}: table '{}' is not created in this file, statement skipped", line, name));
+                None
+            }
+        }
+    }
+
+    fn column(&mut self, table: &str, definition: &ColumnDef, line: usize) -> Column {
+        let mut data_type = definition.data_type.to_string();
+        // Drop the schema of user-defined types: "public.citext" -> "citext".
+        if let Some((_, rest)) = data_type.split_once('.').filter(|(schema, _)| !schema.contains(['(', ' '])) {
+            data_type = rest.to_string();
+        }
+        let enum_type = self.enums.iter().any(|e| e.eq_ignore_ascii_case(&data_type));
+        if enum_type {
+            self.warnings.push(format!(
+                "line {}: {}.{} uses enum type '{}', imported as VARCHAR",
+                line, table, definition.name.value, data_type
+            ));
+        }
+        let mut column = Column {
+            name: definition.name.value.clone(),
+            data_type: if enum_type { "VARCHAR".to_string() } else { data_type },
+            nullable: true,
+            default: None,
+            primary_key: false,
+            unique: false,
+        };
+        for option in &definition.options {
+            match &option.option {
+                ColumnOption::Null => column.nullable = true,
+                ColumnOption::NotNull => column.nullable = false,
+                ColumnOption::Default(expr) => {
+                    let default = expr.to_string();
+                    // Sequence defaults are how SERIAL columns are dumped: the key is generated.
+                    if !default.to_lowercase().starts_with("nextval(") {
+                        column.default = Some(default);
+                    }
+                    // The enum becomes VARCHAR, so its literal must not be cast to it.
+                    if let Some(literal) = column.default.as_ref().filter(|_| enum_type).and_then(|d| d.split_once("::")) {
+                        column.default = Some(literal.0.to_string());
+                    }
+                }
+                ColumnOption::Unique { is_primary: true, .. } => {
+                    column.primary_key = true;
+                    column.nullable = false;
+                }
+                ColumnOption::Unique { is_primary: false, .. } => column.unique = true,
+                ColumnOption::Check(_) => self.warnings.push(format!(
+                    "line {}: CHECK constraint on {}.{} is not supported, skipped",
+                    line, table, column.name
+                )),
+                ColumnOption::Generated { generation_expr: Some(_), .. } => self.warnings.push(format!(
+                    "line {}: {}.{} is a generated column, imported as a plain column",
+                    line, table, column.name
+                )),
+                _ => {}
+            }
+        }
+        column
+    }
+
+    fn foreign_keys(definition: &ColumnDef) -> Vec<ForeignKey> {
+        definition
+            .options
+            .iter()
+            .filter_map(|option| match &option.option {
+                ColumnOption::ForeignKey {
+                    foreign_table,
+                    referred_columns,
+                    on_delete,
+                    ..
+                } => Some(ForeignKey {
+                    columns: vec![definition.name.value.clone()],
+                    table: last_name(foreign_table),
+                    references: referred_columns.iter().map(|c| c.value.clone()).collect(),
+                    on_delete: on_delete.map(|action| action.to_string()),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn add_column(&mut self, table: usize, definition: &ColumnDef, line: usize) {
+        let name = self.tables[table].name.clone();
+        let column = self.column(&name, definition, line);
+        let table = &mut self.tables[table];
+        table.foreign_keys.extend(Self::foreign_keys(definition));
+        table.columns.push(column);
+    }
+
+    fn constraint(&mut self, table: usize, constraint: &TableConstraint, line: usize) {
+        let name = self.tables[table].name.clone();
+        let table = &mut self.tables[table];
+        match constraint {
+            TableConstraint::PrimaryKey { columns, .. } => {
+                for column in table.columns.iter_mut().filter(|c| columns.iter().any(|k| k.value == c.name)) {
+                    column.primary_key = true;
+                    column.nullable = false;
+                }
+            }
+            TableConstraint::Unique { name: index, columns, .. } => {
+                let columns: Vec<String> = columns.iter().map(|c| c.value.clone()).collect();
+                match columns.as_slice() {
+                    [single] => {
+                        if let Some(column) = table.columns.iter_mut().find(|c| &c.name == single) {
+                            column.unique = true;
+                        }
+                    }
+                    _ => table.indexes.push(Index {
+                        name: index.as_ref().map_or(format!("uq_{}_{}", name, columns.join("_")), |i| i.value.clone()),
+                        columns,
+                        unique: true,
+                    }),
+                }
+            }
+            TableConstraint::ForeignKey {
+                columns,
+                foreign_table,
+                referred_columns,
+                on_delete,
+                ..
+            } => table.foreign_keys.push(ForeignKey {
+                columns: columns.iter().map(|c| c.value.clone()).collect(),
+                table: last_name(foreign_table),
+                references: referred_columns.iter().map(|c| c.value.clone()).collect(),
+                on_delete: on_delete.map(|action| action.to_string()),
+            }),
+            TableConstraint::Index { name: index, columns, .. } => {
+                let columns: Vec<String> = columns.iter().map(|c| c.value.clone()).collect();
+                table.indexes.push(Index {
+                    name: index.as_ref().map_or(format!("ix_{}_{}", name, columns.join("_")), |i| i.value.clone()),
+                    columns,
+                    unique: false,
+                });
+            }
+            TableConstraint::Check { .. } => {
+                self.warnings.push(format!("line {}: CHECK constraint on '{}' is not supported, skipped", line, name))
+            }
+            TableConstraint::FulltextOrSpatial { .. } => {
+                self.warnings.push(format!("line {}: FULLTEXT/SPATIAL index on '{}' is not supported, skipped", line, name))
+            }
+        }
+    }
+
+    fn create_table(&mut self, create: &CreateTable, line: usize) {
+        let name = last_name(&create.name);
+        if self.tables.iter().any(|t| t.name == name) {
+            self.warnings.push(format!("line {}: table '{}' is created twice, keeping the first definition", line, name));
+            return;
+        }
+        self.tables.push(Table {
+            name,
+            columns: Vec::new(),
+            foreign_keys: Vec::new(),
+            indexes: Vec::new(),
+        });
+        let index = self.tables.len() - 1;
+        for definition in &create.columns {
+            self.add_column(index, definition, line);
+        }
+        for constraint in &create.constraints {
+            self.constraint(index, constraint, line);
+        }
+    }
+
+    fn alter_table(&mut self, name: &ObjectName, operations: &[AlterTableOperation], line: usize) {
+        if operations.iter().all(|o| matches!(o, AlterTableOperation::OwnerTo { .. })) {
+            return;
+        }
+        if self.table(name, line).is_none() {
+            return;
+        }
+        let table = self.tables.iter().position(|t| t.name == last_name(name)).unwrap_or_default();
+        let table_name = self.tables[table].name.clone();
+        for operation in operations {
+            match operation {
+                AlterTableOperation::AddConstraint(constraint) => self.constraint(table, constraint, line),
+                AlterTableOperation::AddColumn { column_def, .. } => self.add_column(table, column_def, line),
+                AlterTableOperation::DropColumn { column_name, .. } => {
+                    let table = &mut self.tables[table];
+                    table.columns.retain(|c| c.name != column_name.value);
+                    table.foreign_keys.retain(|f| !f.columns.contains(&column_name.value));
+                    table.indexes.retain(|i| !i.columns.contains(&column_name.value));
+                }
+                AlterTableOperation::RenameColumn {
+                    old_column_name,
+                    new_column_name,
+                } => {
+                    let table = &mut self.tables[table];
+                    let rename = |name: &mut String| {
+                        if *name == old_column_name.value {
+                            *name = new_column_name.value.clone();
+                        }
+                    };
+                    table.columns.iter_mut().for_each(|c| rename(&mut c.name));
+                    table.foreign_keys.iter_mut().for_each(|f| f.columns.iter_mut().for_each(rename));
+                    table.indexes.iter_mut().for_each(|i| i.columns.iter_mut().for_each(rename));
+                }
+                AlterTableOperation::RenameTable { table_name: renamed } => {
+                    let renamed = last_name(renamed);
+                    for foreign_key in self.tables.iter_mut().flat_map(|t| t.foreign_keys.iter_mut()) {
+                        if foreign_key.table == table_name {
+                            foreign_key.table = renamed.clone();
+                        }
+                    }
+                    self.tables[table].name = renamed;
+                }
+                AlterTableOperation::AlterColumn { column_name, op } => {
+                    let Some(column) = self.tables[table].columns.iter_mut().find(|c| c.name == column_name.value) else {
+                        self.warnings.push(format!(
+                            "line {}: column '{}.{}' does not exist, statement skipped",
+                            line, table_name, column_name.value
+                        ));
+                        continue;
+                    };
+                    match op {
+                        AlterColumnOperation::SetNotNull => column.nullable = false,
+                        AlterColumnOperation::DropNotNull => column.nullable = true,
+                        AlterColumnOperation::SetDefault { value } => {
+                            let default = value.to_string();
+                            column.default = (!default.to_lowercase().starts_with("nextval(")).then_some(default);
+                        }
+                        AlterColumnOperation::DropDefault => column.default = None,
+                        AlterColumnOperation::SetDataType { data_type, .. } => column.data_type = data_type.to_string(),
+                        AlterColumnOperation::AddGenerated { .. } => {}
+                    }
+                }
+                AlterTableOperation::OwnerTo { .. } => {}
+                other => self.warnings.push(format!(
+                    "line {}: unsupported ALTER TABLE operation on '{}' skipped: {}",
+                    line, table_name, other
+                )),
+            }
+        }
+    }
+
+    fn create_index(&mut self, create: &CreateIndex, line: usize) {
+        let columns: Option<Vec<String>> = create.columns.iter().map(|c| column_name(&c.expr)).collect();
+        let Some(columns) = columns else {
+            self.warnings.push(format!(
+                "line {}: expression index on '{}' is not supported, skipped",
+                line,
+                last_name(&create.table_name)
+            ));
+            return;
+        };
+        if create.predicate.is_some() {
+            self.warnings.push(format!(
+                "line {}: partial index on '{}' imported without its WHERE clause",
+                line,
+                last_name(&create.table_name)
+            ));
+        }
+        let Some(table) = self.table(&create.table_name, line) else {
+            return;
+        };
+        let name = create
+            .name
+            .as_ref()
+            .map_or(format!("ix_{}_{}", table.name, columns.join("_")), last_name);
+        // A unique index on one column is rendered as the column's `unique=True`.
+        if let (true, [single]) = (create.unique, columns.as_slice())
+            && let Some(column) = table.columns.iter_mut().find(|c| &c.name == single)
+        {
+            column.unique = true;
+        }
+        table.indexes.push(Index {
+            name,
+            columns,
+            unique: create.unique,
+        });
+    }
+}
+
+/// Parses `CREATE TABLE`, `ALTER TABLE`, `CREATE INDEX` and enum `CREATE TYPE`
+/// statements of a (Postgres) DDL script into tables. Every statement that
+/// cannot be represented is reported in the returned warnings.
+pub fn parse_ddl(sql: &str) -> (Vec<Table>, Vec<String>) {
+    let dialect = PostgreSqlDialect {};
+    let mut parsed = Parsed {
+        tables: Vec::new(),
+        enums: Vec::new(),
+        warnings: Vec::new(),
+    };
+    for chunk in split_statements(sql) {
+        let words: Vec<String> = chunk.sql.split_whitespace().take(4).map(str::to_uppercase).collect();
+        let head = words.join(" ");
+        let is = |prefix: &str| head == prefix || head.starts_with(&format!("{} ", prefix));
+        if IGNORED.iter().any(|prefix| is(prefix)) || (is("ALTER TABLE") && head.contains("OWNER")) {
+            continue;
+        }
+        let supported = ["CREATE TABLE", "CREATE UNLOGGED TABLE", "ALTER TABLE", "CREATE INDEX", "CREATE UNIQUE INDEX", "CREATE TYPE"];
+        if !supported.iter().any(|prefix| is(prefix)) {
+            let kind: Vec<&str> = words.iter().take(2).map(String::as_str).collect();
+            parsed.warnings.push(format!("line {}: unsupported statement '{} ...' skipped", chunk.line, kind.join(" ")));
+            continue;
+        }
+        let statements = match Parser::parse_sql(&dialect, &chunk.sql) {
+            Ok(statements) => statements,
+            Err(error) => {
+                parsed.warnings.push(format!("line {}: could not parse '{} ...', skipped ({})", chunk.line, head, error));
+                continue;
+            }
+        };
+        for statement in &statements {
+            match statement {
+                Statement::CreateTable(create) => parsed.create_table(create, chunk.line),
+                Statement::AlterTable { name, operations, .. } => parsed.alter_table(name, operations, chunk.line),
+                Statement::CreateIndex(create) => parsed.create_index(create, chunk.line),
+                Statement::CreateType {
+                    name,
+                    representation: UserDefinedTypeRepresentation::Enum { .. },
+                } => parsed.enums.push(last_name(name)),
+                _ => parsed.warnings.push(format!("line {}: unsupported statement '{} ...' skipped", chunk.line, head)),
+            }
+        }
+    }
+    (parsed.tables, parsed.warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::tables::render_table;
+
+    #[test]
+    fn single_column_unique_index_keeps_uniqueness() {
+        let (tables, warnings) = parse_ddl("CREATE TABLE t (id integer PRIMARY KEY, email text);\nCREATE UNIQUE INDEX ux ON t (email);");
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let email = tables[0].columns.iter().find(|c| c.name == "email").unwrap();
+        assert!(email.unique);
+        let model = render_table("# Model: t", &tables[0], &tables);
+        assert!(model.contains("unique=True"), "{}", model);
+    }
+
+    #[test]
+    fn multi_column_unique_index_becomes_constraint() {
+        let (tables, _) = parse_ddl("CREATE TABLE t (a integer, b integer);\nCREATE UNIQUE INDEX ux_ab ON t (a, b);");
+        assert!(tables[0].columns.iter().all(|c| !c.unique));
+        let model = render_table("# Model: t", &tables[0], &tables);
+        assert!(model.contains("UniqueConstraint(\"a\", \"b\", name=\"ux_ab\")"), "{}", model);
+    }
+
+    #[test]
+    fn plain_index_is_rendered() {
+        let (tables, _) = parse_ddl("CREATE TABLE t (id integer PRIMARY KEY, name text);\nCREATE INDEX ix_name ON t (name);");
+        assert!(!tables[0].columns[1].unique);
+        let model = render_table("# Model: t", &tables[0], &tables);
+        assert!(model.contains("Index(\"ix_name\", \"name\")"), "{}", model);
+    }
+
+    #[test]
+    fn table_without_primary_key_is_not_mapped() {
+        let sql = "CREATE TABLE public.audit_log (\n    at timestamp NOT NULL,\n    message text\n);\n\
+                   CREATE TABLE public.orders (id integer NOT NULL, total numeric(10, 2));\n\
+                   ALTER TABLE ONLY public.orders ADD CONSTRAINT orders_pkey PRIMARY KEY (id);";
+        let (tables, warnings) = parse_ddl(sql);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        let (audit_log, orders) = (&tables[0], &tables[1]);
+        assert!(!audit_log.is_mapped() && orders.is_mapped());
+
+        let model = render_table("# Model: audit_log", audit_log, &tables);
+        assert!(!model.contains("(Base):"), "{}", model);
+        assert!(model.contains("audit_log_table = Table(\n    \"audit_log\",\n    Base.metadata,\n"), "{}", model);
+        assert!(model.contains("    Column(\"message\", Text, nullable=True),\n)\n"), "{}", model);
+        let model = render_table("# Model: orders", orders, &tables);
+        assert!(model.contains("class Order(Base):"), "{}", model);
+    }
+
+    #[test]
+    fn inline_unique_and_primary_key() {
+        let (tables, _) = parse_ddl("CREATE TABLE users (id serial PRIMARY KEY, email varchar(255) NOT NULL UNIQUE);");
+        let table = &tables[0];
+        assert!(table.columns[0].primary_key);
+        assert!(table.columns[1].unique && !table.columns[1].nullable);
+    }
+
+    #[test]
+    fn unsupported_statements_are_reported() {
+        let (tables, warnings) = parse_ddl("CREATE VIEW v AS SELECT 1;\nCREATE TABLE t (id integer);\nCREATE INDEX ON t ((lower(id::text)));");
+        assert_eq!(tables.len(), 1);
+        assert!(warnings.iter().any(|w| w.contains("line 1") && w.contains("CREATE VIEW")), "{:?}", warnings);
+        assert!(warnings.iter().any(|w| w.contains("expression index")), "{:?}", warnings);
+    }
+
+    #[test]
+    fn statements_are_split_outside_strings_and_comments() {
+        let (tables, warnings) = parse_ddl("-- a; comment\nCREATE TABLE t (note text DEFAULT 'a;b');\nCREATE TABLE u (id integer);");
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(tables.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), ["t", "u"]);
+    }
+}
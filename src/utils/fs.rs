@@ -1,8 +1,14 @@
 use std::fs::{self, OpenOptions};
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use crate::utils::python::{included_router, logical_lines};
 
 pub fn append_to_init(init_path: &str, name: &str) {
+    let import_line = format!("from .{} import router as {}_router", name, name);
+    append_import(init_path, &import_line, name);
+}
+
+pub fn append_import(init_path: &str, import_line: &str, name: &str) {
     let mut content = String::new();
     if Path::new(init_path).exists() {
         fs::File::open(init_path)
@@ -11,8 +17,7 @@ pub fn append_to_init(init_path: &str, name: &str) {
             .unwrap();
     }
 
-    let import_line = format!("from .{} import router as {}_router", name, name);
-    if !content.contains(&import_line) {
+    if !content.contains(import_line) {
         let mut file = OpenOptions::new()
             .append(true)
             .create(true)
@@ -24,3 +29,97 @@ pub fn append_to_init(init_path: &str, name: &str) {
         println!("ℹ️  '{}' already registered in '{}'", name, init_path);
     }
 }
+
+pub fn remove_from_init(init_path: &str, name: &str) -> bool {
+    let Ok(content) = fs::read_to_string(init_path) else {
+        return false;
+    };
+
+    let module_import = format!("from .{} import", name);
+    let package_import = format!("from . import {}", name);
+    let kept: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            let line = line.trim();
+            !line.starts_with(&module_import) && line != package_import
+        })
+        .collect();
+
+    if kept.len() == content.lines().count() {
+        return false;
+    }
+
+    let mut updated = kept.join("\n");
+    if !updated.is_empty() {
+        updated.push('\n');
+    }
+    fs::write(init_path, updated).unwrap();
+    println!("🔗 Updated '{}'", init_path);
+    true
+}
+
+pub fn python_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let skip = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.') || n == "__pycache__");
+            if !skip {
+                files.extend(python_files(&path));
+            }
+        } else if path.extension().is_some_and(|ext| ext == "py") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    files
+}
+
+/// Imports `<name>_router` from `app.routes` in `main.py` and includes it in the
+/// app, next to the routers already there. Returns false when it was included.
+pub fn include_router(main_py: &Path, name: &str) -> std::io::Result<bool> {
+    let content = fs::read_to_string(main_py)?;
+    let router = format!("{}_router", name);
+    if logical_lines(&content).iter().any(|(_, line)| included_router(line.trim()).as_deref() == Some(router.as_str())) {
+        return Ok(false);
+    }
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    match lines.iter().position(|l| l.starts_with("from app.routes import ")) {
+        Some(index) => lines[index] = format!("{}, {}", lines[index].trim_end(), router),
+        None => {
+            let last_import = lines
+                .iter()
+                .rposition(|l| l.starts_with("import ") || l.starts_with("from "))
+                .map_or(0, |i| i + 1);
+            lines.insert(last_import, format!("from app.routes import {}", router));
+        }
+    }
+
+    let include = format!("app.include_router({})", router);
+    match lines.iter().rposition(|l| l.starts_with("app.include_router(")) {
+        Some(index) => lines.insert(index + 1, include),
+        None => {
+            let at = lines
+                .iter()
+                .position(|l| l.starts_with("if __name__"))
+                .unwrap_or(lines.len());
+            lines.insert(at, include);
+            lines.insert(at + 1, String::new());
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    fs::write(main_py, updated)?;
+    println!("🔗 Included '{}' in '{}'", router, main_py.display());
+    Ok(true)
+}
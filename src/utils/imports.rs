@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::utils::fs::python_files;
+use crate::utils::python::{logical_lines, split_top_level};
+
+#[derive(Debug, Clone)]
+pub struct ImportEdge {
+    pub from: String,
+    pub to: String,
+    pub line: usize,
+}
+
+/// Static import graph of a project's Python packages: module name to file, plus
+/// one edge per imported module.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    pub modules: BTreeMap<String, PathBuf>,
+    pub edges: Vec<ImportEdge>,
+}
+
+pub fn module_for_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?.with_extension("");
+    let mut parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    if parts.last().is_some_and(|p| p == "__init__") {
+        parts.pop();
+    }
+    (!parts.is_empty()).then(|| parts.join("."))
+}
+
+/// Returns `(line, module)` for every import statement; relative imports are
+/// resolved against `module` (`is_package` when the file is an `__init__.py`).
+/// `from pkg import name` yields `pkg.name`, callers decide whether it is a module.
+pub fn parse_imports(source: &str, module: &str, is_package: bool) -> Vec<(usize, String, String)> {
+    let mut imports = Vec::new();
+    for (number, line) in logical_lines(source) {
+        for statement in line.split(';') {
+            let statement = statement.trim();
+            if let Some(rest) = statement.strip_prefix("import ") {
+                for name in split_top_level(rest, ',') {
+                    let name = name.split(" as ").next().unwrap_or("").trim();
+                    if !name.is_empty() {
+                        imports.push((number, name.to_string(), name.to_string()));
+                    }
+                }
+            } else if let Some(rest) = statement.strip_prefix("from ") {
+                let Some((base, names)) = rest.split_once(" import ") else {
+                    continue;
+                };
+                let Some(base) = resolve_relative(base.trim(), module, is_package) else {
+                    continue;
+                };
+                let names = names.trim().trim_start_matches('(').trim_end_matches(')');
+                for name in split_top_level(names, ',') {
+                    let name = name.split(" as ").next().unwrap_or("").trim();
+                    if name.is_empty() || name == "*" {
+                        imports.push((number, base.clone(), base.clone()));
+                    } else if base.is_empty() {
+                        imports.push((number, name.to_string(), name.to_string()));
+                    } else {
+                        imports.push((number, base.clone(), format!("{}.{}", base, name)));
+                    }
+                }
+            }
+        }
+    }
+    imports
+}
+
+fn resolve_relative(base: &str, module: &str, is_package: bool) -> Option<String> {
+    let dots = base.chars().take_while(|c| *c == '.').count();
+    if dots == 0 {
+        return Some(base.to_string());
+    }
+    let mut package: Vec<&str> = module.split('.').collect();
+    if !is_package {
+        package.pop();
+    }
+    for _ in 1..dots {
+        package.pop()?;
+    }
+    let rest = &base[dots..];
+    if !rest.is_empty() {
+        package.push(rest);
+    }
+    Some(package.join("."))
+}
+
+impl ModuleGraph {
+    /// Scans every Python file under `root/<package>` for each top-level package.
+    pub fn scan(root: &Path, packages: &[&str]) -> ModuleGraph {
+        let mut graph = ModuleGraph::default();
+        for package in packages {
+            for path in python_files(&root.join(package)) {
+                if let Some(module) = module_for_path(root, &path) {
+                    graph.modules.insert(module, path);
+                }
+            }
+        }
+
+        let sources: Vec<(String, String, bool)> = graph
+            .modules
+            .iter()
+            .filter_map(|(module, path)| {
+                let source = fs::read_to_string(path).ok()?;
+                Some((module.clone(), source, path.file_stem().is_some_and(|s| s == "__init__")))
+            })
+            .collect();
+        graph.link(&sources);
+        graph
+    }
+
+    /// Adds the edges of every `(module, source, is_package)` once all modules are known.
+    fn link(&mut self, sources: &[(String, String, bool)]) {
+        for (module, source, is_package) in sources {
+            for (line, base, candidate) in parse_imports(source, module, *is_package) {
+                // `from pkg import name` only targets `pkg.name` when that is a module.
+                let to = if self.modules.contains_key(&candidate) { candidate } else { base };
+                if to != *module {
+                    self.edges.push(ImportEdge {
+                        from: module.clone(),
+                        to,
+                        line,
+                    });
+                }
+            }
+        }
+    }
+
+    pub fn is_internal(&self, module: &str) -> bool {
+        self.modules.contains_key(module)
+            || self.modules.keys().any(|m| m.starts_with(&format!("{}.", module)))
+    }
+}
+
+/// Returns `(local name, module, attribute)` for every name bound by an import
+/// statement; `import a.b` binds `a`, `from a import b as c` binds `c`.
+pub fn imported_names(source: &str, module: &str, is_package: bool) -> Vec<(String, String, Option<String>)> {
+    let mut names = Vec::new();
+    for (_, line) in logical_lines(source) {
+        for statement in line.split(';') {
+            let statement = statement.trim();
+            if let Some(rest) = statement.strip_prefix("import ") {
+                for name in split_top_level(rest, ',') {
+                    let (target, alias) = match name.split_once(" as ") {
+                        Some((target, alias)) => (target.trim(), alias.trim()),
+                        None => (name.trim(), name.trim().split('.').next().unwrap_or("")),
+                    };
+                    if !target.is_empty() {
+                        names.push((alias.to_string(), target.to_string(), None));
+                    }
+                }
+            } else if let Some(rest) = statement.strip_prefix("from ") {
+                let Some((base, imported)) = rest.split_once(" import ") else {
+                    continue;
+                };
+                let Some(base) = resolve_relative(base.trim(), module, is_package) else {
+                    continue;
+                };
+                let imported = imported.trim().trim_start_matches('(').trim_end_matches(')');
+                for name in split_top_level(imported, ',') {
+                    let (target, alias) = match name.split_once(" as ") {
+                        Some((target, alias)) => (target.trim(), alias.trim()),
+                        None => (name.trim(), name.trim()),
+                    };
+                    if !target.is_empty() && target != "*" {
+                        names.push((alias.to_string(), base.clone(), Some(target.to_string())));
+                    }
+                }
+            }
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(sources: &[(&str, &str, bool)]) -> Vec<(String, String, usize)> {
+        let mut graph = ModuleGraph::default();
+        for (module, _, _) in sources {
+            graph.modules.insert(module.to_string(), PathBuf::new());
+        }
+        let sources: Vec<(String, String, bool)> =
+            sources.iter().map(|(m, s, p)| (m.to_string(), s.to_string(), *p)).collect();
+        graph.link(&sources);
+        graph.edges.into_iter().map(|e| (e.from, e.to, e.line)).collect()
+    }
+
+    #[test]
+    fn relative_imports_resolve_from_modules_and_packages() {
+        assert_eq!(resolve_relative(".models", "app.routes.users", false).as_deref(), Some("app.routes.models"));
+        assert_eq!(resolve_relative("..core", "app.routes.users", false).as_deref(), Some("app.core"));
+        assert_eq!(resolve_relative(".", "app.routes", true).as_deref(), Some("app.routes"));
+        assert_eq!(resolve_relative("..", "app.routes", true).as_deref(), Some("app"));
+        assert_eq!(resolve_relative("....x", "app.routes.users", false), None);
+        assert_eq!(resolve_relative("fastapi", "app.main", false).as_deref(), Some("fastapi"));
+    }
+
+    #[test]
+    fn imported_names_target_modules_only_when_they_exist() {
+        let edges = graph(&[
+            ("app.routes", "", true),
+            ("app.routes.users", "from . import items, helpers\nfrom ..core.config import (\n    settings,\n)\nimport fastapi, app.models as m\n", false),
+            ("app.routes.items", "from .users import *\n", false),
+            ("app.core.config", "", false),
+            ("app.models", "", true),
+        ]);
+        assert_eq!(
+            edges,
+            [
+                ("app.routes.users".into(), "app.routes.items".into(), 1),
+                ("app.routes.users".into(), "app.routes".into(), 1),
+                ("app.routes.users".into(), "app.core.config".into(), 2),
+                ("app.routes.users".into(), "fastapi".into(), 5),
+                ("app.routes.users".into(), "app.models".into(), 5),
+                ("app.routes.items".into(), "app.routes.users".into(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn names_bound_by_imports() {
+        let names = imported_names("import os.path, numpy as np\nfrom .users import router as users_router, Item\n", "app.routes", true);
+        assert_eq!(
+            names,
+            [
+                ("os".to_string(), "os.path".to_string(), None),
+                ("np".to_string(), "numpy".to_string(), None),
+                ("users_router".to_string(), "app.routes.users".to_string(), Some("router".to_string())),
+                ("Item".to_string(), "app.routes.users".to_string(), Some("Item".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn module_names_from_paths() {
+        let root = Path::new("/project");
+        assert_eq!(module_for_path(root, Path::new("/project/app/routes/__init__.py")).as_deref(), Some("app.routes"));
+        assert_eq!(module_for_path(root, Path::new("/project/app/main.py")).as_deref(), Some("app.main"));
+        assert_eq!(module_for_path(root, Path::new("/elsewhere/x.py")), None);
+    }
+}
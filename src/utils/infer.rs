@@ -0,0 +1,410 @@
+use std::collections::BTreeSet;
+use serde_json::{json, Map, Value};
+use crate::utils::python::typing_names;
+use crate::utils::spec::python_literal;
+use crate::utils::string::{to_camel_case, to_field_names, to_singular, to_snake_case};
+
+/// What a JSON value looked like across every sample it appeared in.
+#[derive(Default)]
+struct Shape {
+    null: bool,
+    boolean: bool,
+    integer: bool,
+    float: bool,
+    strings: BTreeSet<&'static str>,
+    /// Set once an array was seen, merging all of its items.
+    items: Option<Box<Shape>>,
+    object: Option<ObjectShape>,
+}
+
+#[derive(Default)]
+struct ObjectShape {
+    samples: usize,
+    /// Field name, its shape and how many objects had it.
+    fields: Vec<(String, Shape, usize)>,
+}
+
+pub struct InferredField {
+    pub wire: String,
+    pub name: String,
+    pub annotation: String,
+    pub optional: bool,
+}
+
+pub struct InferredModel {
+    pub class: String,
+    pub fields: Vec<InferredField>,
+}
+
+fn string_kind(text: &str) -> &'static str {
+    let bytes = text.as_bytes();
+    let digits = |range: std::ops::Range<usize>| bytes[range].iter().all(u8::is_ascii_digit);
+    let is_date = bytes.len() >= 10 && digits(0..4) && bytes[4] == b'-' && digits(5..7) && bytes[7] == b'-' && digits(8..10);
+    if is_date && bytes.len() == 10 {
+        return "date";
+    }
+    if is_date && bytes.len() >= 19 && matches!(bytes[10], b'T' | b' ') && bytes[13] == b':' && bytes[16] == b':' {
+        return "datetime";
+    }
+    let is_uuid = bytes.len() == 36
+        && text.char_indices().all(|(i, c)| if [8, 13, 18, 23].contains(&i) { c == '-' } else { c.is_ascii_hexdigit() });
+    if is_uuid { "UUID" } else { "str" }
+}
+
+impl Shape {
+    fn observe(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.null = true,
+            Value::Bool(_) => self.boolean = true,
+            Value::Number(number) if number.is_f64() => self.float = true,
+            Value::Number(_) => self.integer = true,
+            Value::String(text) => {
+                self.strings.insert(string_kind(text));
+            }
+            Value::Array(items) => {
+                let shape = self.items.get_or_insert_with(Box::default);
+                for item in items {
+                    shape.observe(item);
+                }
+            }
+            Value::Object(map) => {
+                let object = self.object.get_or_insert_with(ObjectShape::default);
+                object.samples += 1;
+                for (key, value) in map {
+                    match object.fields.iter_mut().find(|(name, _, _)| name == key) {
+                        Some((_, shape, seen)) => {
+                            shape.observe(value);
+                            *seen += 1;
+                        }
+                        None => {
+                            let mut shape = Shape::default();
+                            shape.observe(value);
+                            object.fields.push((key.clone(), shape, 1));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        !(self.null || self.boolean || self.integer || self.float)
+            && self.strings.is_empty()
+            && self.items.is_none()
+            && self.object.is_none()
+    }
+}
+
+struct Inference {
+    models: Vec<InferredModel>,
+}
+
+impl Inference {
+    /// Class name for a nested object, prefixed with its parent when the short
+    /// name is already taken: "customer" -> "Customer", then "InvoiceCustomer".
+    fn class_name(&self, hint: &str, parent: &str) -> String {
+        let base = to_camel_case(&to_snake_case(hint));
+        let base = if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("{}Item", parent)
+        } else {
+            base
+        };
+        let taken = |name: &str| self.models.iter().any(|m| m.class == name);
+        if !taken(&base) {
+            return base;
+        }
+        let prefixed = format!("{}{}", parent, base);
+        let mut candidate = prefixed.clone();
+        let mut counter = 2;
+        while taken(&candidate) {
+            candidate = format!("{}{}", prefixed, counter);
+            counter += 1;
+        }
+        candidate
+    }
+
+    fn annotation(&mut self, shape: &Shape, hint: &str, parent: &str) -> String {
+        let mut types = Vec::new();
+        if shape.boolean {
+            types.push("bool".to_string());
+        }
+        match (shape.integer, shape.float) {
+            (true, false) => types.push("int".to_string()),
+            (_, true) => types.push("float".to_string()),
+            _ => {}
+        }
+        match shape.strings.len() {
+            0 => {}
+            1 => types.push(shape.strings.iter().next().unwrap_or(&"str").to_string()),
+            _ => types.push("str".to_string()),
+        }
+        if let Some(items) = &shape.items {
+            let item = if items.is_empty() {
+                "Any".to_string()
+            } else {
+                let annotation = self.annotation(items, &to_singular(&to_snake_case(hint)), parent);
+                if items.null && annotation != "Any" { format!("Optional[{}]", annotation) } else { annotation }
+            };
+            types.push(format!("List[{}]", item));
+        }
+        if let Some(object) = &shape.object {
+            if object.fields.is_empty() {
+                types.push("Dict[str, Any]".to_string());
+            } else {
+                let class = self.class_name(hint, parent);
+                self.model(object, &class);
+                types.push(class);
+            }
+        }
+        match types.len() {
+            0 => "Any".to_string(),
+            1 => types.remove(0),
+            _ => format!("Union[{}]", types.join(", ")),
+        }
+    }
+
+    fn model(&mut self, object: &ObjectShape, class: &str) {
+        // Reserve the name before nested classes are named.
+        let index = self.models.len();
+        self.models.push(InferredModel {
+            class: class.to_string(),
+            fields: Vec::new(),
+        });
+        let names = to_field_names(object.fields.iter().map(|(wire, _, _)| wire.as_str()));
+        let mut fields = Vec::new();
+        for ((wire, shape, seen), name) in object.fields.iter().zip(names) {
+            fields.push(InferredField {
+                wire: wire.clone(),
+                name,
+                annotation: self.annotation(shape, wire, class),
+                optional: shape.null || *seen < object.samples,
+            });
+        }
+        // Nested classes must be defined before the model that uses them.
+        let mut model = self.models.remove(index);
+        model.fields = fields;
+        self.models.push(model);
+    }
+}
+
+/// Infers Pydantic models from JSON samples of the same payload: fields missing
+/// from some samples or null in any become optional. A top-level array counts
+/// each of its objects as a sample.
+pub fn infer_models(class: &str, samples: &[Value]) -> Result<Vec<InferredModel>, String> {
+    let mut shape = Shape::default();
+    for sample in samples {
+        match sample {
+            Value::Object(_) => shape.observe(sample),
+            Value::Array(items) if items.iter().all(Value::is_object) && !items.is_empty() => {
+                items.iter().for_each(|item| shape.observe(item))
+            }
+            _ => return Err("Samples must be JSON objects or arrays of objects".to_string()),
+        }
+    }
+    let object = shape.object.ok_or("No JSON samples given")?;
+    let mut inference = Inference { models: Vec::new() };
+    inference.model(&object, class);
+    Ok(inference.models)
+}
+
+/// Splits the arguments of a generic annotation at its top-level commas.
+fn type_arguments(inner: &str) -> Vec<&str> {
+    let mut arguments = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    arguments.push(inner[start..].trim());
+    arguments
+}
+
+/// JSON schema for an annotation produced by the inference; classes become
+/// `#/components/schemas` references.
+fn annotation_schema(annotation: &str) -> Value {
+    let generic = |prefix: &str| annotation.strip_prefix(prefix).and_then(|rest| rest.strip_suffix(']'));
+    if let Some(inner) = generic("Optional[") {
+        return json!({"anyOf": [annotation_schema(inner), {"type": "null"}]});
+    }
+    if let Some(inner) = generic("List[") {
+        return json!({"type": "array", "items": annotation_schema(inner)});
+    }
+    if let Some(inner) = generic("Union[") {
+        return json!({"anyOf": type_arguments(inner).into_iter().map(annotation_schema).collect::<Vec<_>>()});
+    }
+    match annotation {
+        "Any" => json!({}),
+        "Dict[str, Any]" => json!({"type": "object"}),
+        "bool" => json!({"type": "boolean"}),
+        "int" => json!({"type": "integer"}),
+        "float" => json!({"type": "number"}),
+        "str" => json!({"type": "string"}),
+        "date" => json!({"type": "string", "format": "date"}),
+        "datetime" => json!({"type": "string", "format": "date-time"}),
+        "UUID" => json!({"type": "string", "format": "uuid"}),
+        class => json!({"$ref": format!("#/components/schemas/{}", class)}),
+    }
+}
+
+/// Infers the same models as `infer_models`, as OpenAPI component schemas
+/// keyed by class name; properties keep their wire names.
+pub fn infer_schemas(class: &str, samples: &[Value]) -> Result<Vec<(String, Value)>, String> {
+    let models = infer_models(class, samples)?;
+    Ok(models
+        .iter()
+        .map(|model| {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for field in &model.fields {
+                properties.insert(field.wire.clone(), annotation_schema(&field.annotation));
+                if !field.optional {
+                    required.push(json!(field.wire));
+                }
+            }
+            let mut schema = json!({"type": "object", "properties": properties});
+            if !required.is_empty() {
+                schema["required"] = Value::Array(required);
+            }
+            (model.class.clone(), schema)
+        })
+        .collect())
+}
+
+/// Renders inferred models as a Python module, with its imports.
+pub fn render_models(header: &str, models: &[InferredModel]) -> String {
+    let mut blocks = Vec::new();
+    for model in models {
+        let mut body = String::new();
+        for field in &model.fields {
+            let annotation = if field.optional && field.annotation != "Any" && !field.annotation.starts_with("Optional[") {
+                format!("Optional[{}]", field.annotation)
+            } else {
+                field.annotation.clone()
+            };
+            let default = if field.optional { "None" } else { "..." };
+            let line = if field.name != field.wire {
+                format!("{}: {} = Field({}, alias={})", field.name, annotation, default, python_literal(&Value::String(field.wire.clone())))
+            } else if field.optional {
+                format!("{}: {} = None", field.name, annotation)
+            } else {
+                format!("{}: {}", field.name, annotation)
+            };
+            body.push_str(&format!("    {}\n", line));
+        }
+        if model.fields.iter().any(|f| f.name != f.wire) {
+            body.push_str("\n    model_config = ConfigDict(populate_by_name=True)\n");
+        }
+        if body.is_empty() {
+            body.push_str("    pass\n");
+        }
+        blocks.push(format!("class {}(BaseModel):\n{}", model.class, body));
+    }
+    let code = blocks.join("\n\n");
+
+    let annotations: Vec<&str> = models.iter().flat_map(|m| m.fields.iter().map(|f| f.annotation.as_str())).collect();
+    let used = |name: &str| {
+        annotations
+            .iter()
+            .any(|a| a.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|word| word == name))
+    };
+    let mut standard = Vec::new();
+    let datetime: Vec<&str> = ["date", "datetime"].into_iter().filter(|name| used(name)).collect();
+    if !datetime.is_empty() {
+        standard.push(format!("from datetime import {}", datetime.join(", ")));
+    }
+    let typing = typing_names(&[code.as_str()]);
+    if !typing.is_empty() {
+        standard.push(format!("from typing import {}", typing.join(", ")));
+    }
+    if used("UUID") {
+        standard.push("from uuid import UUID".to_string());
+    }
+    let mut pydantic = vec!["BaseModel"];
+    pydantic.extend(["ConfigDict", "Field"].into_iter().filter(|name| code.contains(&format!("{}(", name))));
+
+    let mut imports = String::new();
+    if !standard.is_empty() {
+        imports.push_str(&format!("{}\n\n", standard.join("\n")));
+    }
+    imports.push_str(&format!("from pydantic import {}\n", pydantic.join(", ")));
+    format!("{}\n{}\n\n{}", header, imports, code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(model: &InferredModel) -> Vec<(&str, &str, &str, bool)> {
+        model.fields.iter().map(|f| (f.wire.as_str(), f.name.as_str(), f.annotation.as_str(), f.optional)).collect()
+    }
+
+    #[test]
+    fn samples_merge_into_optional_fields() {
+        let samples = [
+            json!({"id": 1, "price": 2, "name": "a", "when": "2026-01-01", "tags": ["x"]}),
+            json!({"id": 2, "price": 2.5, "name": null, "when": "2026-01-01T10:00:00Z", "ref": "123e4567-e89b-12d3-a456-426614174000"}),
+        ];
+        let models = infer_models("Order", &samples).unwrap();
+        assert_eq!(
+            fields(&models[0]),
+            [
+                ("id", "id", "int", false),
+                ("price", "price", "float", false),
+                ("name", "name", "str", true),
+                ("when", "when", "str", false),
+                ("tags", "tags", "List[str]", true),
+                ("ref", "ref", "UUID", true),
+            ]
+        );
+        assert_eq!(string_kind("2026-01-01"), "date");
+        assert_eq!(string_kind("2026-01-01 10:00:00"), "datetime");
+        assert!(infer_models("Order", &[json!(1)]).is_err());
+    }
+
+    #[test]
+    fn nested_objects_become_classes_defined_first() {
+        let samples = [json!({
+            "customer": {"name": "a"},
+            "lines": [{"sku": "x", "customer": {"email": "e"}}],
+            "meta": {},
+        })];
+        let models = infer_models("Invoice", &samples).unwrap();
+        let classes: Vec<&str> = models.iter().map(|m| m.class.as_str()).collect();
+        assert_eq!(classes, ["Customer", "LineCustomer", "Line", "Invoice"]);
+        let invoice = fields(models.last().unwrap());
+        assert_eq!(invoice[1].2, "List[Line]");
+        assert_eq!(invoice[2].2, "Dict[str, Any]");
+    }
+
+    #[test]
+    fn renamed_fields_keep_their_alias() {
+        let models = infer_models("User", &[json!({"userName": "a", "_id": "b", "class": 1, "note": null})]).unwrap();
+        let code = render_models("# header", &models);
+        assert!(code.contains("user_name: str = Field(..., alias=\"userName\")"), "{}", code);
+        assert!(code.contains("field_id: str = Field(..., alias=\"_id\")"), "{}", code);
+        assert!(code.contains("class_: int = Field(..., alias=\"class\")"), "{}", code);
+        assert!(code.contains("note: Any = None"), "{}", code);
+        assert!(code.contains("model_config = ConfigDict(populate_by_name=True)"), "{}", code);
+        assert!(code.contains("from typing import Any\n\nfrom pydantic import BaseModel, ConfigDict, Field\n"), "{}", code);
+    }
+
+    #[test]
+    fn schemas_use_wire_names_and_references() {
+        let schemas = infer_schemas("Pet", &[json!({"petId": 1, "owner": {"name": "a"}, "size": [1, null]})]).unwrap();
+        assert_eq!(schemas[0].0, "Owner");
+        let (class, pet) = &schemas[1];
+        assert_eq!(class, "Pet");
+        assert_eq!(pet["properties"]["petId"], json!({"type": "integer"}));
+        assert_eq!(pet["properties"]["owner"], json!({"$ref": "#/components/schemas/Owner"}));
+        assert_eq!(pet["properties"]["size"]["items"], json!({"anyOf": [{"type": "integer"}, {"type": "null"}]}));
+        assert_eq!(pet["required"], json!(["petId", "owner", "size"]));
+        assert_eq!(type_arguments("int, List[Union[str, int]]"), ["int", "List[Union[str, int]]"]);
+    }
+}
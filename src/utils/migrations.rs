@@ -0,0 +1,318 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An Alembic revision read from `migrations/versions`.
+pub struct Revision {
+    pub id: String,
+    /// Parent revisions: none for the base, several for a merge.
+    pub down: Vec<String>,
+    pub message: String,
+    pub file: PathBuf,
+}
+
+/// The versions directory of the Alembic environment, following the
+/// `script_location` of `alembic.ini`.
+pub fn versions_dir(root: &Path) -> PathBuf {
+    let ini = fs::read_to_string(root.join("alembic.ini")).unwrap_or_default();
+    let location = ini
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "script_location")
+        .map(|(_, value)| value.trim().replace("%(here)s/", ""))
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "migrations".to_string());
+    root.join(location).join("versions")
+}
+
+/// Value assigned to `name` on a line such as `down_revision: Union[str, None] = 'abc'`.
+fn assignment<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(name)?;
+    if !rest.starts_with([' ', ':', '=']) {
+        return None;
+    }
+    let (_, value) = rest.split_once('=')?;
+    Some(value.trim())
+}
+
+/// Quoted strings of a Python literal: `'a'`, `("a", "b")`, `None`.
+fn strings(value: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\'' || c == '"' {
+            let text: String = chars.by_ref().take_while(|next| *next != c).collect();
+            found.push(text);
+        } else if c == '#' {
+            break;
+        }
+    }
+    found
+}
+
+fn parse_revision(source: &str, file: &Path) -> Option<Revision> {
+    let mut id = None;
+    let mut down = Vec::new();
+    for line in source.lines() {
+        if let Some(value) = assignment(line, "revision") {
+            id = strings(value).into_iter().next();
+        } else if let Some(value) = assignment(line, "down_revision") {
+            down = strings(value);
+        }
+    }
+    let message = source
+        .trim_start()
+        .strip_prefix("\"\"\"")
+        .and_then(|doc| doc.lines().next())
+        .map(|line| line.trim().trim_end_matches("\"\"\"").to_string())
+        .unwrap_or_default();
+    Some(Revision {
+        id: id?,
+        down,
+        message,
+        file: file.to_path_buf(),
+    })
+}
+
+/// Reads every revision file, sorted by file name.
+pub fn read_revisions(dir: &Path) -> Vec<Revision> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|e| e == "py")).collect())
+        .unwrap_or_default();
+    files.sort();
+    files
+        .iter()
+        .filter_map(|file| parse_revision(&fs::read_to_string(file).ok()?, file))
+        .collect()
+}
+
+/// Revisions no other revision builds on.
+pub fn heads(revisions: &[Revision]) -> Vec<&Revision> {
+    let parents: BTreeSet<&str> = revisions.iter().flat_map(|r| r.down.iter().map(String::as_str)).collect();
+    revisions.iter().filter(|r| !parents.contains(r.id.as_str())).collect()
+}
+
+pub fn children<'a>(revisions: &'a [Revision], id: &str) -> Vec<&'a Revision> {
+    revisions.iter().filter(|r| r.down.iter().any(|d| d == id)).collect()
+}
+
+/// Duplicate ids and parents that do not exist, which break the chain.
+pub fn problems(revisions: &[Revision]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut seen = BTreeSet::new();
+    for revision in revisions {
+        if !seen.insert(revision.id.as_str()) {
+            problems.push(format!("revision '{}' is defined twice ({})", revision.id, revision.file.display()));
+        }
+        for parent in revision.down.iter().filter(|d| !revisions.iter().any(|r| &r.id == *d)) {
+            problems.push(format!(
+                "revision '{}' ({}) revises '{}', which does not exist",
+                revision.id,
+                revision.file.display(),
+                parent
+            ));
+        }
+    }
+    if !revisions.is_empty() && heads(revisions).is_empty() {
+        problems.push("the revisions form a cycle, no head found".to_string());
+    }
+    problems
+}
+
+/// Revisions from the heads down to the base, each one listed after all the
+/// revisions that build on it.
+pub fn history(revisions: &[Revision]) -> Vec<&Revision> {
+    let mut ordered: Vec<&Revision> = Vec::new();
+    let mut emitted: BTreeSet<&str> = BTreeSet::new();
+    loop {
+        let ready: Vec<&Revision> = revisions
+            .iter()
+            .rev()
+            .filter(|r| !emitted.contains(r.id.as_str()))
+            .filter(|r| children(revisions, &r.id).iter().all(|c| emitted.contains(c.id.as_str())))
+            .collect();
+        if ready.is_empty() {
+            break;
+        }
+        for revision in ready {
+            emitted.insert(&revision.id);
+            ordered.push(revision);
+        }
+    }
+    ordered
+}
+
+/// Alembic-style file name slug: "Add users table" -> "add_users_table".
+pub fn slug(message: &str) -> String {
+    let words: Vec<String> = message
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut slug = String::new();
+    for word in words {
+        if slug.len() + word.len() + 1 > 40 && !slug.is_empty() {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('_');
+        }
+        slug.push_str(&word);
+    }
+    slug
+}
+
+/// Current UTC time as `YYYY-MM-DD HH:MM:SS.ffffff`, the format of Alembic's Create Date.
+pub fn utc_now() -> String {
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+    let seconds = now.as_secs() as i64;
+    let (days, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60,
+        now.subsec_micros()
+    )
+}
+
+/// A new 12-character hex revision id, like Alembic's `uuid4().hex[-12:]`.
+pub fn revision_id(taken: &[Revision]) -> String {
+    use std::hash::{BuildHasher, Hasher};
+    let mut salt = 0u64;
+    loop {
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u128(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos());
+        hasher.write_u64(salt);
+        let id = format!("{:012x}", hasher.finish() & 0xffff_ffff_ffff);
+        if !taken.iter().any(|r| r.id == id) {
+            return id;
+        }
+        salt += 1;
+    }
+}
+
+fn python_repr(down: &[String]) -> String {
+    match down {
+        [] => "None".to_string(),
+        [single] => format!("'{}'", single),
+        several => format!("({})", several.iter().map(|d| format!("'{}'", d)).collect::<Vec<_>>().join(", ")),
+    }
+}
+
+/// Revision module as `script.py.mako` renders it with empty upgrade and downgrade.
+pub fn render_revision(id: &str, down: &[String], message: &str, date: &str) -> String {
+    format!(
+        r#""""{message}
+
+Revision ID: {id}
+Revises: {revises}
+Create Date: {date}
+
+"""
+from typing import Sequence, Union
+
+from alembic import op
+import sqlalchemy as sa
+
+
+# revision identifiers, used by Alembic.
+revision: str = '{id}'
+down_revision: Union[str, None] = {down}
+branch_labels: Union[str, Sequence[str], None] = None
+depends_on: Union[str, Sequence[str], None] = None
+
+
+def upgrade() -> None:
+    pass
+
+
+def downgrade() -> None:
+    pass
+"#,
+        message = message.replace("\"\"\"", "'''"),
+        id = id,
+        revises = down.join(", "),
+        date = date,
+        down = python_repr(down)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revision(id: &str, down: &[&str]) -> Revision {
+        let down: Vec<String> = down.iter().map(|d| d.to_string()).collect();
+        let source = render_revision(id, &down, &format!("Revision {}", id), "2026-01-01 00:00:00.000000");
+        parse_revision(&source, Path::new(&format!("{}.py", id))).unwrap()
+    }
+
+    fn ids(revisions: &[&Revision]) -> Vec<String> {
+        revisions.iter().map(|r| r.id.clone()).collect()
+    }
+
+    #[test]
+    fn rendered_revisions_parse_back() {
+        let merge = revision("c3", &["a1", "b2"]);
+        assert_eq!((merge.id.as_str(), merge.message.as_str()), ("c3", "Revision c3"));
+        assert_eq!(merge.down, ["a1", "b2"]);
+        assert!(revision("a1", &[]).down.is_empty());
+
+        let source = "\"\"\"Add users\"\"\"\nrevision = \"abc\"  # id\ndown_revision = None\nrevision_note = 'x'\n";
+        let parsed = parse_revision(source, Path::new("abc.py")).unwrap();
+        assert_eq!((parsed.id.as_str(), parsed.message.as_str()), ("abc", "Add users"));
+        assert!(parse_revision("x = 1\n", Path::new("x.py")).is_none());
+    }
+
+    #[test]
+    fn heads_history_and_branches() {
+        let revisions = [revision("a1", &[]), revision("b2", &["a1"]), revision("b3", &["a1"]), revision("c4", &["b2", "b3"]), revision("d5", &["c4"])];
+        assert_eq!(ids(&heads(&revisions)), ["d5"]);
+        assert_eq!(ids(&children(&revisions, "a1")), ["b2", "b3"]);
+        assert_eq!(ids(&history(&revisions)), ["d5", "c4", "b3", "b2", "a1"]);
+        assert!(problems(&revisions).is_empty());
+        assert_eq!(ids(&heads(&revisions[..3])), ["b2", "b3"]);
+    }
+
+    #[test]
+    fn broken_chains_are_reported() {
+        let revisions = [revision("a1", &["zz"]), revision("a1", &[])];
+        let problems = problems(&revisions);
+        assert!(problems.iter().any(|p| p.contains("'a1' (a1.py) revises 'zz', which does not exist")), "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("'a1' is defined twice")), "{:?}", problems);
+
+        let cycle = [revision("a1", &["b2"]), revision("b2", &["a1"])];
+        assert_eq!(super::problems(&cycle), ["the revisions form a cycle, no head found"]);
+    }
+
+    #[test]
+    fn names_ids_and_dates() {
+        assert_eq!(slug("Add users table!"), "add_users_table");
+        assert_eq!(slug("a very long message that keeps going past the Alembic limit"), "a_very_long_message_that_keeps_going");
+        let id = revision_id(&[]);
+        assert!(id.len() == 12 && id.chars().all(|c| c.is_ascii_hexdigit()), "{}", id);
+        let now = utc_now();
+        assert_eq!(now.len(), "2026-01-01 00:00:00.000000".len());
+        assert!(now.starts_with("20") && &now[4..5] == "-" && &now[10..11] == " ", "{}", now);
+        assert_eq!(python_repr(&["a".into(), "b".into()]), "('a', 'b')");
+    }
+
+    #[test]
+    fn versions_follow_script_location() {
+        assert_eq!(versions_dir(Path::new("/missing")), Path::new("/missing/migrations/versions"));
+    }
+}
@@ -1,3 +1,16 @@
+pub mod config;
+pub mod ddl;
 pub mod fs;
+pub mod imports;
+pub mod infer;
+pub mod migrations;
+pub mod openapi;
+pub mod python;
+pub mod recordings;
+pub mod routes;
+pub mod spec;
+pub mod spec_diff;
 pub mod string;
+pub mod tables;
 pub mod testing;
+pub mod typescript;
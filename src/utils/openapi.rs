@@ -0,0 +1,928 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use serde_json::{json, Map, Value};
+use crate::utils::fs::python_files;
+use crate::utils::python::{
+    call_arguments, class_attributes, docstring, find_classes, logical_lines, split_call, split_top_level,
+    string_literal, Param,
+};
+use crate::utils::routes::{collect_endpoints, Endpoint};
+
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+const MODEL_BASES: [&str; 2] = ["BaseModel", "SQLModel"];
+const ENUM_BASES: [&str; 4] = ["Enum", "IntEnum", "StrEnum", "Flag"];
+/// Parameter annotations FastAPI injects itself; they never reach the schema.
+const INJECTED_TYPES: [&str; 8] = [
+    "Request", "Response", "BackgroundTasks", "WebSocket", "Session", "AsyncSession", "HTTPConnection", "SecurityScopes",
+];
+const PARAM_LOCATIONS: [(&str, &str); 7] = [
+    ("Query", "query"),
+    ("Path", "path"),
+    ("Header", "header"),
+    ("Cookie", "cookie"),
+    ("Body", "body"),
+    ("Form", "form"),
+    ("File", "file"),
+];
+
+#[derive(Debug, Clone)]
+enum TypeDef {
+    Model {
+        bases: Vec<String>,
+        fields: Vec<Param>,
+        doc: Option<String>,
+    },
+    Enum {
+        values: Vec<Value>,
+    },
+}
+
+/// Pydantic models and enums found in the project, plus the ones referenced so far.
+struct Schemas {
+    types: BTreeMap<String, TypeDef>,
+    used: Vec<String>,
+    /// Schemas built on the fly, like the `Body_*` wrappers of multi-field bodies.
+    extra: BTreeMap<String, Value>,
+    warnings: Vec<String>,
+}
+
+pub struct Document {
+    pub value: Value,
+    pub warnings: Vec<String>,
+}
+
+fn last_segment(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name).trim()
+}
+
+/// "read_example" -> "Read Example", like FastAPI's default summaries and titles.
+pub fn title_case(name: &str) -> String {
+    name.split('_')
+        .filter(|w| !w.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Converts a Python literal (strings, numbers, booleans, None, empty containers)
+/// to JSON.
+pub fn literal_value(expr: &str) -> Option<Value> {
+    let expr = expr.trim();
+    if let Some(text) = string_literal(expr) {
+        return Some(Value::String(text));
+    }
+    match expr {
+        "True" => return Some(Value::Bool(true)),
+        "False" => return Some(Value::Bool(false)),
+        "None" => return Some(Value::Null),
+        "[]" | "list()" | "()" => return Some(json!([])),
+        "{}" | "dict()" => return Some(json!({})),
+        _ => {}
+    }
+    if let Ok(number) = expr.parse::<i64>() {
+        return Some(json!(number));
+    }
+    if let Ok(number) = expr.parse::<f64>() {
+        return Some(json!(number));
+    }
+    if (expr.starts_with('[') && expr.ends_with(']')) || (expr.starts_with('(') && expr.ends_with(')')) {
+        let items: Option<Vec<Value>> = split_top_level(&expr[1..expr.len() - 1], ',')
+            .iter()
+            .filter(|item| !item.trim().is_empty())
+            .map(|item| literal_value(item))
+            .collect();
+        return items.map(Value::Array);
+    }
+    None
+}
+
+/// JSON Schema type of a literal value.
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Splits `Outer[inner]` into `("Outer", "inner")`.
+fn generic(annotation: &str) -> Option<(&str, &str)> {
+    let open = annotation.find('[')?;
+    annotation
+        .ends_with(']')
+        .then(|| (annotation[..open].trim(), &annotation[open + 1..annotation.len() - 1]))
+}
+
+fn discover_types(root: &Path) -> BTreeMap<String, TypeDef> {
+    let mut classes = Vec::new();
+    for path in python_files(&root.join("app")) {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for class in find_classes(&source) {
+            classes.push((class, source.clone()));
+        }
+    }
+
+    let mut types = BTreeMap::new();
+    // Repeat until stable so subclasses of project models are found in any order.
+    loop {
+        let before = types.len();
+        for (class, source) in &classes {
+            if types.contains_key(&class.name) {
+                continue;
+            }
+            let bases: Vec<&str> = class.bases.iter().map(|b| last_segment(b)).collect();
+            let is_enum = bases.iter().any(|b| ENUM_BASES.contains(b));
+            let is_model = bases
+                .iter()
+                .any(|b| MODEL_BASES.contains(b) || matches!(types.get(*b), Some(TypeDef::Model { .. })));
+            let attributes = class_attributes(source, &class.name);
+            if is_enum {
+                let values = attributes
+                    .iter()
+                    .filter(|a| a.annotation.is_none() && !a.name.starts_with('_'))
+                    .map(|a| {
+                        a.default
+                            .as_deref()
+                            .and_then(literal_value)
+                            .unwrap_or_else(|| Value::String(a.name.to_lowercase()))
+                    })
+                    .collect();
+                types.insert(class.name.clone(), TypeDef::Enum { values });
+            } else if is_model {
+                let fields = attributes
+                    .into_iter()
+                    .filter(|a| a.annotation.as_deref().is_some_and(|t| !t.starts_with("ClassVar")))
+                    .filter(|a| !a.name.starts_with('_') && a.name != "model_config")
+                    .collect();
+                types.insert(
+                    class.name.clone(),
+                    TypeDef::Model {
+                        bases: bases.iter().map(|b| b.to_string()).collect(),
+                        fields,
+                        doc: docstring(source, class.line),
+                    },
+                );
+            }
+        }
+        if types.len() == before {
+            break;
+        }
+    }
+    types
+}
+
+impl Schemas {
+    fn reference(&mut self, name: &str) -> Value {
+        if !self.used.iter().any(|u| u == name) {
+            self.used.push(name.to_string());
+        }
+        json!({ "$ref": format!("#/components/schemas/{}", name) })
+    }
+
+    fn is_model(&self, annotation: &str) -> bool {
+        let annotation = annotation.trim();
+        match generic(annotation) {
+            Some(("Optional" | "Annotated", inner)) => split_top_level(inner, ',')
+                .first()
+                .is_some_and(|first| self.is_model(first)),
+            Some(("List" | "list" | "Sequence" | "Set" | "set", inner)) => self.is_model(inner),
+            Some(_) => false,
+            None => {
+                let base = annotation.split('|').next().unwrap_or(annotation).trim();
+                matches!(self.types.get(last_segment(base)), Some(TypeDef::Model { .. }))
+            }
+        }
+    }
+
+    /// Converts a Python type annotation to a JSON Schema, recording referenced models.
+    fn schema(&mut self, annotation: &str, context: &str) -> Value {
+        let annotation = annotation.trim().trim_matches(['"', '\'']);
+        let union = split_top_level(annotation, '|');
+        if union.len() > 1 {
+            let variants: Vec<Value> = union.iter().map(|v| self.schema(v, context)).collect();
+            return json!({ "anyOf": variants });
+        }
+
+        if let Some((outer, inner)) = generic(annotation) {
+            let args = split_top_level(inner, ',');
+            let first = args.first().map(String::as_str).unwrap_or("Any");
+            return match last_segment(outer) {
+                "Optional" => json!({ "anyOf": [self.schema(first, context), { "type": "null" }] }),
+                "Union" => {
+                    let variants: Vec<Value> = args.iter().map(|v| self.schema(v, context)).collect();
+                    json!({ "anyOf": variants })
+                }
+                "Annotated" => self.schema(first, context),
+                "List" | "list" | "Sequence" | "Iterable" => json!({ "type": "array", "items": self.schema(first, context) }),
+                "Set" | "set" | "FrozenSet" | "frozenset" => {
+                    json!({ "type": "array", "items": self.schema(first, context), "uniqueItems": true })
+                }
+                "Tuple" | "tuple" => json!({ "type": "array", "items": self.schema(first, context) }),
+                "Dict" | "dict" | "Mapping" => {
+                    let value = args.get(1).map(String::as_str).unwrap_or("Any");
+                    json!({ "type": "object", "additionalProperties": self.schema(value, context) })
+                }
+                "Literal" => {
+                    let values: Vec<Value> = args.iter().filter_map(|v| literal_value(v)).collect();
+                    let mut schema = json!({ "enum": values });
+                    // Like pydantic, values of a single JSON type give the enum its type.
+                    let mut kinds = values.iter().map(json_type);
+                    if let Some(kind) = kinds.next()
+                        && kinds.all(|other| other == kind)
+                    {
+                        schema["type"] = json!(kind);
+                    }
+                    schema
+                }
+                "conlist" => json!({ "type": "array", "items": self.schema(first, context) }),
+                other => {
+                    self.warnings.push(format!("{}: generic type '{}' is not supported, treated as any", context, other));
+                    json!({})
+                }
+            };
+        }
+
+        let name = last_segment(annotation);
+        let (kind, format) = match name {
+            "str" | "constr" => ("string", None),
+            "int" | "conint" => ("integer", None),
+            "float" | "confloat" | "Decimal" | "condecimal" => ("number", None),
+            "bool" => ("boolean", None),
+            "bytes" => ("string", Some("binary")),
+            "UploadFile" => ("string", Some("binary")),
+            "datetime" => ("string", Some("date-time")),
+            "date" => ("string", Some("date")),
+            "time" => ("string", Some("time")),
+            "timedelta" => ("string", Some("duration")),
+            "UUID" | "UUID4" => ("string", Some("uuid")),
+            "EmailStr" => ("string", Some("email")),
+            "HttpUrl" | "AnyUrl" | "AnyHttpUrl" => ("string", Some("uri")),
+            "list" | "List" | "tuple" | "set" => return json!({ "type": "array", "items": {} }),
+            "dict" | "Dict" | "Json" => return json!({ "type": "object" }),
+            "None" => return json!({ "type": "null" }),
+            "Any" | "object" | "" => return json!({}),
+            _ if self.types.contains_key(name) => return self.reference(name),
+            _ => {
+                self.warnings.push(format!("{}: unknown type '{}', treated as any", context, annotation));
+                return json!({});
+            }
+        };
+        match format {
+            Some(format) => json!({ "type": kind, "format": format }),
+            None => json!({ "type": kind }),
+        }
+    }
+
+    /// Schema of one model field or parameter; returns it with whether it is required.
+    fn field_schema(&mut self, field: &Param, context: &str) -> (Value, bool) {
+        let annotation = field.annotation.as_deref().unwrap_or("Any");
+        let mut schema = self.schema(annotation, context);
+        let mut required = field.default.is_none();
+        let mut details = Map::new();
+
+        // `Field(...)`, `Query(...)` and friends carry the default and constraints.
+        let default = match metadata_call(field) {
+            Some((args, is_default)) => {
+                let (positional, keywords) = call_arguments(&args);
+                let keywords: BTreeMap<String, String> = keywords.into_iter().collect();
+                for (keyword, key) in [
+                    ("description", "description"),
+                    ("title", "title"),
+                    ("ge", "minimum"),
+                    ("gt", "exclusiveMinimum"),
+                    ("le", "maximum"),
+                    ("lt", "exclusiveMaximum"),
+                    ("min_length", "minLength"),
+                    ("max_length", "maxLength"),
+                    ("pattern", "pattern"),
+                    ("regex", "pattern"),
+                    ("examples", "examples"),
+                    ("deprecated", "deprecated"),
+                ] {
+                    if let Some(value) = keywords.get(keyword).and_then(|v| literal_value(v)) {
+                        details.insert(key.to_string(), value);
+                    }
+                }
+                // Inside `Annotated[...]` the default is the one after `=`.
+                let default = match is_default {
+                    true => positional.first().or(keywords.get("default")).cloned(),
+                    false => field.default.clone().or_else(|| keywords.get("default").cloned()),
+                };
+                required = match default.as_deref() {
+                    Some("...") | Some("Ellipsis") => true,
+                    Some(_) => false,
+                    None => !keywords.contains_key("default_factory"),
+                };
+                default
+            }
+            None => field.default.clone(),
+        };
+        if let Some(value) = default.as_deref().filter(|_| !required).and_then(literal_value) {
+            details.insert("default".to_string(), value);
+        }
+
+        if let Value::Object(object) = &mut schema {
+            // Like pydantic, references to other models carry no title of their own.
+            if !details.contains_key("title") && !object.contains_key("$ref") {
+                object.insert("title".to_string(), Value::String(title_case(&field.name)));
+            }
+            object.extend(details);
+        }
+        (schema, required)
+    }
+
+    fn component(&mut self, name: &str) -> Value {
+        match self.types.get(name).cloned() {
+            Some(TypeDef::Enum { values }) => {
+                let kind = if values.iter().all(Value::is_string) { "string" } else { "integer" };
+                json!({ "type": kind, "enum": values, "title": name })
+            }
+            Some(TypeDef::Model { fields, doc, .. }) => {
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                for field in self.inherited_fields(name).into_iter().chain(fields) {
+                    let context = format!("{}.{}", name, field.name);
+                    let (schema, is_required) = self.field_schema(&field, &context);
+                    let key = alias(&field).unwrap_or_else(|| field.name.clone());
+                    if is_required && !required.contains(&key) {
+                        required.push(key.clone());
+                    }
+                    properties.insert(key, schema);
+                }
+                let mut schema = json!({ "properties": properties, "type": "object" });
+                if !required.is_empty() {
+                    schema["required"] = json!(required);
+                }
+                schema["title"] = json!(name);
+                if let Some(doc) = doc {
+                    schema["description"] = json!(doc);
+                }
+                schema
+            }
+            None => json!({}),
+        }
+    }
+
+    fn inherited_fields(&self, name: &str) -> Vec<Param> {
+        let Some(TypeDef::Model { bases, .. }) = self.types.get(name) else {
+            return Vec::new();
+        };
+        let mut fields = Vec::new();
+        for base in bases {
+            if let Some(TypeDef::Model { fields: own, .. }) = self.types.get(base) {
+                fields.extend(self.inherited_fields(base));
+                fields.extend(own.iter().cloned());
+            }
+        }
+        fields
+    }
+}
+
+/// Arguments of the `Field(...)` or `Query(...)`-like call configuring a field or
+/// parameter, written as its default or as `Annotated[...]` metadata; the flag
+/// tells whether it is the default.
+fn metadata_call(field: &Param) -> Option<(String, bool)> {
+    let is_marker = |callee: &str| {
+        let callee = last_segment(callee);
+        callee == "Field" || PARAM_LOCATIONS.iter().any(|(name, _)| *name == callee)
+    };
+    if let Some((callee, args)) = field.default.as_deref().and_then(split_call)
+        && is_marker(callee)
+    {
+        return Some((args.to_string(), true));
+    }
+    let (_, inner) = generic(field.annotation.as_deref()?.trim()).filter(|(outer, _)| last_segment(outer) == "Annotated")?;
+    split_top_level(inner, ',').iter().skip(1).find_map(|meta| {
+        split_call(meta)
+            .filter(|(callee, _)| is_marker(callee))
+            .map(|(_, args)| (args.to_string(), false))
+    })
+}
+
+fn alias(field: &Param) -> Option<String> {
+    let (args, _) = metadata_call(field)?;
+    let (_, keywords) = call_arguments(&args);
+    keywords
+        .into_iter()
+        .find(|(k, _)| k == "alias")
+        .and_then(|(_, v)| string_literal(&v))
+}
+
+/// Where FastAPI reads a parameter from, given its annotation and default.
+fn param_location(param: &Param, path_params: &[String], schemas: &Schemas) -> Option<&'static str> {
+    let annotation = param.annotation.as_deref().unwrap_or("");
+    let marker = param
+        .default
+        .as_deref()
+        .and_then(split_call)
+        .map(|(callee, _)| last_segment(callee).to_string())
+        .or_else(|| {
+            // `Annotated[int, Query(ge=1)]`
+            let (_, inner) = generic(annotation).filter(|(outer, _)| last_segment(outer) == "Annotated")?;
+            split_top_level(inner, ',').iter().skip(1).find_map(|meta| split_call(meta).map(|(c, _)| last_segment(c).to_string()))
+        });
+    match marker.as_deref() {
+        Some("Depends") | Some("Security") => return None,
+        Some(marker) => {
+            if let Some((_, location)) = PARAM_LOCATIONS.iter().find(|(name, _)| *name == marker) {
+                return Some(location);
+            }
+        }
+        None => {}
+    }
+    if INJECTED_TYPES.contains(&last_segment(annotation)) {
+        return None;
+    }
+    if path_params.contains(&param.name) {
+        Some("path")
+    } else if schemas.is_model(annotation) || matches!(last_segment(annotation), "dict" | "Dict") {
+        Some("body")
+    } else {
+        Some("query")
+    }
+}
+
+fn path_parameters(path: &str) -> Vec<String> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split('}').next())
+        .map(|name| name.split(':').next().unwrap_or(name).to_string())
+        .collect()
+}
+
+/// FastAPI's default operation id: handler name plus path, non-word characters
+/// replaced by `_`, then the method.
+pub fn operation_id(endpoint: &Endpoint) -> String {
+    let raw = format!("{}{}", endpoint.handler, endpoint.path);
+    let cleaned: String = raw.chars().map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' }).collect();
+    format!("{}_{}", cleaned, endpoint.method.to_lowercase())
+}
+
+fn status_code(expr: &str) -> Option<String> {
+    let expr = expr.trim();
+    if expr.chars().all(|c| c.is_ascii_digit()) {
+        return Some(expr.to_string());
+    }
+    // `status.HTTP_201_CREATED`
+    last_segment(expr)
+        .strip_prefix("HTTP_")
+        .and_then(|rest| rest.split('_').next())
+        .map(str::to_string)
+}
+
+/// Finds the security scheme used by the auth dependencies, FastAPI naming it after
+/// the class (`OAuth2PasswordBearer`, `HTTPBearer`, ...).
+fn security_scheme(root: &Path) -> Option<(String, Value)> {
+    for path in python_files(&root.join("app")) {
+        let source = fs::read_to_string(&path).unwrap_or_default();
+        for (_, line) in logical_lines(&source) {
+            let Some((_, call)) = line.split_once('=') else {
+                continue;
+            };
+            let Some((callee, args)) = split_call(call) else {
+                continue;
+            };
+            let (positional, keywords) = call_arguments(args);
+            let keywords: BTreeMap<String, String> = keywords.into_iter().collect();
+            let name = last_segment(callee);
+            let scheme = match name {
+                "OAuth2PasswordBearer" => {
+                    let url = keywords
+                        .get("tokenUrl")
+                        .or(positional.first())
+                        .and_then(|u| string_literal(u))
+                        .unwrap_or_else(|| "token".to_string());
+                    json!({ "type": "oauth2", "flows": { "password": { "scopes": {}, "tokenUrl": url } } })
+                }
+                "HTTPBearer" => json!({ "type": "http", "scheme": "bearer" }),
+                "HTTPBasic" => json!({ "type": "http", "scheme": "basic" }),
+                "APIKeyHeader" | "APIKeyQuery" | "APIKeyCookie" => {
+                    let location = match name {
+                        "APIKeyHeader" => "header",
+                        "APIKeyQuery" => "query",
+                        _ => "cookie",
+                    };
+                    let key = keywords.get("name").and_then(|n| string_literal(n)).unwrap_or_default();
+                    json!({ "type": "apiKey", "in": location, "name": key })
+                }
+                _ => continue,
+            };
+            return Some((name.to_string(), scheme));
+        }
+    }
+    None
+}
+
+/// Title, version and description given to `FastAPI(...)` in app/main.py.
+fn app_info(root: &Path) -> Value {
+    let source = fs::read_to_string(root.join("app").join("main.py")).unwrap_or_default();
+    let mut info = json!({ "title": "FastAPI", "version": "0.1.0" });
+    for (_, line) in logical_lines(&source) {
+        let Some((callee, args)) = line.split_once('=').and_then(|(_, call)| split_call(call)) else {
+            continue;
+        };
+        if last_segment(callee) != "FastAPI" {
+            continue;
+        }
+        for (key, value) in call_arguments(args).1 {
+            let Some(value) = string_literal(&value) else {
+                continue;
+            };
+            if matches!(key.as_str(), "title" | "version" | "description" | "summary") {
+                info[key] = json!(value);
+            }
+        }
+    }
+    info
+}
+
+fn validation_error_schemas() -> [(&'static str, Value); 2] {
+    [
+        (
+            "HTTPValidationError",
+            json!({
+                "properties": {
+                    "detail": { "items": { "$ref": "#/components/schemas/ValidationError" }, "type": "array", "title": "Detail" }
+                },
+                "type": "object",
+                "title": "HTTPValidationError"
+            }),
+        ),
+        (
+            "ValidationError",
+            json!({
+                "properties": {
+                    "loc": { "items": { "anyOf": [{ "type": "string" }, { "type": "integer" }] }, "type": "array", "title": "Location" },
+                    "msg": { "type": "string", "title": "Message" },
+                    "type": { "type": "string", "title": "Error Type" }
+                },
+                "type": "object",
+                "required": ["loc", "msg", "type"],
+                "title": "ValidationError"
+            }),
+        ),
+    ]
+}
+
+fn operation(endpoint: &Endpoint, schemas: &mut Schemas, security: Option<&str>) -> Value {
+    let context = format!("{}:{} {}", endpoint.file, endpoint.line, endpoint.handler);
+    let path_params = path_parameters(&endpoint.path);
+    let mut parameters = Vec::new();
+    let mut body: Vec<(Param, &'static str)> = Vec::new();
+
+    for param in &endpoint.params {
+        if param.name.starts_with('*') || param.name == "self" {
+            continue;
+        }
+        // `form_data: OAuth2PasswordRequestForm = Depends()` is a form body.
+        if param.annotation.as_deref().is_some_and(|a| last_segment(a) == "OAuth2PasswordRequestForm") {
+            for (name, required) in [("username", true), ("password", true), ("scope", false), ("client_id", false), ("client_secret", false)] {
+                let field = Param {
+                    name: name.to_string(),
+                    annotation: Some("str".to_string()),
+                    default: (!required).then(|| "\"\"".to_string()),
+                };
+                body.push((field, "form"));
+            }
+            continue;
+        }
+        let Some(location) = param_location(param, &path_params, schemas) else {
+            continue;
+        };
+        if matches!(location, "body" | "form" | "file") {
+            body.push((param.clone(), location));
+            continue;
+        }
+        let (schema, required) = schemas.field_schema(param, &format!("{} parameter '{}'", context, param.name));
+        let name = alias(param).unwrap_or_else(|| {
+            if location == "header" { param.name.replace('_', "-") } else { param.name.clone() }
+        });
+        parameters.push(json!({
+            "name": name,
+            "in": location,
+            "required": required || location == "path",
+            "schema": schema,
+        }));
+    }
+    for name in &path_params {
+        if !parameters.iter().any(|p| p["in"] == "path" && p["name"] == *name) {
+            schemas.warnings.push(format!("{}: path parameter '{}' is not a handler argument", context, name));
+            parameters.push(json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }));
+        }
+    }
+
+    let summary = endpoint
+        .options
+        .get("summary")
+        .and_then(|s| string_literal(s))
+        .unwrap_or_else(|| title_case(&endpoint.handler));
+    let mut operation = json!({ "summary": summary });
+    if !endpoint.tags.is_empty() {
+        operation["tags"] = json!(endpoint.tags);
+    }
+    let description = endpoint
+        .options
+        .get("description")
+        .and_then(|d| string_literal(d))
+        .or_else(|| endpoint.docstring.clone());
+    if let Some(description) = description {
+        operation["description"] = json!(description);
+    }
+    operation["operationId"] = json!(
+        endpoint
+            .options
+            .get("operation_id")
+            .and_then(|id| string_literal(id))
+            .unwrap_or_else(|| operation_id(endpoint))
+    );
+    if endpoint.options.get("deprecated").is_some_and(|d| d == "True") {
+        operation["deprecated"] = json!(true);
+    }
+    if !parameters.is_empty() {
+        operation["parameters"] = json!(parameters);
+    }
+
+    if !body.is_empty() {
+        let form = body.iter().any(|(_, location)| *location != "body");
+        let media = if body.iter().any(|(_, location)| *location == "file") {
+            "multipart/form-data"
+        } else if form {
+            "application/x-www-form-urlencoded"
+        } else {
+            "application/json"
+        };
+        let (schema, required) = if body.len() == 1 && !form {
+            let (param, _) = &body[0];
+            schemas.field_schema(param, &format!("{} body", context))
+        } else {
+            // Several body fields are embedded in one `Body_<operation id>` schema.
+            let name = format!("Body_{}", operation["operationId"].as_str().unwrap_or_default());
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for (param, _) in &body {
+                let (schema, is_required) = schemas.field_schema(param, &format!("{} body", context));
+                if is_required {
+                    required.push(param.name.clone());
+                }
+                properties.insert(param.name.clone(), schema);
+            }
+            let mut schema = json!({ "properties": properties, "type": "object" });
+            if !required.is_empty() {
+                schema["required"] = json!(required);
+            }
+            schema["title"] = json!(name);
+            schemas.extra.insert(name.clone(), schema);
+            (schemas.reference(&name), !required.is_empty())
+        };
+        let mut request_body = json!({ "content": { media: { "schema": schema } } });
+        if required {
+            request_body["required"] = json!(true);
+        }
+        operation["requestBody"] = request_body;
+    }
+
+    let status = endpoint
+        .status_code
+        .as_deref()
+        .and_then(status_code)
+        .unwrap_or_else(|| "200".to_string());
+    let model = match endpoint.response_model.as_deref() {
+        Some("None") => None,
+        Some(model) => Some(model.to_string()),
+        None => endpoint
+            .returns
+            .clone()
+            .filter(|r| !INJECTED_TYPES.contains(&last_segment(r)) && !last_segment(r).ends_with("Response") && r != "None"),
+    };
+    let mut response = json!({ "description": "Successful Response" });
+    if status != "204" {
+        let schema = match model {
+            Some(model) => schemas.schema(&model, &format!("{} response", context)),
+            None => json!({}),
+        };
+        response["content"] = json!({ "application/json": { "schema": schema } });
+    }
+    let mut responses = json!({ status: response });
+    if !parameters.is_empty() || !body.is_empty() {
+        responses["422"] = json!({
+            "description": "Validation Error",
+            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/HTTPValidationError" } } }
+        });
+        schemas.reference("HTTPValidationError");
+    }
+    operation["responses"] = responses;
+
+    if let Some(scheme) = security.filter(|_| !endpoint.auth.is_empty()) {
+        operation["security"] = json!([{ scheme: [] }]);
+    }
+    operation
+}
+
+/// Builds an OpenAPI document from the project sources, the way FastAPI would
+/// render it at `/openapi.json`, collecting warnings for what it cannot resolve.
+pub fn build_document(root: &Path) -> Document {
+    let mut schemas = Schemas {
+        types: discover_types(root),
+        used: Vec::new(),
+        warnings: Vec::new(),
+        extra: BTreeMap::new(),
+    };
+    let security = security_scheme(root);
+    let endpoints = collect_endpoints(root);
+
+    let mut paths = Map::new();
+    for endpoint in &endpoints {
+        if !endpoint.included {
+            schemas.warnings.push(format!(
+                "{}:{}: {} {} is skipped, its router is never included",
+                endpoint.file, endpoint.line, endpoint.method, endpoint.path
+            ));
+            continue;
+        }
+        if endpoint.options.get("include_in_schema").is_some_and(|v| v == "False") {
+            continue;
+        }
+        if endpoint.path.contains("{{") || endpoint.path.starts_with('{') {
+            schemas.warnings.push(format!(
+                "{}:{}: path '{}' is not a string literal",
+                endpoint.file, endpoint.line, endpoint.path
+            ));
+        }
+        if !endpoint.auth.is_empty() && security.is_none() {
+            schemas.warnings.push(format!(
+                "{}:{}: no security scheme found for {}",
+                endpoint.file,
+                endpoint.line,
+                endpoint.auth.join(", ")
+            ));
+        }
+        let operation = operation(endpoint, &mut schemas, security.as_ref().map(|(name, _)| name.as_str()));
+        let item = paths.entry(endpoint.path.clone()).or_insert_with(|| json!({}));
+        item[endpoint.method.to_lowercase()] = operation;
+    }
+
+    // Components reference each other, so resolve until no new name shows up.
+    let mut components = BTreeMap::new();
+    let mut index = 0;
+    while index < schemas.used.len() {
+        let name = schemas.used[index].clone();
+        index += 1;
+        let schema = match schemas.extra.get(&name) {
+            Some(schema) => schema.clone(),
+            None => match validation_error_schemas().into_iter().find(|(n, _)| *n == name) {
+                Some((_, schema)) => {
+                    schemas.reference("ValidationError");
+                    schema
+                }
+                None => schemas.component(&name),
+            },
+        };
+        components.insert(name, schema);
+    }
+
+    let mut document = json!({
+        "openapi": OPENAPI_VERSION,
+        "info": app_info(root),
+        "paths": paths,
+    });
+    let mut component_section = Map::new();
+    if !components.is_empty() {
+        component_section.insert("schemas".to_string(), json!(components));
+    }
+    if let Some((name, scheme)) = security.filter(|_| endpoints.iter().any(|e| e.included && !e.auth.is_empty())) {
+        component_section.insert("securitySchemes".to_string(), json!({ name: scheme }));
+    }
+    if !component_section.is_empty() {
+        document["components"] = Value::Object(component_section);
+    }
+
+    let mut warnings = schemas.warnings;
+    warnings.dedup();
+    Document { value: document, warnings }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schemas() -> Schemas {
+        Schemas { types: BTreeMap::new(), used: Vec::new(), extra: BTreeMap::new(), warnings: Vec::new() }
+    }
+
+    fn param(name: &str, annotation: &str, default: Option<&str>) -> Param {
+        Param { name: name.to_string(), annotation: Some(annotation.to_string()), default: default.map(str::to_string) }
+    }
+
+    fn parameters(params: Vec<Param>) -> Value {
+        let endpoint = Endpoint {
+            method: "GET".to_string(),
+            path: "/items/{item_id}".to_string(),
+            handler: "list_items".to_string(),
+            module: "app.routes.items".to_string(),
+            file: "app/routes/items.py".to_string(),
+            line: 1,
+            auth: Vec::new(),
+            tags: Vec::new(),
+            response_model: None,
+            status_code: None,
+            included: true,
+            params,
+            returns: None,
+            docstring: None,
+            options: BTreeMap::new(),
+        };
+        let mut schemas = schemas();
+        let operation = operation(&endpoint, &mut schemas, None);
+        assert!(schemas.warnings.is_empty(), "{:?}", schemas.warnings);
+        operation["parameters"].clone()
+    }
+
+    #[test]
+    fn literals_carry_their_type() {
+        let mut schemas = schemas();
+        assert_eq!(schemas.schema("Literal['asc', \"desc\"]", "t"), json!({"enum": ["asc", "desc"], "type": "string"}));
+        assert_eq!(schemas.schema("Literal[1, 2]", "t"), json!({"enum": [1, 2], "type": "integer"}));
+        assert_eq!(schemas.schema("typing.Literal[True]", "t"), json!({"enum": [true], "type": "boolean"}));
+        assert_eq!(schemas.schema("Literal[1, 'one']", "t"), json!({"enum": [1, "one"]}));
+    }
+
+    #[test]
+    fn query_options_written_as_the_default() {
+        let parameters = parameters(vec![
+            param("item_id", "int", None),
+            param("page", "int", Some("Query(1, alias=\"p\", ge=1)")),
+        ]);
+        assert_eq!(parameters[0]["name"], "item_id");
+        assert_eq!(parameters[0]["in"], "path");
+        assert_eq!(parameters[1]["name"], "p");
+        assert_eq!(parameters[1]["required"], false);
+        assert_eq!(parameters[1]["schema"]["minimum"], 1);
+        assert_eq!(parameters[1]["schema"]["default"], 1);
+    }
+
+    #[test]
+    fn query_options_written_in_annotated() {
+        let parameters = parameters(vec![
+            param("item_id", "Annotated[int, Path(gt=0)]", None),
+            param("page", "Annotated[int, Query(alias=\"p\", ge=1, le=50)]", Some("1")),
+            param("tag", "Annotated[str, Query(min_length=2)]", None),
+            param("x_token", "Annotated[str | None, Header(description=\"Token\")]", Some("None")),
+        ]);
+        assert_eq!(parameters[0]["schema"], json!({"type": "integer", "exclusiveMinimum": 0, "title": "Item Id"}));
+        assert_eq!(parameters[1]["name"], "p");
+        assert_eq!(parameters[1]["required"], false);
+        assert_eq!(parameters[1]["schema"], json!({"type": "integer", "minimum": 1, "maximum": 50, "default": 1, "title": "Page"}));
+        assert_eq!(parameters[2]["name"], "tag");
+        assert_eq!(parameters[2]["required"], true);
+        assert_eq!(parameters[2]["schema"]["minLength"], 2);
+        assert_eq!(parameters[3]["name"], "x-token");
+        assert_eq!(parameters[3]["in"], "header");
+        assert_eq!(parameters[3]["schema"]["description"], "Token");
+        assert_eq!(parameters[3]["required"], false);
+    }
+
+    #[test]
+    fn model_fields_use_aliases_from_either_form() {
+        let mut schemas = schemas();
+        schemas.types.insert(
+            "User".to_string(),
+            TypeDef::Model {
+                bases: vec!["BaseModel".to_string()],
+                fields: vec![
+                    param("user_name", "Annotated[str, Field(alias=\"userName\", min_length=1)]", None),
+                    param("birth_date", "date", Some("Field(None, alias=\"birthDate\")")),
+                ],
+                doc: None,
+            },
+        );
+        let component = schemas.component("User");
+        assert_eq!(component["required"], json!(["userName"]));
+        assert_eq!(component["properties"]["userName"]["minLength"], 1);
+        assert_eq!(component["properties"]["birthDate"]["format"], "date");
+    }
+
+    #[test]
+    fn python_literals() {
+        assert_eq!(literal_value("'a'"), Some(json!("a")));
+        assert_eq!(literal_value("-3"), Some(json!(-3)));
+        assert_eq!(literal_value("1.5"), Some(json!(1.5)));
+        assert_eq!(literal_value("[1, 'b', None]"), Some(json!([1, "b", null])));
+        assert_eq!(literal_value("datetime.now()"), None);
+        assert_eq!(path_parameters("/users/{user_id}/orders/{order_id}"), ["user_id", "order_id"]);
+        assert_eq!(title_case("read_example"), "Read Example");
+    }
+}
@@ -0,0 +1,704 @@
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub annotation: Option<String>,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodSpec {
+    pub name: String,
+    pub params: Vec<Param>,
+    pub returns: Option<String>,
+    pub is_async: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassDef {
+    pub name: String,
+    pub bases: Vec<String>,
+    pub line: usize,
+}
+
+impl Param {
+    fn parse(raw: &str) -> Option<Param> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return None;
+        }
+        let (head, default) = match split_top_level_once(raw, '=') {
+            Some((head, default)) => (head, Some(default.trim().to_string())),
+            None => (raw, None),
+        };
+        let (name, annotation) = match split_top_level_once(head, ':') {
+            Some((name, annotation)) => (name, Some(annotation.trim().to_string())),
+            None => (head, None),
+        };
+        Some(Param {
+            name: name.trim().to_string(),
+            annotation,
+            default,
+        })
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = self.name.clone();
+        if let Some(annotation) = &self.annotation {
+            out.push_str(": ");
+            out.push_str(annotation);
+        }
+        if let Some(default) = &self.default {
+            out.push_str(if self.annotation.is_some() { " = " } else { "=" });
+            out.push_str(default);
+        }
+        out
+    }
+}
+
+impl MethodSpec {
+    /// Parses the CLI shorthand `get_user(id:int)->User`.
+    pub fn parse(spec: &str) -> Result<MethodSpec, String> {
+        let spec = spec.trim();
+        let (is_async, spec) = match spec.strip_prefix("async ") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, spec),
+        };
+        let open = spec
+            .find('(')
+            .ok_or_else(|| format!("Invalid method '{}', expected name(args)->Type", spec))?;
+        let close = matching_paren(spec, open)
+            .ok_or_else(|| format!("Unbalanced parentheses in method '{}'", spec))?;
+
+        let name = spec[..open].trim();
+        if !is_identifier(name) {
+            return Err(format!("Invalid method name '{}'", name));
+        }
+
+        let params = split_top_level(&spec[open + 1..close], ',')
+            .iter()
+            .filter_map(|p| Param::parse(p))
+            .filter(|p| p.name != "self")
+            .collect();
+
+        let rest = spec[close + 1..].trim();
+        let returns = match rest.strip_prefix("->") {
+            Some(ty) if !ty.trim().is_empty() => Some(ty.trim().to_string()),
+            Some(_) => return Err(format!("Missing return type in method '{}'", spec)),
+            None if rest.is_empty() => None,
+            None => return Err(format!("Unexpected '{}' in method '{}'", rest, spec)),
+        };
+
+        Ok(MethodSpec {
+            name: name.to_string(),
+            params,
+            returns,
+            is_async,
+        })
+    }
+
+    pub fn signature(&self) -> String {
+        let mut params = vec!["self".to_string()];
+        params.extend(self.params.iter().map(Param::render));
+        let returns = self
+            .returns
+            .as_ref()
+            .map(|r| format!(" -> {}", r))
+            .unwrap_or_default();
+        format!(
+            "{}def {}({}){}:",
+            if self.is_async { "async " } else { "" },
+            self.name,
+            params.join(", "),
+            returns
+        )
+    }
+
+    pub fn annotations(&self) -> Vec<&str> {
+        self.params
+            .iter()
+            .filter_map(|p| p.annotation.as_deref())
+            .chain(self.returns.as_deref())
+            .collect()
+    }
+}
+
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn matching_paren(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices().skip_while(|(i, _)| *i < open) {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits on `sep` while ignoring separators nested in brackets or string literals.
+pub fn split_top_level(text: &str, sep: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut current = String::new();
+
+    for c in text.chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ if c == sep && depth == 0 => {
+                    parts.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        current.push(c);
+    }
+
+    if !current.trim().is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+fn split_top_level_once(text: &str, sep: char) -> Option<(&str, &str)> {
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let bytes: Vec<(usize, char)> = text.char_indices().collect();
+
+    for (idx, &(i, c)) in bytes.iter().enumerate() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ if c == sep && depth == 0 => {
+                    // `==`, `<=`, `>=`, `!=` and `->` are not separators.
+                    let next = bytes.get(idx + 1).map(|(_, c)| *c);
+                    let prev = idx.checked_sub(1).map(|p| bytes[p].1);
+                    if sep == '=' && (next == Some('=') || matches!(prev, Some('=' | '<' | '>' | '!'))) {
+                        continue;
+                    }
+                    return Some((&text[..i], &text[i + c.len_utf8()..]));
+                }
+                _ => {}
+            },
+        }
+    }
+    None
+}
+
+/// Blanks comments and string contents, also reporting for every line whether it
+/// starts inside a string literal (a continued triple-quoted string).
+fn mask(source: &str) -> (String, Vec<bool>) {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len());
+    let mut continued = vec![false];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            continued.push(false);
+            out.push(c);
+            i += 1;
+            continue;
+        }
+        if c == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                out.push(' ');
+                i += 1;
+            }
+            continue;
+        }
+        if c == '"' || c == '\'' {
+            let triple = i + 2 < chars.len() && chars[i + 1] == c && chars[i + 2] == c;
+            let delim_len = if triple { 3 } else { 1 };
+            for _ in 0..delim_len {
+                out.push(c);
+            }
+            i += delim_len;
+            while i < chars.len() {
+                if chars[i] == '\\' {
+                    out.push(' ');
+                    if i + 1 < chars.len() {
+                        if chars[i + 1] == '\n' {
+                            out.push('\n');
+                            continued.push(true);
+                        } else {
+                            out.push(' ');
+                        }
+                    }
+                    i += 2;
+                    continue;
+                }
+                let closes = if triple {
+                    i + 2 < chars.len() && chars[i] == c && chars[i + 1] == c && chars[i + 2] == c
+                } else {
+                    chars[i] == c || chars[i] == '\n'
+                };
+                if closes {
+                    let len = if triple { 3 } else if chars[i] == '\n' { 0 } else { 1 };
+                    for _ in 0..len {
+                        out.push(c);
+                    }
+                    i += len;
+                    break;
+                }
+                if chars[i] == '\n' {
+                    out.push('\n');
+                    continued.push(true);
+                } else {
+                    out.push(' ');
+                }
+                i += 1;
+            }
+            continue;
+        }
+        out.push(c);
+        i += 1;
+    }
+    (out, continued)
+}
+
+/// Joins statements spanning several physical lines (open brackets, continued
+/// strings, backslashes) into one logical line with comments removed, returning each
+/// with the 1-based number of the line it starts on.
+pub fn logical_lines(source: &str) -> Vec<(usize, String)> {
+    let (stripped, continued) = mask(source);
+    let original: Vec<&str> = source.lines().collect();
+    let stripped: Vec<&str> = stripped.lines().collect();
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+    let mut depth = 0i32;
+
+    for (number, line) in stripped.iter().enumerate() {
+        if current.is_empty() {
+            start = number + 1;
+        }
+        for c in line.chars() {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth = (depth - 1).max(0),
+                _ => {}
+            }
+        }
+        // Keep string contents from the original source so literals stay readable;
+        // a line ending inside a string has no comment to cut.
+        let next_continues = continued.get(number + 1).copied().unwrap_or(false);
+        let text = original.get(number).copied().unwrap_or(line);
+        let length = if next_continues { text.chars().count() } else { code_length(line) };
+        let code: String = text.chars().take(length).collect();
+        if !current.is_empty() {
+            current.push(' ');
+            current.push_str(code.trim_start());
+        } else {
+            current.push_str(&code);
+        }
+
+        if current.ends_with('\\') {
+            current.pop();
+            current.truncate(current.trim_end().len());
+        } else if depth == 0 && !next_continues {
+            result.push((start, std::mem::take(&mut current)));
+        }
+    }
+    if !current.is_empty() {
+        result.push((start, current));
+    }
+    result
+}
+
+fn code_length(stripped_line: &str) -> usize {
+    // Comments were blanked to spaces, so trailing whitespace covers them.
+    stripped_line.trim_end().chars().count()
+}
+
+pub fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+pub fn find_classes(source: &str) -> Vec<ClassDef> {
+    logical_lines(source)
+        .into_iter()
+        .filter(|(_, line)| indentation(line) == 0)
+        .filter_map(|(number, line)| {
+            let rest = line.trim().strip_prefix("class ")?;
+            let end = rest.find(['(', ':']).unwrap_or(rest.len());
+            let name = rest[..end].trim().to_string();
+            let bases = match rest[end..].strip_prefix('(') {
+                Some(after) => {
+                    let close = after.rfind(')').unwrap_or(after.len());
+                    split_top_level(&after[..close], ',')
+                        .into_iter()
+                        .map(|b| b.trim().to_string())
+                        .filter(|b| !b.is_empty() && !b.contains('='))
+                        .collect()
+                }
+                None => Vec::new(),
+            };
+            Some(ClassDef { name, bases, line: number })
+        })
+        .collect()
+}
+
+/// Parses a `def` statement (possibly `async`) collapsed onto one logical line.
+pub fn parse_def(line: &str) -> Option<MethodSpec> {
+    let line = line.trim();
+    let (is_async, rest) = match line.strip_prefix("async ") {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, line),
+    };
+    let rest = rest.strip_prefix("def ")?;
+    // Cut at the colon ending the header, which may be followed by an inline body.
+    let close = matching_paren(rest, rest.find('(')?)?;
+    let header_end = split_top_level_once(&rest[close + 1..], ':').map_or(rest.len(), |(returns, _)| close + 1 + returns.len());
+    let body = rest[..header_end].trim_end();
+    let mut spec = MethodSpec::parse(body).ok()?;
+    spec.is_async = is_async;
+    Some(spec)
+}
+
+/// Returns the methods of `class_name` decorated with `@abstractmethod`.
+pub fn parse_abstract_methods(source: &str, class_name: &str) -> Vec<MethodSpec> {
+    class_methods(source, class_name)
+        .into_iter()
+        .filter(|(decorators, _)| decorators.iter().any(|d| d.ends_with("abstractmethod")))
+        .map(|(_, method)| method)
+        .collect()
+}
+
+/// Returns every method defined directly in `class_name` with its decorators.
+pub fn class_methods(source: &str, class_name: &str) -> Vec<(Vec<String>, MethodSpec)> {
+    let lines = logical_lines(source);
+    let mut methods = Vec::new();
+    let mut inside = false;
+    let mut body_indent = None;
+    let mut decorators = Vec::new();
+
+    for (_, line) in &lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indent = indentation(line);
+        if indent == 0 {
+            inside = find_classes(line).first().is_some_and(|c| c.name == class_name);
+            body_indent = None;
+            decorators.clear();
+            continue;
+        }
+        if !inside {
+            continue;
+        }
+        let level = *body_indent.get_or_insert(indent);
+        if indent != level {
+            continue;
+        }
+        let trimmed = line.trim();
+        if let Some(decorator) = trimmed.strip_prefix('@') {
+            decorators.push(decorator.split('(').next().unwrap_or(decorator).trim().to_string());
+        } else if let Some(method) = parse_def(trimmed) {
+            methods.push((std::mem::take(&mut decorators), method));
+        } else {
+            decorators.clear();
+        }
+    }
+    methods
+}
+
+/// Finds the port class in a module: the first class deriving from `ABC`, falling
+/// back to the first class defined.
+pub fn find_port_class(path: &Path) -> Option<String> {
+    let source = fs::read_to_string(path).ok()?;
+    let classes = find_classes(&source);
+    classes
+        .iter()
+        .find(|c| c.bases.iter().any(|b| b == "ABC" || b.ends_with(".ABC")))
+        .or(classes.first())
+        .map(|c| c.name.clone())
+}
+
+const BUILTIN_TYPES: &[&str] = &[
+    "Any", "Dict", "List", "Optional", "Set", "Tuple", "Union", "Iterable", "Sequence",
+    "Mapping", "Callable", "None", "Literal", "Annotated", "UUID", "Decimal",
+];
+
+const TYPING_NAMES: &[&str] = &[
+    "Any", "Dict", "List", "Optional", "Set", "Tuple", "Union", "Iterable", "Sequence",
+    "Mapping", "Callable", "Literal", "Annotated",
+];
+
+/// Returns the `typing` helpers used by the annotations, in a stable order.
+pub fn typing_names(annotations: &[&str]) -> Vec<&'static str> {
+    TYPING_NAMES
+        .iter()
+        .copied()
+        .filter(|name| {
+            annotations.iter().any(|a| {
+                a.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .any(|word| word == *name)
+            })
+        })
+        .collect()
+}
+
+/// Extracts capitalized names from type annotations, skipping `typing` helpers.
+pub fn referenced_types(annotations: &[&str]) -> Vec<String> {
+    let mut names = Vec::new();
+    for annotation in annotations {
+        for word in annotation.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.')) {
+            let name = word.rsplit('.').next().unwrap_or(word);
+            let capitalized = name.chars().next().is_some_and(|c| c.is_uppercase());
+            if capitalized && !BUILTIN_TYPES.contains(&name) && !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Returns the value of a plain, raw or f-string literal expression.
+pub fn string_literal(expr: &str) -> Option<String> {
+    let expr = expr.trim();
+    let body = expr.trim_start_matches(['r', 'R', 'f', 'F', 'b', 'B', 'u', 'U']);
+    for quote in ["\"\"\"", "'''", "\"", "'"] {
+        if body.len() >= 2 * quote.len() && body.starts_with(quote) && body.ends_with(quote) {
+            return Some(body[quote.len()..body.len() - quote.len()].to_string());
+        }
+    }
+    None
+}
+
+/// Splits the arguments of a call into positional expressions and keyword arguments.
+pub fn call_arguments(args: &str) -> (Vec<String>, Vec<(String, String)>) {
+    let mut positional = Vec::new();
+    let mut keywords = Vec::new();
+    for arg in split_top_level(args, ',') {
+        let arg = arg.trim();
+        if arg.is_empty() {
+            continue;
+        }
+        match split_top_level_once(arg, '=') {
+            Some((name, value)) if is_identifier(name.trim()) => {
+                keywords.push((name.trim().to_string(), value.trim().to_string()))
+            }
+            _ => positional.push(arg.to_string()),
+        }
+    }
+    (positional, keywords)
+}
+
+/// Returns `(callee, arguments)` for an expression like `callee(arguments)`.
+pub fn split_call(expr: &str) -> Option<(&str, &str)> {
+    let expr = expr.trim();
+    let open = expr.find('(')?;
+    let close = matching_paren(expr, open)?;
+    Some((expr[..open].trim(), &expr[open + 1..close]))
+}
+
+/// Router passed to `app.include_router(...)` in a logical line, positionally or as `router=`.
+pub fn included_router(statement: &str) -> Option<String> {
+    let (callee, args) = split_call(statement)?;
+    if callee != "app.include_router" {
+        return None;
+    }
+    let (positional, keywords) = call_arguments(args);
+    positional
+        .into_iter()
+        .next()
+        .or_else(|| keywords.into_iter().find(|(name, _)| name == "router").map(|(_, value)| value))
+}
+
+/// Names passed to `Depends(...)` or `Security(...)` anywhere in `text`.
+pub fn dependency_names(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for marker in ["Depends(", "Security("] {
+        let mut rest = text;
+        while let Some(index) = rest.find(marker) {
+            let preceded = rest[..index].chars().last().is_some_and(|c| c.is_alphanumeric() || c == '_');
+            rest = &rest[index + marker.len()..];
+            if preceded {
+                continue;
+            }
+            let name: String = rest
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                .collect();
+            if !name.is_empty() {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Returns the attributes assigned directly in the body of `class_name`, like
+/// `name: str = "x"` or enum members `RED = "red"`.
+pub fn class_attributes(source: &str, class_name: &str) -> Vec<Param> {
+    let mut attributes = Vec::new();
+    let mut inside = false;
+    let mut body_indent = None;
+
+    for (_, line) in logical_lines(source) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indent = indentation(&line);
+        if indent == 0 {
+            inside = find_classes(&line).first().is_some_and(|c| c.name == class_name);
+            body_indent = None;
+            continue;
+        }
+        if !inside || indent != *body_indent.get_or_insert(indent) {
+            continue;
+        }
+        let Some(attribute) = Param::parse(&line) else {
+            continue;
+        };
+        let assigned = attribute.annotation.is_some() || attribute.default.is_some();
+        if assigned && is_identifier(&attribute.name) {
+            attributes.push(attribute);
+        }
+    }
+    attributes
+}
+
+/// Returns the cleaned docstring of the `def` or `class` statement starting on
+/// `line` (1-based), keeping its line breaks.
+pub fn docstring(source: &str, line: usize) -> Option<String> {
+    let lines = logical_lines(source);
+    let index = lines.iter().position(|(number, _)| *number > line)?;
+    let start = lines[index].0;
+    let end = lines.get(index + 1).map_or(usize::MAX, |(number, _)| *number);
+    let raw: Vec<&str> = source.lines().skip(start - 1).take(end.saturating_sub(start)).collect();
+    let text = string_literal(raw.join("\n").trim())?;
+
+    let mut doc_lines = text.lines();
+    let first = doc_lines.next().unwrap_or("").trim().to_string();
+    let rest: Vec<&str> = doc_lines.collect();
+    let margin = rest
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| indentation(l))
+        .min()
+        .unwrap_or(0);
+    let mut cleaned = vec![first];
+    cleaned.extend(rest.iter().map(|l| l.get(margin..).unwrap_or("").trim_end().to_string()));
+    let cleaned = cleaned.join("\n").trim().to_string();
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logical_lines_join_brackets_backslashes_and_strings() {
+        let source = "x = call(\n    a,  # first\n    b)\ny = 1 + \\\n    2\nz = \"\"\"one\ntwo\"\"\"\n# only a comment\n";
+        let lines = logical_lines(source);
+        assert_eq!(lines[0], (1, "x = call( a, b)".to_string()));
+        assert_eq!(lines[1], (4, "y = 1 + 2".to_string()));
+        assert_eq!(lines[2], (6, "z = \"\"\"one two\"\"\"".to_string()));
+        assert_eq!(lines[3], (8, String::new()));
+    }
+
+    #[test]
+    fn brackets_and_hashes_inside_strings_are_ignored() {
+        let lines = logical_lines("a = \"(#\"  # ( comment\nb = ')'\n");
+        assert_eq!(lines, [(1, "a = \"(#\"".to_string()), (2, "b = ')'".to_string())]);
+    }
+
+    #[test]
+    fn split_top_level_respects_nesting_and_quotes() {
+        assert_eq!(split_top_level("a: Dict[str, int], b='x,y', c=(1, 2)", ','), ["a: Dict[str, int]", " b='x,y'", " c=(1, 2)"]);
+        assert_eq!(split_top_level_once("x: int = a == b", '='), Some(("x: int ", " a == b")));
+        assert_eq!(split_top_level_once("f(a=1)", '='), None);
+    }
+
+    #[test]
+    fn classes_and_bases() {
+        let source = "class Port(ABC):\n    pass\n\nclass Impl(\n    Port,\n    metaclass=Meta,\n):\n    def run(self): ...\n\nclass Plain:\n    pass\n";
+        let classes = find_classes(source);
+        assert_eq!(classes.iter().map(|c| (c.name.as_str(), c.line)).collect::<Vec<_>>(), [("Port", 1), ("Impl", 4), ("Plain", 10)]);
+        assert_eq!(classes[1].bases, ["Port"]);
+        assert!(classes[2].bases.is_empty());
+    }
+
+    #[test]
+    fn class_methods_keep_decorators_and_skip_nested_code() {
+        let source = "class Repo(ABC):\n    @abstractmethod\n    async def get(\n        self, id: int,\n    ) -> Optional[User]:\n        pass\n\n    @property\n    def name(self) -> str:\n        def inner(x): ...\n        return 'n'\n\nclass Other:\n    def get(self): ...\n";
+        let methods = class_methods(source, "Repo");
+        assert_eq!(methods.len(), 2);
+        assert_eq!(methods[0].0, ["abstractmethod"]);
+        assert!(methods[0].1.is_async);
+        assert_eq!(methods[0].1.signature(), "async def get(self, id: int) -> Optional[User]:");
+        assert_eq!(methods[1].0, ["property"]);
+        assert_eq!(parse_abstract_methods(source, "Repo").iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), ["get"]);
+    }
+
+    #[test]
+    fn method_shorthand() {
+        let method = MethodSpec::parse("async find(name:str, limit:int = 10)->List[User]").unwrap();
+        assert_eq!(method.signature(), "async def find(self, name: str, limit: int = 10) -> List[User]:");
+        assert_eq!(method.annotations(), ["str", "int", "List[User]"]);
+        assert!(MethodSpec::parse("find(name").is_err());
+        assert!(MethodSpec::parse("1find()").is_err());
+        assert!(MethodSpec::parse("find()->").is_err());
+    }
+
+    #[test]
+    fn calls_and_literals() {
+        assert_eq!(split_call(" Query(None, alias=\"q\") "), Some(("Query", "None, alias=\"q\"")));
+        let (positional, keywords) = call_arguments("1, x == 2, alias='a', default=f(b=1)");
+        assert_eq!(positional, ["1", "x == 2"]);
+        assert_eq!(keywords, [("alias".to_string(), "'a'".to_string()), ("default".to_string(), "f(b=1)".to_string())]);
+        assert_eq!(string_literal("r'^a$'"), Some("^a$".to_string()));
+        assert_eq!(string_literal("\"\"\"doc\"\"\""), Some("doc".to_string()));
+        assert_eq!(string_literal("name"), None);
+        assert_eq!(included_router("app.include_router(users_router, prefix=\"/u\")"), Some("users_router".to_string()));
+        assert_eq!(included_router("app.include_router(prefix=\"/u\", router=users_router)"), Some("users_router".to_string()));
+        assert_eq!(included_router("api.include_router(users_router)"), None);
+        assert_eq!(dependency_names("user=Depends(get_user), s=Security(auth.scheme), x=MyDepends(y)"), ["get_user", "auth.scheme"]);
+    }
+
+    #[test]
+    fn types_in_annotations() {
+        assert_eq!(typing_names(&["Optional[List[User]]", "Dict[str, Any]"]), ["Any", "Dict", "List", "Optional"]);
+        assert_eq!(referenced_types(&["Optional[models.User]", "List[Order]", "UUID"]), ["User", "Order"]);
+    }
+
+    #[test]
+    fn attributes_and_docstrings() {
+        let source = "class Color(Enum):\n    \"\"\"Paint colors.\n\n        Used by the shop.\n    \"\"\"\n    RED = \"red\"\n    shade: int = 1\n    def f(self):\n        inner = 2\n";
+        let attributes = class_attributes(source, "Color");
+        assert_eq!(attributes.iter().map(|a| a.render()).collect::<Vec<_>>(), ["RED=\"red\"", "shade: int = 1"]);
+        assert_eq!(docstring(source, 1), Some("Paint colors.\n\nUsed by the shop.".to_string()));
+    }
+}
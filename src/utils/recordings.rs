@@ -0,0 +1,744 @@
+use std::collections::{BTreeMap, BTreeSet};
+use serde_json::{json, Map, Value};
+use crate::utils::infer::infer_schemas;
+use crate::utils::spec::Spec;
+use crate::utils::string::{to_camel_case, to_field_name, to_python_identifier, to_singular, to_snake_case};
+
+/// A request recorded in a Postman collection or a HAR capture.
+pub struct Recording {
+    /// Postman request name, used as the operation summary.
+    pub name: Option<String>,
+    /// Top-level Postman folder, used as the tag.
+    pub folder: Option<String>,
+    pub method: String,
+    /// Path segments with the variable name of the ones declared as
+    /// variables (`:id`, `{{userId}}`).
+    pub segments: Vec<(String, Option<String>)>,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Value>,
+    pub status: Option<u16>,
+    pub response: Option<Value>,
+}
+
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], text.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Splits a recorded URL into its path and query pairs, dropping the scheme
+/// and host or a leading `{{baseUrl}}`-style variable.
+fn split_url(url: &str) -> (String, Vec<(String, String)>) {
+    let url = url.split('#').next().unwrap_or("");
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |i| &rest[i..]),
+        None if url.starts_with("{{") => url.split_once("}}").map_or("", |(_, rest)| rest),
+        None => url,
+    };
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(&key.replace('+', " ")), decode(&value.replace('+', " ")))
+        })
+        .collect();
+    (path.to_string(), query)
+}
+
+/// Replaces the `{{name}}` placeholders of known variables.
+fn substitute(text: &str, variables: &BTreeMap<String, String>) -> String {
+    variables
+        .iter()
+        .fold(text.to_string(), |text, (key, value)| text.replace(&format!("{{{{{}}}}}", key), value))
+}
+
+fn segments(path: &str, values: &BTreeMap<String, String>) -> Vec<(String, Option<String>)> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let variable = segment
+                .strip_prefix(':')
+                .or_else(|| segment.strip_prefix("{{").and_then(|s| s.strip_suffix("}}")));
+            match variable {
+                Some(name) => {
+                    let value = values.get(name).filter(|v| !v.is_empty()).cloned().unwrap_or_else(|| "1".to_string());
+                    (value, Some(name.to_string()))
+                }
+                None => (decode(segment), None),
+            }
+        })
+        .collect()
+}
+
+fn pairs(value: Option<&Value>, key: &str, variables: &BTreeMap<String, String>) -> Vec<(String, String)> {
+    value
+        .and_then(Value::as_array)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter(|entry| entry.get("disabled").and_then(Value::as_bool) != Some(true))
+                .filter_map(|entry| {
+                    let name = entry.get(key)?.as_str()?;
+                    let value = entry.get("value").and_then(Value::as_str).unwrap_or("");
+                    Some((name.to_string(), substitute(value, variables)))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parses a JSON body in which unresolved `{{variables}}` outside strings are
+/// replaced by null.
+fn json_body(text: &str, variables: &BTreeMap<String, String>) -> Option<Value> {
+    let text = substitute(text, variables);
+    if text.trim().is_empty() {
+        return None;
+    }
+    if let Ok(value) = serde_json::from_str(&text) {
+        return Some(value);
+    }
+    let mut cleaned = String::new();
+    let mut rest = text.as_str();
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..].find("}}").map_or(rest.len(), |e| start + e + 2);
+        cleaned.push_str(&rest[..start]);
+        if rest[..start].ends_with('"') {
+            cleaned.push_str(&rest[start..end]);
+        } else {
+            cleaned.push_str("null");
+        }
+        rest = &rest[end..];
+    }
+    cleaned.push_str(rest);
+    serde_json::from_str(&cleaned).ok()
+}
+
+struct PostmanRequest {
+    method: String,
+    segments: Vec<(String, Option<String>)>,
+    query: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    body: Option<Value>,
+}
+
+fn postman_request(request: &Value, name: &str, variables: &BTreeMap<String, String>, warnings: &mut Vec<String>) -> PostmanRequest {
+    // A request can be a bare URL string.
+    let url = request.get("url").unwrap_or(request);
+    let raw = url.as_str().or_else(|| url.get("raw").and_then(Value::as_str)).unwrap_or("");
+    let (raw_path, raw_query) = split_url(raw);
+    let mut values = variables.clone();
+    values.extend(pairs(url.get("variable"), "key", variables));
+    let path = match url.get("path").and_then(Value::as_array) {
+        Some(parts) => parts
+            .iter()
+            .filter_map(|part| part.as_str().or_else(|| part.get("value").and_then(Value::as_str)))
+            .collect::<Vec<_>>()
+            .join("/"),
+        None => raw_path,
+    };
+    let query = if url.get("query").is_some() {
+        pairs(url.get("query"), "key", variables)
+    } else {
+        raw_query.into_iter().map(|(k, v)| (k, substitute(&v, variables))).collect()
+    };
+
+    let body = request.get("body").and_then(|body| {
+        match body.get("mode").and_then(Value::as_str).unwrap_or("raw") {
+            "raw" => {
+                let text = body.get("raw").and_then(Value::as_str).unwrap_or("");
+                let parsed = json_body(text, variables);
+                if parsed.is_none() && !text.trim().is_empty() {
+                    warnings.push(format!("'{}': body is not JSON, not imported", name));
+                }
+                parsed
+            }
+            mode => {
+                warnings.push(format!("'{}': {} body is not imported", name, mode));
+                None
+            }
+        }
+    });
+    PostmanRequest {
+        method: request.get("method").and_then(Value::as_str).unwrap_or("GET").to_lowercase(),
+        segments: segments(&path, &values),
+        query,
+        headers: pairs(request.get("header"), "key", variables),
+        body,
+    }
+}
+
+fn walk_postman(
+    items: &[Value],
+    folder: Option<&str>,
+    variables: &BTreeMap<String, String>,
+    recordings: &mut Vec<Recording>,
+    warnings: &mut Vec<String>,
+) {
+    for item in items {
+        let name = item.get("name").and_then(Value::as_str).unwrap_or("request");
+        if let Some(children) = item.get("item").and_then(Value::as_array) {
+            walk_postman(children, folder.or(Some(name)), variables, recordings, warnings);
+            continue;
+        }
+        let Some(request) = item.get("request") else {
+            continue;
+        };
+        let examples = item.get("response").and_then(Value::as_array).filter(|e| !e.is_empty());
+        let responses: Vec<(&Value, Option<u16>, Option<Value>)> = match examples {
+            Some(examples) => examples
+                .iter()
+                .map(|example| {
+                    let status = example.get("code").and_then(Value::as_u64).map(|code| code as u16);
+                    let body = example.get("body").and_then(Value::as_str).and_then(|b| json_body(b, variables));
+                    (example.get("originalRequest").unwrap_or(request), status, body)
+                })
+                .collect(),
+            None => vec![(request, None, None)],
+        };
+        for (request, status, response) in responses {
+            let request = postman_request(request, name, variables, warnings);
+            recordings.push(Recording {
+                name: Some(name.to_string()),
+                folder: folder.map(str::to_string),
+                method: request.method,
+                segments: request.segments,
+                query: request.query,
+                headers: request.headers,
+                body: request.body,
+                status,
+                response,
+            });
+        }
+    }
+}
+
+/// Reads the requests of a Postman v2 collection, with the saved examples as responses.
+pub fn read_postman(collection: &Value, warnings: &mut Vec<String>) -> Vec<Recording> {
+    let variables: BTreeMap<String, String> = pairs(collection.get("variable"), "key", &BTreeMap::new()).into_iter().collect();
+    let mut recordings = Vec::new();
+    if let Some(items) = collection.get("item").and_then(Value::as_array) {
+        walk_postman(items, None, &variables, &mut recordings, warnings);
+    }
+    recordings
+}
+
+/// Reads the JSON API calls of a HAR capture, skipping pages, assets and CORS preflights.
+pub fn read_har(har: &Value, warnings: &mut Vec<String>) -> Vec<Recording> {
+    let mut recordings = Vec::new();
+    let mut skipped = 0;
+    let entries = har.pointer("/log/entries").and_then(Value::as_array).cloned().unwrap_or_default();
+    for entry in &entries {
+        let request = &entry["request"];
+        let response = &entry["response"];
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("GET").to_lowercase();
+        let status = response.get("status").and_then(Value::as_u64).map(|s| s as u16);
+        let is_json = |mime: Option<&Value>| mime.and_then(Value::as_str).is_some_and(|m| m.contains("json"));
+        let sends_json = is_json(request.pointer("/postData/mimeType"));
+        let returns_json = is_json(response.pointer("/content/mimeType"));
+        let writes = method != "get" && status.is_some_and(|s| (200..300).contains(&s));
+        if method == "options" || !(sends_json || returns_json || writes) {
+            skipped += 1;
+            continue;
+        }
+
+        let url = request.get("url").and_then(Value::as_str).unwrap_or("");
+        let (path, query) = split_url(url);
+        let query = match request.get("queryString").and_then(Value::as_array) {
+            Some(_) => pairs(request.get("queryString"), "name", &BTreeMap::new()),
+            None => query,
+        };
+        let body = match request.pointer("/postData/text").and_then(Value::as_str) {
+            Some(text) if sends_json => serde_json::from_str(text).ok(),
+            Some(text) if !text.is_empty() => {
+                warnings.push(format!("{} {}: non-JSON request body not imported", method.to_uppercase(), path));
+                None
+            }
+            _ => None,
+        };
+        let encoded = response.pointer("/content/encoding").and_then(Value::as_str) == Some("base64");
+        let response = response
+            .pointer("/content/text")
+            .and_then(Value::as_str)
+            .filter(|_| returns_json && !encoded)
+            .and_then(|text| serde_json::from_str(text).ok());
+        recordings.push(Recording {
+            name: None,
+            folder: None,
+            method,
+            segments: segments(&path, &BTreeMap::new()),
+            query,
+            headers: pairs(request.get("headers"), "name", &BTreeMap::new()),
+            body,
+            status,
+            response,
+        });
+    }
+    if skipped > 0 {
+        warnings.push(format!("{} request(s) without JSON skipped (pages, assets, preflights)", skipped));
+    }
+    recordings
+}
+
+/// Numbers, UUIDs and long hex strings are values, never route names.
+fn is_id(segment: &str) -> bool {
+    let hex = segment.chars().all(|c| c.is_ascii_hexdigit());
+    let is_uuid = segment.len() == 36
+        && segment.char_indices().all(|(i, c)| if [8, 13, 18, 23].contains(&i) { c == '-' } else { c.is_ascii_hexdigit() });
+    segment.chars().all(|c| c.is_ascii_digit()) || is_uuid || (hex && segment.len() >= 16 && segment.chars().any(|c| c.is_ascii_digit()))
+}
+
+/// `api` and `v1`-like segments prefix every route and name none of them.
+fn is_prefix(segment: &str) -> bool {
+    let version = segment.len() > 1 && segment.starts_with('v') && segment[1..].chars().all(|c| c.is_ascii_digit());
+    segment == "api" || version
+}
+
+fn is_custom_header(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.starts_with("x-") && lower != "x-requested-with" && !lower.starts_with("x-forwarded")
+}
+
+/// Operation id from the method and the path template, never from sample
+/// values: `GET /users/{user_id}/orders/{order_id}` -> `get_user_order_by_id`.
+fn operation_name(method: &str, template: &[String]) -> String {
+    let segments: Vec<&str> = template.iter().map(String::as_str).filter(|s| !s.is_empty() && !is_prefix(s)).collect();
+    let mut words = vec![method.to_lowercase()];
+    let mut by = None;
+    let mut previous_static = false;
+    for (position, segment) in segments.iter().enumerate() {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(parameter) => {
+                // The collection before a parameter names one of its items.
+                let mut stem = String::new();
+                if previous_static && let Some(last) = words.last_mut() {
+                    *last = to_singular(last);
+                    stem = last.clone();
+                }
+                if position == segments.len() - 1 {
+                    let short = parameter.strip_prefix(&format!("{}_", stem)).filter(|s| !stem.is_empty() && !s.is_empty());
+                    by = Some(short.unwrap_or(parameter).to_string());
+                }
+                previous_static = false;
+            }
+            None => {
+                words.push(to_snake_case(&to_python_identifier(segment)));
+                previous_static = true;
+            }
+        }
+    }
+    if words.len() == 1 && by.is_none() {
+        words.push("root".to_string());
+    }
+    if let Some(by) = by {
+        words.push(format!("by_{}", by));
+    }
+    to_python_identifier(&words.join("_"))
+}
+
+fn skeleton(segments: &[(String, Option<String>)], params: &[bool], length: usize) -> String {
+    segments[..length]
+        .iter()
+        .zip(params)
+        .map(|((value, _), param)| if *param { "{}" } else { value.as_str() })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Marks the path segments that are parameters: declared variables, ids, and
+/// segments that vary between requests of the same shape under a collection
+/// that was itself requested (`/users` and `/users/alice`, `/users/bob`).
+fn detect_parameters(recordings: &[Recording]) -> Vec<Vec<bool>> {
+    let mut params: Vec<Vec<bool>> = recordings
+        .iter()
+        .map(|r| r.segments.iter().map(|(value, variable)| variable.is_some() || is_id(value)).collect())
+        .collect();
+    let longest = recordings.iter().map(|r| r.segments.len()).max().unwrap_or(0);
+    for position in 1..longest {
+        let requested: BTreeSet<String> = recordings
+            .iter()
+            .zip(&params)
+            .map(|(r, p)| skeleton(&r.segments, p, r.segments.len()))
+            .collect();
+        let mut variants: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+        let shape = |r: &Recording, p: &[bool]| {
+            let mut blanked = p.to_vec();
+            blanked[position] = true;
+            skeleton(&r.segments, &blanked, r.segments.len())
+        };
+        for (recording, p) in recordings.iter().zip(&params) {
+            if recording.segments.len() > position && !p[position] && !p[position - 1] {
+                variants.entry(shape(recording, p)).or_default().insert(&recording.segments[position].0);
+            }
+        }
+        let varying: Vec<(usize, bool)> = recordings
+            .iter()
+            .zip(&params)
+            .enumerate()
+            .map(|(i, (recording, p))| {
+                let is_param = recording.segments.len() > position
+                    && !p[position]
+                    && !p[position - 1]
+                    && variants.get(&shape(recording, p)).is_some_and(|values| values.len() > 1)
+                    && requested.contains(&skeleton(&recording.segments, p, position));
+                (i, is_param)
+            })
+            .collect();
+        for (i, is_param) in varying {
+            if is_param {
+                params[i][position] = true;
+            }
+        }
+    }
+    params
+}
+
+fn query_schema(values: &[&str]) -> Value {
+    let example = values.first().copied().unwrap_or("");
+    if values.iter().all(|v| v.parse::<i64>().is_ok()) {
+        json!({"type": "integer", "example": example.parse::<i64>().unwrap_or(1)})
+    } else if values.iter().all(|v| v.parse::<f64>().is_ok()) {
+        json!({"type": "number", "example": example.parse::<f64>().unwrap_or(1.0)})
+    } else if values.iter().all(|v| *v == "true" || *v == "false") {
+        json!({"type": "boolean", "example": example == "true"})
+    } else if values.iter().all(|v| is_id(v) && v.len() == 36) {
+        json!({"type": "string", "format": "uuid", "example": example})
+    } else {
+        json!({"type": "string", "example": example})
+    }
+}
+
+fn rename_refs(value: &mut Value, renames: &[(String, String)]) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if key == "$ref" {
+                    if let Some((_, new)) = renames.iter().find(|(old, _)| child.as_str() == Some(&format!("#/components/schemas/{}", old))) {
+                        *child = json!(format!("#/components/schemas/{}", new));
+                    }
+                } else {
+                    rename_refs(child, renames);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| rename_refs(item, renames)),
+        _ => {}
+    }
+}
+
+/// Adds inferred schemas to the components, reusing identical ones and
+/// prefixing clashing names with the root class. Returns the root's name.
+fn merge_components(components: &mut Map<String, Value>, schemas: Vec<(String, Value)>, root: &str) -> String {
+    let mut renames: Vec<(String, String)> = Vec::new();
+    for (name, mut schema) in schemas {
+        rename_refs(&mut schema, &renames);
+        let prefixed = if name == root { name.clone() } else { format!("{}{}", root, name) };
+        let candidate = [name.clone(), prefixed.clone()]
+            .into_iter()
+            .chain((2..).map(|n| format!("{}{}", prefixed, n)))
+            .find(|c| components.get(c).is_none_or(|existing| *existing == schema))
+            .unwrap_or_default();
+        if candidate != name {
+            renames.push((name.clone(), candidate.clone()));
+        }
+        components.insert(candidate, schema);
+    }
+    renames.into_iter().find(|(old, _)| old == root).map_or(root.to_string(), |(_, new)| new)
+}
+
+/// Schema for recorded JSON payloads, with the first one as its example.
+fn payload_schema(components: &mut Map<String, Value>, class: &str, samples: &[&Value]) -> Value {
+    let example = samples[0].clone();
+    let objects = samples
+        .iter()
+        .all(|s| s.is_object() || s.as_array().is_some_and(|items| !items.is_empty() && items.iter().all(Value::is_object)));
+    let owned: Vec<Value> = samples.iter().map(|s| (*s).clone()).collect();
+    match infer_schemas(class, &owned).ok().filter(|_| objects) {
+        Some(schemas) => {
+            let root = merge_components(components, schemas, class);
+            let reference = json!({"$ref": format!("#/components/schemas/{}", root)});
+            if samples.iter().all(|s| s.is_array()) {
+                json!({"type": "array", "items": reference, "example": example})
+            } else {
+                let mut reference = reference;
+                reference["example"] = example;
+                reference
+            }
+        }
+        None => json!({"example": example}),
+    }
+}
+
+/// Builds an OpenAPI document reproducing the recorded requests: one
+/// operation per method and path template, models inferred from the bodies.
+pub fn recordings_spec(title: &str, recordings: &[Recording], warnings: &mut Vec<String>) -> Spec {
+    let params = detect_parameters(recordings);
+
+    // Parameter names are shared by every method of a path.
+    let mut names: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (recording, p) in recordings.iter().zip(&params) {
+        let key = skeleton(&recording.segments, p, recording.segments.len());
+        if names.contains_key(&key) {
+            continue;
+        }
+        let mut taken: Vec<String> = Vec::new();
+        for (position, (value, variable)) in recording.segments.iter().enumerate() {
+            if !p[position] {
+                taken.push(String::new());
+                continue;
+            }
+            let previous = position.checked_sub(1).filter(|i| !p[*i]).map(|i| &recording.segments[i].0);
+            let mut name = match (variable, previous) {
+                (Some(variable), _) => to_field_name(variable),
+                (None, Some(previous)) => {
+                    let stem = to_singular(&to_snake_case(&to_python_identifier(previous)));
+                    if is_id(value) { format!("{}_id", stem) } else { stem }
+                }
+                (None, None) => "param".to_string(),
+            };
+            if taken.contains(&name) {
+                name = format!("{}_{}", name, position);
+            }
+            taken.push(name);
+        }
+        names.insert(key, taken);
+    }
+
+    let mut groups: Vec<(String, String, Vec<&Recording>)> = Vec::new();
+    for (recording, p) in recordings.iter().zip(&params) {
+        let key = skeleton(&recording.segments, p, recording.segments.len());
+        match groups.iter_mut().find(|(method, k, _)| *method == recording.method && *k == key) {
+            Some((_, _, members)) => members.push(recording),
+            None => groups.push((recording.method.clone(), key, vec![recording])),
+        }
+    }
+
+    let mut paths = Map::new();
+    let mut components = Map::new();
+    let mut operation_ids: Vec<String> = Vec::new();
+    for (method, key, members) in &groups {
+        let first = members[0];
+        let path_names = &names[key];
+        let template: Vec<String> = key
+            .split('/')
+            .zip(path_names)
+            .map(|(segment, name)| if segment == "{}" { format!("{{{}}}", name) } else { segment.to_string() })
+            .collect();
+        let path = format!("/{}", template.join("/"));
+        let label = format!("{} {}", method.to_uppercase(), path);
+        let base = operation_name(method, &template);
+        let operation_id = (1..)
+            .map(|n| if n == 1 { base.clone() } else { format!("{}_{}", base, n) })
+            .find(|id| !operation_ids.contains(id))
+            .unwrap_or_default();
+        operation_ids.push(operation_id.clone());
+        let class = to_camel_case(&operation_id);
+        let tag = first.folder.clone().unwrap_or_else(|| {
+            key.split('/')
+                .find(|s| !s.is_empty() && *s != "{}" && !is_prefix(s))
+                .unwrap_or("default")
+                .to_string()
+        });
+
+        let mut parameters = Vec::new();
+        for (position, segment) in key.split('/').enumerate() {
+            if segment == "{}" {
+                let values: Vec<&str> = members.iter().map(|m| m.segments[position].0.as_str()).collect();
+                let mut schema = query_schema(&values);
+                if schema["type"] == "number" || schema["type"] == "boolean" {
+                    schema = json!({"type": "string", "example": values[0]});
+                }
+                parameters.push(json!({"name": path_names[position], "in": "path", "required": true, "schema": schema}));
+            }
+        }
+        let mut query_names: Vec<&str> = Vec::new();
+        for member in members {
+            for (name, _) in &member.query {
+                if !query_names.contains(&name.as_str()) {
+                    query_names.push(name);
+                }
+            }
+        }
+        for name in query_names {
+            let values: Vec<&str> = members
+                .iter()
+                .filter_map(|m| m.query.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str()))
+                .collect();
+            let required = values.len() == members.len();
+            parameters.push(json!({"name": name, "in": "query", "required": required, "schema": query_schema(&values)}));
+        }
+        let mut header_names: Vec<String> = Vec::new();
+        for member in members {
+            for (name, value) in member.headers.iter().filter(|(name, _)| is_custom_header(name)) {
+                let lower = name.to_lowercase();
+                if !header_names.contains(&lower) {
+                    header_names.push(lower.clone());
+                    parameters.push(json!({"name": name, "in": "header", "required": false, "schema": {"type": "string", "example": value}}));
+                }
+            }
+        }
+
+        let mut operation = json!({"tags": [tag], "operationId": operation_id});
+        if let Some(name) = &first.name {
+            operation["summary"] = json!(name);
+        }
+        // The generated route test replays this request as it was recorded.
+        let url = format!("/{}", first.segments.iter().map(|(value, _)| value.as_str()).collect::<Vec<_>>().join("/"));
+        let headers: Vec<&(String, String)> = first.headers.iter().filter(|(name, _)| is_custom_header(name)).collect();
+        operation["x-recording"] = json!({"url": url, "query": first.query, "headers": headers});
+        if !parameters.is_empty() {
+            operation["parameters"] = Value::Array(parameters);
+        }
+        let bodies: Vec<&Value> = members.iter().filter_map(|m| m.body.as_ref()).collect();
+        if !bodies.is_empty() {
+            let schema = payload_schema(&mut components, &format!("{}Request", class), &bodies);
+            operation["requestBody"] = json!({
+                "required": bodies.len() == members.len(),
+                "content": {"application/json": {"schema": schema}},
+            });
+        }
+
+        let statuses: BTreeSet<u16> = members.iter().filter_map(|m| m.status).collect();
+        let success = statuses.iter().copied().find(|s| (200..300).contains(s));
+        let mut responses = Map::new();
+        match success {
+            Some(status) => {
+                let payloads: Vec<&Value> = members
+                    .iter()
+                    .filter(|m| m.status == Some(status))
+                    .filter_map(|m| m.response.as_ref())
+                    .collect();
+                let mut response = json!({"description": "Recorded response"});
+                if !payloads.is_empty() && status != 204 {
+                    let schema = payload_schema(&mut components, &format!("{}Response", class), &payloads);
+                    response["content"] = json!({"application/json": {"schema": schema}});
+                }
+                responses.insert(status.to_string(), response);
+            }
+            None if !statuses.is_empty() => {
+                warnings.push(format!("{}: only error responses were recorded", label));
+                responses.insert("200".to_string(), json!({"description": "OK"}));
+            }
+            None => {
+                responses.insert("200".to_string(), json!({"description": "OK"}));
+            }
+        }
+        operation["responses"] = Value::Object(responses);
+
+        let item = paths.entry(path).or_insert_with(|| json!({}));
+        item[method.as_str()] = operation;
+    }
+
+    Spec {
+        value: json!({
+            "openapi": "3.1.0",
+            "info": {"title": title, "version": "1.0.0"},
+            "paths": paths,
+            "components": {"schemas": components},
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(method: &str, url: &str, response: &str) -> Value {
+        json!({
+            "request": {"method": method, "url": url, "headers": [{"name": "X-Tenant", "value": "acme"}, {"name": "Accept", "value": "*/*"}]},
+            "response": {"status": 200, "content": {"mimeType": "application/json", "text": response}},
+        })
+    }
+
+    fn spec(entries: Vec<Value>) -> Value {
+        let mut warnings = Vec::new();
+        let recordings = read_har(&json!({"log": {"entries": entries}}), &mut warnings);
+        recordings_spec("test", &recordings, &mut warnings).value
+    }
+
+    fn template(path: &str) -> Vec<String> {
+        path.trim_start_matches('/').split('/').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn operation_names_come_from_the_template() {
+        assert_eq!(operation_name("get", &template("/users")), "get_users");
+        assert_eq!(operation_name("get", &template("/users/{user_id}")), "get_user_by_id");
+        assert_eq!(operation_name("delete", &template("/api/v2/users/{user_id}/orders/{order_id}")), "delete_user_order_by_id");
+        assert_eq!(operation_name("get", &template("/users/{user_id}/orders")), "get_user_orders");
+        assert_eq!(operation_name("get", &template("/files/{path}")), "get_file_by_path");
+        assert_eq!(operation_name("get", &template("/")), "get_root");
+    }
+
+    #[test]
+    fn sample_values_never_reach_operation_ids() {
+        let value = spec(vec![
+            entry("GET", "http://h/users/12", r#"{"id": 12}"#),
+            entry("GET", "http://h/users/7", r#"{"id": 7}"#),
+        ]);
+        let operation = &value["paths"]["/users/{user_id}"]["get"];
+        assert_eq!(operation["operationId"], "get_user_by_id");
+        assert_eq!(operation["x-recording"]["url"], "/users/12");
+    }
+
+    #[test]
+    fn recording_keeps_the_query_and_custom_headers() {
+        let value = spec(vec![entry("GET", "http://h/users?active=true&tag=a&tag=b", "[]")]);
+        let operation = &value["paths"]["/users"]["get"];
+        assert_eq!(operation["x-recording"]["query"], json!([["active", "true"], ["tag", "a"], ["tag", "b"]]));
+        assert_eq!(operation["x-recording"]["headers"], json!([["X-Tenant", "acme"]]));
+        let parameters = operation["parameters"].as_array().unwrap();
+        assert!(parameters.iter().any(|p| p["name"] == "active" && p["schema"]["type"] == "boolean"));
+    }
+
+    #[test]
+    fn varying_segments_under_a_requested_collection_are_parameters() {
+        let value = spec(vec![
+            entry("GET", "http://h/users", "[]"),
+            entry("GET", "http://h/users/alice", "{}"),
+            entry("GET", "http://h/users/bob", "{}"),
+            entry("GET", "http://h/status/live", "{}"),
+        ]);
+        let paths = value["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/users/{user}"), "{:?}", paths.keys().collect::<Vec<_>>());
+        assert!(paths.contains_key("/status/live"));
+        assert_eq!(value["paths"]["/users/{user}"]["get"]["operationId"], "get_user_by_user");
+    }
+
+    #[test]
+    fn clashing_names_are_numbered() {
+        let value = spec(vec![entry("GET", "http://h/users/1", "{}"), entry("GET", "http://h/user/1", "{}")]);
+        let mut ids: Vec<&str> = value["paths"]
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|item| item["get"]["operationId"].as_str().unwrap())
+            .collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["get_user_by_id", "get_user_by_id_2"]);
+    }
+
+    #[test]
+    fn ids_are_recognised() {
+        assert!(is_id("42"));
+        assert!(is_id("3f2504e0-4f89-11d3-9a0c-0305e82c3301"));
+        assert!(is_id("5f1d7c2b9a3e4f6d8c0b1a2e"));
+        assert!(!is_id("users"));
+        assert!(!is_id("deadbeef"));
+    }
+}
@@ -0,0 +1,459 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use crate::utils::fs::python_files;
+use crate::utils::imports::{imported_names, module_for_path};
+use crate::utils::python::{
+    call_arguments, dependency_names, docstring, logical_lines, parse_def, split_call, split_top_level,
+    string_literal, Param,
+};
+
+const HTTP_METHODS: [&str; 8] = ["get", "post", "put", "patch", "delete", "head", "options", "trace"];
+const AUTH_HINTS: [&str; 8] = ["current_user", "current_active", "auth", "token", "permission", "scope", "api_key", "verify"];
+
+/// One endpoint found by statically reading the route modules, with the path
+/// prefixes, tags and dependencies of every router that includes it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Endpoint {
+    pub method: String,
+    pub path: String,
+    pub handler: String,
+    pub module: String,
+    pub file: String,
+    pub line: usize,
+    pub auth: Vec<String>,
+    pub tags: Vec<String>,
+    pub response_model: Option<String>,
+    pub status_code: Option<String>,
+    /// False when no `include_router` chain reaches the router from the app.
+    pub included: bool,
+    #[serde(skip)]
+    pub params: Vec<Param>,
+    #[serde(skip)]
+    pub returns: Option<String>,
+    #[serde(skip)]
+    pub docstring: Option<String>,
+    /// Raw keyword arguments of the route decorator.
+    #[serde(skip)]
+    pub options: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default)]
+struct Router {
+    is_app: bool,
+    prefix: String,
+    tags: Vec<String>,
+    dependencies: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Operation {
+    router: String,
+    methods: Vec<String>,
+    path: String,
+    line: usize,
+    keywords: BTreeMap<String, String>,
+    handler: String,
+    params: Vec<Param>,
+    returns: Option<String>,
+    docstring: Option<String>,
+}
+
+#[derive(Debug)]
+struct Include {
+    on: String,
+    target: String,
+    prefix: String,
+    tags: Vec<String>,
+    dependencies: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct RouteModule {
+    file: PathBuf,
+    routers: BTreeMap<String, Router>,
+    operations: Vec<Operation>,
+    includes: Vec<Include>,
+    /// Local name -> (module, attribute) bound by imports.
+    names: BTreeMap<String, (String, Option<String>)>,
+}
+
+/// Renders a path or prefix argument; non-literal expressions are kept in braces.
+fn path_value(expr: &str) -> String {
+    string_literal(expr).unwrap_or_else(|| format!("{{{}}}", expr.trim()))
+}
+
+fn list_values(expr: Option<&String>) -> Vec<String> {
+    let Some(expr) = expr else {
+        return Vec::new();
+    };
+    let inner = expr.trim().trim_start_matches(['[', '(']).trim_end_matches([']', ')']);
+    split_top_level(inner, ',')
+        .iter()
+        .map(|item| string_literal(item).unwrap_or_else(|| item.trim().to_string()))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn join_paths(prefix: &str, path: &str) -> String {
+    let joined = format!("{}{}", prefix.trim_end_matches('/'), path);
+    if joined.is_empty() {
+        "/".to_string()
+    } else {
+        joined
+    }
+}
+
+pub fn is_auth_dependency(name: &str) -> bool {
+    let name = name.rsplit('.').next().unwrap_or(name).to_lowercase();
+    AUTH_HINTS.iter().any(|hint| name.contains(hint))
+}
+
+fn parse_module(source: &str, module: &str, is_package: bool) -> RouteModule {
+    let mut parsed = RouteModule::default();
+    for (alias, target, attribute) in imported_names(source, module, is_package) {
+        parsed.names.insert(alias, (target, attribute));
+    }
+
+    let lines = logical_lines(source);
+    let mut decorators: Vec<(usize, String)> = Vec::new();
+    for (number, line) in &lines {
+        let statement = line.trim();
+        if let Some(decorator) = statement.strip_prefix('@') {
+            decorators.push((*number, decorator.to_string()));
+            continue;
+        }
+        if let Some(def) = parse_def(statement) {
+            for (decorator_line, decorator) in decorators.drain(..) {
+                let Some((callee, args)) = split_call(&decorator) else {
+                    continue;
+                };
+                let Some((router, verb)) = callee.rsplit_once('.') else {
+                    continue;
+                };
+                let (positional, keywords) = call_arguments(args);
+                let keywords: BTreeMap<String, String> = keywords.into_iter().collect();
+                let methods = if HTTP_METHODS.contains(&verb) {
+                    vec![verb.to_uppercase()]
+                } else if verb == "api_route" {
+                    let methods = list_values(keywords.get("methods"));
+                    if methods.is_empty() { vec!["GET".to_string()] } else { methods.iter().map(|m| m.to_uppercase()).collect() }
+                } else {
+                    continue;
+                };
+                let path = positional.first().or(keywords.get("path")).map(|p| path_value(p)).unwrap_or_default();
+                parsed.operations.push(Operation {
+                    router: router.to_string(),
+                    methods,
+                    path,
+                    line: decorator_line,
+                    keywords,
+                    handler: def.name.clone(),
+                    params: def.params.clone(),
+                    returns: def.returns.clone(),
+                    docstring: docstring(source, *number),
+                });
+            }
+            continue;
+        }
+        decorators.clear();
+
+        if let Some((target, call)) = statement.split_once('=').filter(|(t, _)| !t.contains('(')) {
+            let name = target.split(':').next().unwrap_or(target).trim();
+            if let Some((callee, args)) = split_call(call) {
+                let callee = callee.rsplit('.').next().unwrap_or(callee);
+                if callee == "APIRouter" || callee == "FastAPI" {
+                    let (_, keywords) = call_arguments(args);
+                    let keywords: BTreeMap<String, String> = keywords.into_iter().collect();
+                    parsed.routers.insert(
+                        name.to_string(),
+                        Router {
+                            is_app: callee == "FastAPI",
+                            prefix: keywords.get("prefix").map(|p| path_value(p)).unwrap_or_default(),
+                            tags: list_values(keywords.get("tags")),
+                            dependencies: keywords.get("dependencies").map(|d| dependency_names(d)).unwrap_or_default(),
+                        },
+                    );
+                }
+            }
+            continue;
+        }
+
+        let Some((on, args)) = split_call(statement).and_then(|(callee, args)| Some((callee.strip_suffix(".include_router")?, args)))
+        else {
+            continue;
+        };
+        let (positional, keywords) = call_arguments(args);
+        let keywords: BTreeMap<String, String> = keywords.into_iter().collect();
+        let Some(target) = positional.first().or(keywords.get("router")) else {
+            continue;
+        };
+        parsed.includes.push(Include {
+            on: on.to_string(),
+            target: target.trim().to_string(),
+            prefix: keywords.get("prefix").map(|p| path_value(p)).unwrap_or_default(),
+            tags: list_values(keywords.get("tags")),
+            dependencies: keywords.get("dependencies").map(|d| dependency_names(d)).unwrap_or_default(),
+        });
+    }
+    parsed
+}
+
+struct Scanner<'a> {
+    root: &'a Path,
+    modules: BTreeMap<String, RouteModule>,
+    visited: BTreeSet<(String, String)>,
+    endpoints: Vec<Endpoint>,
+}
+
+struct Chain {
+    prefix: String,
+    tags: Vec<String>,
+    dependencies: Vec<String>,
+    included: bool,
+}
+
+impl Scanner<'_> {
+    /// Resolves an `include_router` argument like `user_router` or `user.router`
+    /// to the module and variable defining the router, following re-exports.
+    fn resolve(&self, module: &str, expr: &str, depth: usize) -> Option<(String, String)> {
+        if depth > 10 {
+            return None;
+        }
+        let (head, rest) = match expr.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (expr, None),
+        };
+        let current = self.modules.get(module)?;
+        if rest.is_none() && current.routers.contains_key(head) {
+            return Some((module.to_string(), head.to_string()));
+        }
+        let qualified_head = match current.names.get(head) {
+            Some((target, Some(attribute))) => format!("{}.{}", target, attribute),
+            Some((target, None)) if target == head || target.starts_with(&format!("{}.", head)) => head.to_string(),
+            Some((target, None)) => target.clone(),
+            None => format!("{}.{}", module, head),
+        };
+        let qualified = match rest {
+            Some(rest) => format!("{}.{}", qualified_head, rest),
+            None => qualified_head,
+        };
+
+        let parts: Vec<&str> = qualified.split('.').collect();
+        let (owner, name) = (1..parts.len()).rev().find_map(|split| {
+            let owner = parts[..split].join(".");
+            self.modules.contains_key(&owner).then(|| (owner, parts[split..].join(".")))
+        })?;
+        if owner == module && name == expr {
+            return None;
+        }
+        self.resolve(&owner, &name, depth + 1)
+    }
+
+    fn walk(&mut self, module: &str, router: &str, chain: Chain) {
+        if !self.visited.insert((module.to_string(), router.to_string())) {
+            return;
+        }
+        let Some(parsed) = self.modules.get(module) else {
+            return;
+        };
+        let definition = &parsed.routers[router];
+        let prefix = join_paths(&chain.prefix, &definition.prefix);
+        let prefix = if prefix == "/" { String::new() } else { prefix };
+        let tags: Vec<String> = chain.tags.iter().chain(&definition.tags).cloned().collect();
+        let dependencies: Vec<String> = chain.dependencies.iter().chain(&definition.dependencies).cloned().collect();
+        let file = parsed.file.strip_prefix(self.root).unwrap_or(&parsed.file).to_string_lossy().replace('\\', "/");
+
+        for operation in parsed.operations.iter().filter(|o| o.router == router) {
+            let mut used = dependencies.clone();
+            used.extend(operation.keywords.get("dependencies").map(|d| dependency_names(d)).unwrap_or_default());
+            for param in &operation.params {
+                used.extend(param.default.as_deref().map(dependency_names).unwrap_or_default());
+            }
+            let mut auth: Vec<String> = Vec::new();
+            for name in used.into_iter().filter(|n| is_auth_dependency(n)) {
+                if !auth.contains(&name) {
+                    auth.push(name);
+                }
+            }
+            let mut endpoint_tags = tags.clone();
+            endpoint_tags.extend(list_values(operation.keywords.get("tags")));
+            for method in &operation.methods {
+                self.endpoints.push(Endpoint {
+                    method: method.clone(),
+                    path: join_paths(&prefix, &operation.path),
+                    handler: operation.handler.clone(),
+                    module: module.to_string(),
+                    file: file.clone(),
+                    line: operation.line,
+                    auth: auth.clone(),
+                    tags: endpoint_tags.clone(),
+                    response_model: operation.keywords.get("response_model").cloned(),
+                    status_code: operation.keywords.get("status_code").cloned(),
+                    included: chain.included,
+                    params: operation.params.clone(),
+                    returns: operation.returns.clone(),
+                    docstring: operation.docstring.clone(),
+                    options: operation.keywords.clone(),
+                });
+            }
+        }
+
+        let includes: Vec<(String, String, Chain)> = parsed
+            .includes
+            .iter()
+            .filter(|i| i.on == router)
+            .filter_map(|include| {
+                let (target_module, target_router) = self.resolve(module, &include.target, 0)?;
+                Some((
+                    target_module,
+                    target_router,
+                    Chain {
+                        prefix: join_paths(&prefix, &include.prefix).trim_end_matches('/').to_string(),
+                        tags: tags.iter().chain(&include.tags).cloned().collect(),
+                        dependencies: dependencies.iter().chain(&include.dependencies).cloned().collect(),
+                        included: chain.included,
+                    },
+                ))
+            })
+            .collect();
+        for (target_module, target_router, chain) in includes {
+            self.walk(&target_module, &target_router, chain);
+        }
+    }
+}
+
+/// Collects every endpoint under `root/app`, starting from each `FastAPI()` app and
+/// following `include_router` calls; routers nothing includes come last with
+/// `included: false`.
+pub fn collect_endpoints(root: &Path) -> Vec<Endpoint> {
+    let mut modules = BTreeMap::new();
+    for path in python_files(&root.join("app")) {
+        let (Some(module), Ok(source)) = (module_for_path(root, &path), fs::read_to_string(&path)) else {
+            continue;
+        };
+        let is_package = path.file_stem().is_some_and(|s| s == "__init__");
+        let mut parsed = parse_module(&source, &module, is_package);
+        parsed.file = path;
+        modules.insert(module, parsed);
+    }
+    scan(root, modules)
+}
+
+fn scan(root: &Path, modules: BTreeMap<String, RouteModule>) -> Vec<Endpoint> {
+    let mut scanner = Scanner {
+        root,
+        modules,
+        visited: BTreeSet::new(),
+        endpoints: Vec::new(),
+    };
+
+    let mut roots: Vec<(String, String, bool)> = Vec::new();
+    for (module, parsed) in &scanner.modules {
+        for (name, router) in &parsed.routers {
+            roots.push((module.clone(), name.clone(), router.is_app));
+        }
+    }
+    let targets: BTreeSet<(String, String)> = scanner
+        .modules
+        .iter()
+        .flat_map(|(module, parsed)| parsed.includes.iter().map(move |i| (module, &i.target)))
+        .filter_map(|(module, target)| scanner.resolve(module, target, 0))
+        .collect();
+    // Apps first (app.main before any other), then the top of every unreached chain.
+    roots.sort_by_key(|(module, _, is_app)| (!is_app, module != "app.main"));
+    for (module, router, is_app) in roots {
+        if !is_app && targets.contains(&(module.clone(), router.clone())) {
+            continue;
+        }
+        let chain = Chain {
+            prefix: String::new(),
+            tags: Vec::new(),
+            dependencies: Vec::new(),
+            included: is_app,
+        };
+        scanner.walk(&module, &router, chain);
+    }
+    scanner.endpoints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scans `(module, source)` pairs; modules named like a package are `__init__.py`.
+    fn endpoints(sources: &[(&str, &str)]) -> Vec<Endpoint> {
+        let packages: BTreeSet<&str> = sources
+            .iter()
+            .flat_map(|(module, _)| module.rmatch_indices('.').map(move |(i, _)| &module[..i]))
+            .collect();
+        let modules = sources
+            .iter()
+            .map(|(module, source)| {
+                let mut parsed = parse_module(source, module, packages.contains(module));
+                parsed.file = PathBuf::from(format!("{}.py", module.replace('.', "/")));
+                (module.to_string(), parsed)
+            })
+            .collect();
+        scan(Path::new(""), modules)
+    }
+
+    fn routes(endpoints: &[Endpoint]) -> Vec<String> {
+        endpoints.iter().map(|e| format!("{} {} {}", e.method, e.path, e.handler)).collect()
+    }
+
+    #[test]
+    fn prefixes_tags_and_auth_follow_the_include_chain() {
+        let endpoints = endpoints(&[
+            (
+                "app.main",
+                "from fastapi import FastAPI, Depends\nfrom app.routes import users_router\nfrom app.core.auth import get_current_user\n\napp = FastAPI()\napp.include_router(users_router, prefix=\"/api\", tags=[\"api\"])\n",
+            ),
+            ("app.routes", "from .users import router as users_router\n"),
+            (
+                "app.routes.users",
+                "router = APIRouter(prefix=\"/users\", tags=[\"users\"])\n\n@router.get(\"/\")\ndef list_users():\n    \"\"\"All users.\"\"\"\n\n@router.api_route(\"/{user_id}\", methods=[\"put\", \"patch\"], status_code=202)\nasync def update_user(\n    user_id: int,\n    user=Depends(get_current_user),\n):\n    ...\n",
+            ),
+        ]);
+        assert_eq!(routes(&endpoints), ["GET /api/users/ list_users", "PUT /api/users/{user_id} update_user", "PATCH /api/users/{user_id} update_user"]);
+        assert!(endpoints.iter().all(|e| e.included && e.tags == ["api", "users"]));
+        assert_eq!(endpoints[0].docstring.as_deref(), Some("All users."));
+        assert_eq!(endpoints[0].file, "app/routes/users.py");
+        assert!(endpoints[0].auth.is_empty());
+        assert_eq!(endpoints[1].auth, ["get_current_user"]);
+        assert_eq!(endpoints[1].status_code.as_deref(), Some("202"));
+        assert_eq!(endpoints[1].params[0].annotation.as_deref(), Some("int"));
+    }
+
+    #[test]
+    fn routers_nothing_includes_are_reported_last() {
+        let endpoints = endpoints(&[
+            ("app.main", "app = FastAPI()\n\n@app.get(\"/\")\ndef root():\n    ...\n"),
+            ("app.routes.orphan", "router = APIRouter(dependencies=[Depends(verify_token)])\n\n@router.delete(\"/items/{id}\")\ndef drop(id: int):\n    ...\n"),
+        ]);
+        assert_eq!(routes(&endpoints), ["GET / root", "DELETE /items/{id} drop"]);
+        assert!(endpoints[0].included && !endpoints[1].included);
+        assert_eq!(endpoints[1].auth, ["verify_token"]);
+    }
+
+    #[test]
+    fn module_attribute_includes_resolve() {
+        let endpoints = endpoints(&[
+            ("app.main", "from app.routes import items\n\napp = FastAPI()\napp.include_router(items.router)\n"),
+            ("app.routes", ""),
+            ("app.routes.items", "router = APIRouter(prefix=PREFIX)\n\n@router.post(path=\"\")\ndef create():\n    ...\n"),
+        ]);
+        assert_eq!(routes(&endpoints), ["POST {PREFIX} create"]);
+        assert!(endpoints[0].included);
+    }
+
+    #[test]
+    fn auth_hints() {
+        assert!(is_auth_dependency("deps.get_current_active_user"));
+        assert!(is_auth_dependency("verify_api_key"));
+        assert!(!is_auth_dependency("get_db"));
+        assert_eq!(join_paths("/api/", "/users"), "/api/users");
+        assert_eq!(join_paths("", ""), "/");
+    }
+}
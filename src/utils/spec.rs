@@ -0,0 +1,314 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::utils::string::{to_camel_case, to_python_identifier};
+
+pub const HTTP_METHODS: [&str; 8] = ["get", "post", "put", "patch", "delete", "head", "options", "trace"];
+
+/// An OpenAPI 3.x document read from JSON or YAML.
+pub struct Spec {
+    pub value: Value,
+}
+
+pub struct SpecOperation<'a> {
+    pub path: &'a str,
+    pub method: &'a str,
+    pub operation: &'a Value,
+    /// Path-level and operation-level parameters, `$ref`s resolved.
+    pub parameters: Vec<&'a Value>,
+}
+
+/// Name of the component a local `$ref` points to.
+pub fn ref_name(schema: &Value) -> Option<&str> {
+    schema.get("$ref")?.as_str()?.rsplit('/').next()
+}
+
+/// Python class name for a component, e.g. "user-profile" -> "UserProfile".
+pub fn class_name(component: &str) -> String {
+    let valid = component.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && component.starts_with(|c: char| c.is_alphabetic());
+    if valid {
+        component.to_string()
+    } else {
+        to_camel_case(to_python_identifier(component).trim_start_matches('_'))
+    }
+}
+
+/// Renders a JSON value as a Python literal.
+pub fn python_literal(value: &Value) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => serde_json::to_string(text).unwrap_or_default(),
+        Value::Array(items) => format!("[{}]", items.iter().map(python_literal).collect::<Vec<_>>().join(", ")),
+        Value::Object(map) => format!(
+            "{{{}}}",
+            map.iter()
+                .map(|(k, v)| format!("{}: {}", python_literal(&json!(k)), python_literal(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+impl Spec {
+    pub fn load(path: &Path) -> Result<Spec, Box<dyn Error>> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+        Spec::parse(&content, &path.display().to_string())
+    }
+
+    /// Parses a spec from JSON or YAML text; `name` is only used in errors.
+    pub fn parse(content: &str, name: &str) -> Result<Spec, Box<dyn Error>> {
+        let value: Value = if name.ends_with(".json") || content.trim_start().starts_with('{') {
+            serde_json::from_str(content)?
+        } else {
+            serde_yaml::from_str(content)?
+        };
+        if value.get("swagger").is_some() {
+            return Err("Swagger 2.0 documents are not supported, convert them to OpenAPI 3 first".into());
+        }
+        if value.get("openapi").is_none() {
+            return Err(format!("'{}' is not an OpenAPI document (no 'openapi' field)", name).into());
+        }
+        Ok(Spec { value })
+    }
+
+    /// Follows local `$ref`s (`#/components/...`) until a concrete value.
+    pub fn resolve<'a>(&'a self, value: &'a Value) -> &'a Value {
+        let mut current = value;
+        for _ in 0..32 {
+            let Some(reference) = current.get("$ref").and_then(Value::as_str) else {
+                break;
+            };
+            let Some(pointer) = reference.strip_prefix('#') else {
+                break;
+            };
+            match self.value.pointer(pointer) {
+                Some(target) => current = target,
+                None => break,
+            }
+        }
+        current
+    }
+
+    pub fn schemas(&self) -> Vec<(&str, &Value)> {
+        self.value
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .map(|schemas| schemas.iter().map(|(name, schema)| (name.as_str(), schema)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn operations(&self) -> Vec<SpecOperation<'_>> {
+        let mut operations = Vec::new();
+        let Some(paths) = self.value.get("paths").and_then(Value::as_object) else {
+            return operations;
+        };
+        for (path, item) in paths {
+            let item = self.resolve(item);
+            let shared: Vec<&Value> = item
+                .get("parameters")
+                .and_then(Value::as_array)
+                .map(|p| p.iter().map(|p| self.resolve(p)).collect())
+                .unwrap_or_default();
+            for method in HTTP_METHODS {
+                let Some(operation) = item.get(method) else {
+                    continue;
+                };
+                let own: Vec<&Value> = operation
+                    .get("parameters")
+                    .and_then(Value::as_array)
+                    .map(|p| p.iter().map(|p| self.resolve(p)).collect())
+                    .unwrap_or_default();
+                // Operation parameters override path-level ones with the same name and location.
+                let mut parameters: Vec<&Value> = shared
+                    .iter()
+                    .filter(|s| !own.iter().any(|o| o.get("name") == s.get("name") && o.get("in") == s.get("in")))
+                    .copied()
+                    .collect();
+                parameters.extend(own);
+                operations.push(SpecOperation {
+                    path,
+                    method,
+                    operation,
+                    parameters,
+                });
+            }
+        }
+        operations
+    }
+
+    /// JSON schema of the request body and its media type, preferring JSON.
+    pub fn request_body<'a>(&'a self, operation: &'a Value) -> Option<(&'a str, &'a Value, bool)> {
+        let body = self.resolve(operation.get("requestBody")?);
+        let content = body.get("content")?.as_object()?;
+        let (media, entry) = content
+            .iter()
+            .find(|(media, _)| media.contains("json"))
+            .or_else(|| content.iter().next())?;
+        let required = body.get("required").and_then(Value::as_bool).unwrap_or(false);
+        Some((media.as_str(), entry.get("schema").unwrap_or(&Value::Null), required))
+    }
+
+    /// First success response: status code and JSON schema, if it has content.
+    pub fn success_response<'a>(&'a self, operation: &'a Value) -> Option<(&'a str, Option<&'a Value>)> {
+        let responses = operation.get("responses")?.as_object()?;
+        let (status, response) = responses
+            .iter()
+            .find(|(status, _)| status.starts_with('2'))
+            .or_else(|| responses.iter().find(|(status, _)| *status == "default"))?;
+        let response = self.resolve(response);
+        let schema = response
+            .get("content")
+            .and_then(Value::as_object)
+            .and_then(|content| content.iter().find(|(media, _)| media.contains("json")).or_else(|| content.iter().next()))
+            .and_then(|(_, entry)| entry.get("schema"));
+        Some((status.as_str(), schema))
+    }
+
+    /// Python annotation for a schema, naming components by their class.
+    pub fn python_type(&self, schema: &Value) -> String {
+        if let Some(name) = ref_name(schema) {
+            return class_name(name);
+        }
+        let nullable = schema.get("nullable").and_then(Value::as_bool).unwrap_or(false);
+        let base = self.python_base_type(schema);
+        if nullable && !base.starts_with("Optional[") && base != "Any" {
+            format!("Optional[{}]", base)
+        } else {
+            base
+        }
+    }
+
+    fn python_base_type(&self, schema: &Value) -> String {
+        for key in ["anyOf", "oneOf"] {
+            if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+                let is_null = |v: &&Value| v.get("type").and_then(Value::as_str) == Some("null");
+                let nullable = variants.iter().any(|v| is_null(&v));
+                let mut types: Vec<String> = variants.iter().filter(|v| !is_null(v)).map(|v| self.python_type(v)).collect();
+                types.dedup();
+                let inner = match types.len() {
+                    0 => "Any".to_string(),
+                    1 => types.remove(0),
+                    _ => format!("Union[{}]", types.join(", ")),
+                };
+                return if nullable { format!("Optional[{}]", inner) } else { inner };
+            }
+        }
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            if parts.len() == 1 {
+                return self.python_type(&parts[0]);
+            }
+            return "Dict[str, Any]".to_string();
+        }
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            return format!("Literal[{}]", values.iter().map(python_literal).collect::<Vec<_>>().join(", "));
+        }
+        if let Some(value) = schema.get("const") {
+            return format!("Literal[{}]", python_literal(value));
+        }
+
+        // OpenAPI 3.1 allows `type: [string, "null"]`.
+        let (kind, nullable) = match schema.get("type") {
+            Some(Value::Array(types)) => {
+                let nullable = types.iter().any(|t| t == "null");
+                let kind = types.iter().filter_map(Value::as_str).find(|t| *t != "null").unwrap_or("null");
+                (kind, nullable)
+            }
+            Some(Value::String(kind)) => (kind.as_str(), false),
+            _ if schema.get("properties").is_some() => ("object", false),
+            _ => ("", false),
+        };
+        let format = schema.get("format").and_then(Value::as_str).unwrap_or("");
+        let base = match kind {
+            "string" => match format {
+                "date-time" => "datetime",
+                "date" => "date",
+                "time" => "time",
+                "uuid" => "UUID",
+                "binary" => "bytes",
+                _ => "str",
+            }
+            .to_string(),
+            "integer" => "int".to_string(),
+            "number" => "float".to_string(),
+            "boolean" => "bool".to_string(),
+            "null" => "None".to_string(),
+            "array" => format!("List[{}]", schema.get("items").map_or("Any".to_string(), |i| self.python_type(i))),
+            "object" => match schema.get("additionalProperties") {
+                Some(Value::Object(values)) if !values.is_empty() => format!("Dict[str, {}]", self.python_type(&Value::Object(values.clone()))),
+                _ => "Dict[str, Any]".to_string(),
+            },
+            _ => "Any".to_string(),
+        };
+        if nullable && base != "None" { format!("Optional[{}]", base) } else { base }
+    }
+
+    /// A plausible example value for a schema, used for test requests.
+    pub fn sample(&self, schema: &Value, depth: usize) -> Value {
+        if let Some(example) = schema.get("example").or_else(|| schema.get("examples").and_then(|e| e.get(0))) {
+            return example.clone();
+        }
+        if let Some(default) = schema.get("default") {
+            return default.clone();
+        }
+        if let Some(value) = schema.get("enum").and_then(|e| e.get(0)).or_else(|| schema.get("const")) {
+            return value.clone();
+        }
+        if schema.get("$ref").is_some() {
+            return if depth > 4 { Value::Null } else { self.sample(self.resolve(schema), depth + 1) };
+        }
+        for key in ["anyOf", "oneOf"] {
+            if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+                let variant = variants.iter().find(|v| v.get("type").and_then(Value::as_str) != Some("null"));
+                return variant.map_or(Value::Null, |v| self.sample(v, depth));
+            }
+        }
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            let mut merged = serde_json::Map::new();
+            for part in parts {
+                if let Value::Object(fields) = self.sample(part, depth) {
+                    merged.extend(fields);
+                }
+            }
+            return Value::Object(merged);
+        }
+
+        let kind = match schema.get("type") {
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).find(|t| *t != "null").unwrap_or("null"),
+            Some(Value::String(kind)) => kind.as_str(),
+            _ if schema.get("properties").is_some() => "object",
+            _ => "",
+        };
+        match kind {
+            "string" => json!(match schema.get("format").and_then(Value::as_str).unwrap_or("") {
+                "date-time" => "2024-01-01T00:00:00Z",
+                "date" => "2024-01-01",
+                "time" => "12:00:00",
+                "uuid" => "00000000-0000-0000-0000-000000000001",
+                "email" => "user@example.com",
+                "uri" | "url" => "https://example.com",
+                _ => "string",
+            }),
+            "integer" => json!(schema.get("minimum").and_then(Value::as_i64).unwrap_or(1).max(1)),
+            "number" => json!(schema.get("minimum").and_then(Value::as_f64).unwrap_or(1.0).max(1.0)),
+            "boolean" => json!(true),
+            "array" if depth <= 4 => json!([self.sample(schema.get("items").unwrap_or(&Value::Null), depth + 1)]),
+            "object" if depth <= 4 => {
+                let mut object = serde_json::Map::new();
+                if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                    for (name, property) in properties {
+                        object.insert(name.clone(), self.sample(property, depth + 1));
+                    }
+                }
+                Value::Object(object)
+            }
+            "array" => json!([]),
+            "object" => json!({}),
+            _ => Value::Null,
+        }
+    }
+}
@@ -0,0 +1,507 @@
+use std::collections::{BTreeMap, BTreeSet};
+use serde::Serialize;
+use serde_json::Value;
+use crate::utils::spec::{Spec, SpecOperation};
+
+#[derive(Debug, Serialize)]
+pub struct Change {
+    pub breaking: bool,
+    pub method: String,
+    pub path: String,
+    pub message: String,
+}
+
+/// Which side of the API a schema is on: requests break when they accept less,
+/// responses when they return something clients did not expect.
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Request,
+    Response,
+}
+
+struct Differ<'a> {
+    old: &'a Spec,
+    new: &'a Spec,
+    method: String,
+    path: String,
+    changes: Vec<Change>,
+}
+
+/// Path with its parameter names blanked, so renaming `{id}` is not a new endpoint.
+fn path_key(path: &str) -> String {
+    let mut key = String::new();
+    let mut inside = false;
+    for c in path.chars() {
+        match c {
+            '{' => {
+                inside = true;
+                key.push_str("{}");
+            }
+            '}' => inside = false,
+            _ if !inside => key.push(c),
+            _ => {}
+        }
+    }
+    key
+}
+
+fn parameter_keys<'a>(operation: &SpecOperation<'a>) -> Vec<(String, &'a Value)> {
+    let mut path_index = 0;
+    operation
+        .parameters
+        .iter()
+        .filter_map(|p| {
+            let location = p.get("in")?.as_str()?;
+            let name = p.get("name")?.as_str()?;
+            let key = match location {
+                // Path parameters are matched by position, their names never reach the wire.
+                "path" => {
+                    path_index += 1;
+                    format!("path #{}", path_index)
+                }
+                "header" => format!("header {}", name.to_lowercase()),
+                _ => format!("{} {}", location, name),
+            };
+            Some((key, *p))
+        })
+        .collect()
+}
+
+fn describe(parameter: &Value) -> String {
+    format!(
+        "{} parameter '{}'",
+        parameter.get("in").and_then(Value::as_str).unwrap_or("query"),
+        parameter.get("name").and_then(Value::as_str).unwrap_or("?")
+    )
+}
+
+fn is_required(value: &Value) -> bool {
+    value.get("required").and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn security(spec: &Spec, operation: &Value) -> bool {
+    let requirements = operation.get("security").or_else(|| spec.value.get("security"));
+    requirements
+        .and_then(Value::as_array)
+        .is_some_and(|r| !r.is_empty() && !r.iter().any(|s| s.as_object().is_some_and(|o| o.is_empty())))
+}
+
+/// JSON types a schema accepts, with "null" when nullable; empty means anything.
+fn types(spec: &Spec, schema: &Value) -> BTreeSet<String> {
+    let schema = spec.resolve(schema);
+    let mut kinds = BTreeSet::new();
+    for key in ["anyOf", "oneOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            for variant in variants {
+                let variant_types = types(spec, variant);
+                if variant_types.is_empty() {
+                    return BTreeSet::new();
+                }
+                kinds.extend(variant_types);
+            }
+            return kinds;
+        }
+    }
+    match schema.get("type") {
+        Some(Value::String(kind)) => {
+            kinds.insert(kind.clone());
+        }
+        Some(Value::Array(list)) => kinds.extend(list.iter().filter_map(Value::as_str).map(str::to_string)),
+        _ if schema.get("properties").is_some() => {
+            kinds.insert("object".to_string());
+        }
+        _ => {}
+    }
+    if schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        kinds.insert("null".to_string());
+    }
+    kinds
+}
+
+/// Whether every type in `narrow` is accepted by `wide`; integers are numbers.
+fn accepts(wide: &BTreeSet<String>, narrow: &BTreeSet<String>) -> bool {
+    wide.is_empty()
+        || narrow.iter().all(|kind| wide.contains(kind) || kind == "integer" && wide.contains("number"))
+}
+
+fn type_label(kinds: &BTreeSet<String>) -> String {
+    if kinds.is_empty() {
+        "any".to_string()
+    } else {
+        kinds.iter().cloned().collect::<Vec<_>>().join(" | ")
+    }
+}
+
+/// Properties and required names of an object schema, merging `allOf` parts.
+fn properties<'a>(spec: &'a Spec, schema: &'a Value) -> (BTreeMap<String, &'a Value>, BTreeSet<String>) {
+    let schema = spec.resolve(schema);
+    let mut fields = BTreeMap::new();
+    let mut required = BTreeSet::new();
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        for part in parts {
+            let (part_fields, part_required) = properties(spec, part);
+            fields.extend(part_fields);
+            required.extend(part_required);
+        }
+    }
+    if let Some(map) = schema.get("properties").and_then(Value::as_object) {
+        fields.extend(map.iter().map(|(name, value)| (name.clone(), value)));
+    }
+    if let Some(names) = schema.get("required").and_then(Value::as_array) {
+        required.extend(names.iter().filter_map(Value::as_str).map(str::to_string));
+    }
+    (fields, required)
+}
+
+fn enum_values(spec: &Spec, schema: &Value) -> Option<Vec<Value>> {
+    let schema = spec.resolve(schema);
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return Some(values.clone());
+    }
+    schema.get("const").map(|value| vec![value.clone()])
+}
+
+fn number(schema: &Value, key: &str) -> Option<f64> {
+    schema.get(key).and_then(Value::as_f64)
+}
+
+impl Differ<'_> {
+    fn push(&mut self, breaking: bool, message: String) {
+        self.changes.push(Change {
+            breaking,
+            method: self.method.clone(),
+            path: self.path.clone(),
+            message,
+        });
+    }
+
+    fn schema(&mut self, old: &Value, new: &Value, direction: Direction, location: &str, depth: usize) {
+        if depth > 8 {
+            return;
+        }
+        let old = self.old.resolve(old);
+        let new = self.new.resolve(new);
+        let request = direction == Direction::Request;
+
+        let (old_types, new_types) = (types(self.old, old), types(self.new, new));
+        let compatible = if request { accepts(&new_types, &old_types) } else { accepts(&old_types, &new_types) };
+        if !compatible {
+            self.push(
+                true,
+                format!("{}: type changed from {} to {}", location, type_label(&old_types), type_label(&new_types)),
+            );
+            return;
+        }
+        if old_types != new_types {
+            self.push(
+                false,
+                format!("{}: type changed from {} to {}", location, type_label(&old_types), type_label(&new_types)),
+            );
+        }
+
+        if let (Some(old_values), Some(new_values)) = (enum_values(self.old, old), enum_values(self.new, new)) {
+            let removed: Vec<String> = old_values.iter().filter(|v| !new_values.contains(v)).map(Value::to_string).collect();
+            let added: Vec<String> = new_values.iter().filter(|v| !old_values.contains(v)).map(Value::to_string).collect();
+            if !removed.is_empty() {
+                self.push(request, format!("{}: enum value(s) {} removed", location, removed.join(", ")));
+            }
+            if !added.is_empty() {
+                self.push(!request, format!("{}: enum value(s) {} added", location, added.join(", ")));
+            }
+        } else if enum_values(self.new, new).is_some() && request {
+            self.push(true, format!("{}: now restricted to an enum", location));
+        }
+
+        if request {
+            for (key, is_lower_bound) in [
+                ("minLength", true),
+                ("maxLength", false),
+                ("minimum", true),
+                ("maximum", false),
+                ("minItems", true),
+                ("maxItems", false),
+            ] {
+                let narrowed = match (number(old, key), number(new, key)) {
+                    (None, Some(_)) => true,
+                    (Some(before), Some(after)) => if is_lower_bound { after > before } else { after < before },
+                    _ => false,
+                };
+                if narrowed {
+                    self.push(true, format!("{}: {} narrowed to {}", location, key, new[key]));
+                }
+            }
+        }
+
+        if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+            self.schema(old_items, new_items, direction, &format!("{}[]", location), depth + 1);
+        }
+
+        let (old_fields, old_required) = properties(self.old, old);
+        let (new_fields, new_required) = properties(self.new, new);
+        for (name, old_field) in &old_fields {
+            let field = format!("{}.{}", location, name);
+            match new_fields.get(name) {
+                None => self.push(!request, format!("{}: field removed", field)),
+                Some(new_field) => {
+                    if request && !old_required.contains(name) && new_required.contains(name) {
+                        self.push(true, format!("{}: field became required", field));
+                    } else if !request && old_required.contains(name) && !new_required.contains(name) {
+                        self.push(true, format!("{}: field is no longer always returned", field));
+                    }
+                    self.schema(old_field, new_field, direction, &field, depth + 1);
+                }
+            }
+        }
+        for name in new_fields.keys().filter(|n| !old_fields.contains_key(*n)) {
+            let required = request && new_required.contains(name);
+            let label = if required { "required field added" } else { "field added" };
+            self.push(required, format!("{}.{}: {}", location, name, label));
+        }
+    }
+
+    fn operation(&mut self, old: &SpecOperation, new: &SpecOperation) {
+        let old_parameters = parameter_keys(old);
+        let new_parameters = parameter_keys(new);
+        for (key, old_parameter) in &old_parameters {
+            match new_parameters.iter().find(|(k, _)| k == key) {
+                None => self.push(false, format!("{} removed", describe(old_parameter))),
+                Some((_, new_parameter)) => {
+                    let label = describe(new_parameter);
+                    if !is_required(old_parameter) && is_required(new_parameter) {
+                        self.push(true, format!("{} became required", label));
+                    } else if is_required(old_parameter) && !is_required(new_parameter) {
+                        self.push(false, format!("{} became optional", label));
+                    }
+                    let null = Value::Null;
+                    self.schema(
+                        old_parameter.get("schema").unwrap_or(&null),
+                        new_parameter.get("schema").unwrap_or(&null),
+                        Direction::Request,
+                        &label,
+                        0,
+                    );
+                }
+            }
+        }
+        for (_, new_parameter) in new_parameters.iter().filter(|(k, _)| !old_parameters.iter().any(|(o, _)| o == k)) {
+            let required = is_required(new_parameter);
+            let label = if required { "new required" } else { "new optional" };
+            self.push(required, format!("{} {}", label, describe(new_parameter)));
+        }
+
+        match (self.old.request_body(old.operation), self.new.request_body(new.operation)) {
+            (None, Some((_, _, true))) => self.push(true, "new required request body".to_string()),
+            (None, Some(_)) => self.push(false, "new optional request body".to_string()),
+            (Some(_), None) => self.push(false, "request body removed".to_string()),
+            (Some((old_media, old_schema, old_required)), Some((new_media, new_schema, new_required))) => {
+                if old_media != new_media {
+                    self.push(true, format!("request body media type changed from {} to {}", old_media, new_media));
+                }
+                if !old_required && new_required {
+                    self.push(true, "request body became required".to_string());
+                }
+                self.schema(old_schema, new_schema, Direction::Request, "request body", 0);
+            }
+            (None, None) => {}
+        }
+
+        match (self.old.success_response(old.operation), self.new.success_response(new.operation)) {
+            (Some((old_status, old_schema)), Some((new_status, new_schema))) => {
+                if old_status != new_status {
+                    self.push(true, format!("success status changed from {} to {}", old_status, new_status));
+                }
+                match (old_schema, new_schema) {
+                    (Some(old_schema), Some(new_schema)) => {
+                        self.schema(old_schema, new_schema, Direction::Response, "response", 0)
+                    }
+                    (Some(_), None) => self.push(true, "response body removed".to_string()),
+                    (None, Some(_)) => self.push(false, "response body added".to_string()),
+                    (None, None) => {}
+                }
+            }
+            (Some((status, _)), None) => self.push(true, format!("success response {} removed", status)),
+            _ => {}
+        }
+
+        match (security(self.old, old.operation), security(self.new, new.operation)) {
+            (false, true) => self.push(true, "now requires authentication".to_string()),
+            (true, false) => self.push(false, "no longer requires authentication".to_string()),
+            _ => {}
+        }
+        if old.operation.get("deprecated").and_then(Value::as_bool) != Some(true)
+            && new.operation.get("deprecated").and_then(Value::as_bool) == Some(true)
+        {
+            self.push(false, "deprecated".to_string());
+        }
+    }
+}
+
+/// Compares two OpenAPI documents operation by operation, breaking changes first.
+pub fn diff(old: &Spec, new: &Spec) -> Vec<Change> {
+    let mut differ = Differ {
+        old,
+        new,
+        method: String::new(),
+        path: String::new(),
+        changes: Vec::new(),
+    };
+    let old_operations = old.operations();
+    let new_operations = new.operations();
+    let find = |operations: &[SpecOperation<'_>], target: &SpecOperation| {
+        operations
+            .iter()
+            .position(|o| o.method == target.method && path_key(o.path) == path_key(target.path))
+    };
+
+    for operation in &old_operations {
+        differ.method = operation.method.to_uppercase();
+        match find(&new_operations, operation) {
+            Some(index) => {
+                differ.path = new_operations[index].path.to_string();
+                differ.operation(operation, &new_operations[index]);
+            }
+            None => {
+                differ.path = operation.path.to_string();
+                differ.push(true, "endpoint removed".to_string());
+            }
+        }
+    }
+    for operation in &new_operations {
+        if find(&old_operations, operation).is_none() {
+            differ.method = operation.method.to_uppercase();
+            differ.path = operation.path.to_string();
+            differ.push(false, "endpoint added".to_string());
+        }
+    }
+
+    let mut changes = differ.changes;
+    changes.sort_by_key(|c| !c.breaking);
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(paths: Value) -> Spec {
+        Spec {
+            value: json!({
+                "openapi": "3.1.0",
+                "paths": paths,
+                "components": {"schemas": {"Pet": {
+                    "type": "object",
+                    "properties": {"id": {"type": "integer"}, "name": {"type": "string"}},
+                    "required": ["id", "name"],
+                }}},
+            }),
+        }
+    }
+
+    fn get(parameters: Value, response: Value) -> Value {
+        json!({"/pets/{pet_id}": {"get": {
+            "parameters": parameters,
+            "responses": {"200": {"description": "OK", "content": {"application/json": {"schema": response}}}},
+        }}})
+    }
+
+    fn messages(changes: &[Change]) -> Vec<(bool, &str)> {
+        changes.iter().map(|c| (c.breaking, c.message.as_str())).collect()
+    }
+
+    fn pet_id(name: &str) -> Value {
+        json!({"in": "path", "name": name, "required": true, "schema": {"type": "integer"}})
+    }
+
+    #[test]
+    fn identical_specs_have_no_changes() {
+        let old = spec(get(json!([pet_id("pet_id")]), json!({"$ref": "#/components/schemas/Pet"})));
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn renamed_path_parameter_is_the_same_endpoint() {
+        let old = spec(get(json!([pet_id("pet_id")]), json!({})));
+        let new = spec(json!({"/pets/{id}": get(json!([pet_id("id")]), json!({}))["/pets/{pet_id}"]}));
+        assert!(diff(&old, &new).is_empty(), "{:?}", diff(&old, &new));
+    }
+
+    #[test]
+    fn removed_endpoint_is_breaking_and_added_is_not() {
+        let old = spec(get(json!([]), json!({})));
+        let new = spec(json!({"/pets/{pet_id}/": old.value["paths"]["/pets/{pet_id}"].clone()}));
+        let changes = diff(&old, &new);
+        assert_eq!(messages(&changes), [(true, "endpoint removed"), (false, "endpoint added")]);
+        assert_eq!(changes[1].path, "/pets/{pet_id}/");
+    }
+
+    #[test]
+    fn new_required_query_parameter_is_breaking() {
+        let old = spec(get(json!([pet_id("pet_id")]), json!({})));
+        let verbose = json!({"in": "query", "name": "verbose", "required": true, "schema": {"type": "boolean"}});
+        let limit = json!({"in": "query", "name": "limit", "schema": {"type": "integer"}});
+        let new = spec(get(json!([pet_id("pet_id"), verbose, limit]), json!({})));
+        assert_eq!(
+            messages(&diff(&old, &new)),
+            [(true, "new required query parameter 'verbose'"), (false, "new optional query parameter 'limit'")]
+        );
+    }
+
+    #[test]
+    fn request_types_may_widen_but_not_narrow() {
+        let query = |schema: Value| json!([pet_id("pet_id"), {"in": "query", "name": "q", "schema": schema}]);
+        let integer = spec(get(query(json!({"type": "integer"})), json!({})));
+        let number = spec(get(query(json!({"type": "number"})), json!({})));
+        assert_eq!(messages(&diff(&integer, &number)), [(false, "query parameter 'q': type changed from integer to number")]);
+        assert_eq!(messages(&diff(&number, &integer)), [(true, "query parameter 'q': type changed from number to integer")]);
+        let bounded = spec(get(query(json!({"type": "number", "maximum": 10})), json!({})));
+        assert_eq!(messages(&diff(&number, &bounded)), [(true, "query parameter 'q': maximum narrowed to 10")]);
+    }
+
+    #[test]
+    fn response_fields_through_refs() {
+        let old = spec(get(json!([pet_id("pet_id")]), json!({"$ref": "#/components/schemas/Pet"})));
+        let mut new = spec(get(json!([pet_id("pet_id")]), json!({"$ref": "#/components/schemas/Pet"})));
+        new.value["components"]["schemas"]["Pet"]["properties"] = json!({"id": {"type": "integer"}, "tag": {"type": "string"}});
+        new.value["components"]["schemas"]["Pet"]["required"] = json!(["id"]);
+        assert_eq!(
+            messages(&diff(&old, &new)),
+            [(true, "response.name: field removed"), (false, "response.tag: field added")]
+        );
+    }
+
+    #[test]
+    fn enums_break_in_opposite_directions() {
+        let status = |values: Value| json!({"type": "object", "properties": {"status": {"type": "string", "enum": values}}});
+        let request = |values: Value| {
+            json!({"/pets": {"post": {
+                "requestBody": {"content": {"application/json": {"schema": status(values.clone())}}},
+                "responses": {"200": {"description": "OK", "content": {"application/json": {"schema": status(values)}}}},
+            }}})
+        };
+        let old = spec(request(json!(["a", "b"])));
+        let new = spec(request(json!(["a", "c"])));
+        assert_eq!(
+            messages(&diff(&old, &new)),
+            [
+                (true, "request body.status: enum value(s) \"b\" removed"),
+                (true, "response.status: enum value(s) \"c\" added"),
+                (false, "request body.status: enum value(s) \"c\" added"),
+                (false, "response.status: enum value(s) \"b\" removed"),
+            ]
+        );
+    }
+
+    #[test]
+    fn authentication_and_status_changes() {
+        let old = spec(get(json!([pet_id("pet_id")]), json!({})));
+        let mut new = spec(get(json!([pet_id("pet_id")]), json!({})));
+        let operation = &mut new.value["paths"]["/pets/{pet_id}"]["get"];
+        operation["security"] = json!([{"OAuth2PasswordBearer": []}]);
+        operation["responses"] = json!({"201": operation["responses"]["200"].clone()});
+        assert_eq!(
+            messages(&diff(&old, &new)),
+            [(true, "success status changed from 200 to 201"), (true, "now requires authentication")]
+        );
+    }
+}
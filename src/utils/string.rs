@@ -1,4 +1,3 @@
-#[allow(dead_code)]
 pub fn to_camel_case(snake: &str) -> String {
     snake
         .split('_')
@@ -11,3 +10,125 @@ pub fn to_camel_case(snake: &str) -> String {
         })
         .collect()
 }
+
+/// "listUsers", "User Accounts" or "get-user" -> "list_users", "user_accounts", "get_user".
+pub fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            let boundary = c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit());
+            if boundary && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else if !snake.is_empty() && !snake.ends_with('_') {
+            snake.push('_');
+        }
+        previous = Some(c);
+    }
+    snake.trim_end_matches('_').to_string()
+}
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+    "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+    "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Turns any name into a valid Python identifier: snake_case, no leading digit,
+/// keywords suffixed with `_`.
+pub fn to_python_identifier(name: &str) -> String {
+    let mut identifier = to_snake_case(name);
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if PYTHON_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Model attribute for a wire name, e.g. "birthDate" -> "birth_date"; Pydantic
+/// treats a leading `_` as private, so "_id" becomes "field_id".
+pub fn to_field_name(wire: &str) -> String {
+    let identifier = to_python_identifier(wire);
+    if identifier.starts_with('_') {
+        format!("field{}", identifier)
+    } else if wire.starts_with('_') {
+        format!("field_{}", identifier)
+    } else {
+        identifier
+    }
+}
+
+/// `name`, or `name_2`, `name_3`... when an earlier attribute already took it.
+pub fn unique_name(name: String, taken: &[String]) -> String {
+    let mut unique = name.clone();
+    let mut counter = 2;
+    while taken.contains(&unique) {
+        unique = format!("{}_{}", name, counter);
+        counter += 1;
+    }
+    unique
+}
+
+/// Attributes for the wire names of one model, in order, so that "id" and "ID"
+/// become "id" and "id_2" instead of one overwriting the other.
+pub fn to_field_names<'a>(wires: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut names = Vec::new();
+    for wire in wires {
+        names.push(unique_name(to_field_name(wire), &names));
+    }
+    names
+}
+
+/// Naive English singular for class names of list items: "line_items" -> "line_item".
+pub fn to_singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies").filter(|s| !s.is_empty()) {
+        format!("{}y", stem)
+    } else if let Some(singular) = ["sses", "xes", "ches", "shes"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix).map(|stem| format!("{}{}", stem, &suffix[..suffix.len() - 2])))
+    {
+        singular
+    } else if word.ends_with('s') && !word.ends_with("ss") && !word.ends_with("us") && word.len() > 1 {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_names_are_valid_public_attributes() {
+        assert_eq!(to_field_name("birthDate"), "birth_date");
+        assert_eq!(to_field_name("_id"), "field_id");
+        assert_eq!(to_field_name("__v"), "field_v");
+        assert_eq!(to_field_name("1st_place"), "field_1st_place");
+        assert_eq!(to_field_name("class"), "class_");
+        assert_eq!(to_field_name("@type"), "type");
+        assert_eq!(to_field_name(""), "field_");
+    }
+
+    #[test]
+    fn colliding_field_names_get_a_suffix() {
+        assert_eq!(to_field_names(["id", "_id"]), ["id", "field_id"]);
+        assert_eq!(to_field_names(["id", "ID", "Id", "id_2"]), ["id", "id_2", "id_3", "id_2_2"]);
+        assert_eq!(to_field_names(["user-name", "user_name", "userName"]), ["user_name", "user_name_2", "user_name_3"]);
+    }
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(to_snake_case("listUsers"), "list_users");
+        assert_eq!(to_snake_case("User Accounts"), "user_accounts");
+        assert_eq!(to_python_identifier("2fa"), "_2fa");
+        assert_eq!(to_python_identifier("import"), "import_");
+        assert_eq!(to_singular("line_items"), "line_item");
+        assert_eq!(to_singular("categories"), "category");
+        assert_eq!(to_singular("status"), "status");
+    }
+}
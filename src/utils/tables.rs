@@ -0,0 +1,493 @@
+use serde_json::{json, Value};
+use crate::utils::python::{is_identifier, typing_names};
+use crate::utils::spec::python_literal;
+use crate::utils::string::{to_camel_case, to_field_name, to_python_identifier, to_singular};
+
+/// Database-agnostic description of a table, filled by the SQLite introspection
+/// and the DDL parser and rendered as SQLAlchemy and Pydantic models.
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Column>,
+    pub foreign_keys: Vec<ForeignKey>,
+    pub indexes: Vec<Index>,
+}
+
+pub struct Column {
+    pub name: String,
+    /// Type as written in the database, e.g. "VARCHAR(255)".
+    pub data_type: String,
+    pub nullable: bool,
+    /// Server-side default as a SQL expression.
+    pub default: Option<String>,
+    pub primary_key: bool,
+    pub unique: bool,
+}
+
+pub struct ForeignKey {
+    pub columns: Vec<String>,
+    pub table: String,
+    pub references: Vec<String>,
+    pub on_delete: Option<String>,
+}
+
+pub struct Index {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+/// SQLAlchemy type expression and Python annotation for a column.
+pub struct ColumnType {
+    pub sqlalchemy: String,
+    pub python: String,
+    /// False when the type was guessed, so callers can report it.
+    pub known: bool,
+}
+
+fn arguments(data_type: &str) -> Vec<String> {
+    data_type
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(args, _)| args.split(',').map(|a| a.trim().to_string()).filter(|a| !a.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+pub fn column_type(data_type: &str) -> ColumnType {
+    let lower = data_type.trim().to_lowercase();
+    if let Some(element) = lower.strip_suffix("[]") {
+        let inner = column_type(element);
+        return ColumnType {
+            sqlalchemy: format!("ARRAY({})", inner.sqlalchemy),
+            python: format!("List[{}]", inner.python),
+            known: inner.known,
+        };
+    }
+    let base = lower.split('(').next().unwrap_or("").trim().to_string();
+    let args = arguments(&lower);
+    let length = args.first().filter(|a| a.chars().all(|c| c.is_ascii_digit())).cloned();
+    let (sqlalchemy, python) = match base.as_str() {
+        "int" | "integer" | "int4" | "mediumint" | "serial" | "serial4" => ("Integer".to_string(), "int"),
+        "bigint" | "int8" | "bigserial" | "serial8" => ("BigInteger".to_string(), "int"),
+        "smallint" | "int2" | "tinyint" | "smallserial" | "serial2" => ("SmallInteger".to_string(), "int"),
+        "varchar" | "character varying" | "nvarchar" | "varchar2" | "char" | "character" | "nchar" | "bpchar" => (
+            length.map_or("String".to_string(), |n| format!("String({})", n)),
+            "str",
+        ),
+        "text" | "clob" | "citext" | "mediumtext" | "longtext" | "tinytext" | "ntext" => ("Text".to_string(), "str"),
+        "bool" | "boolean" => ("Boolean".to_string(), "bool"),
+        "real" | "float" | "float4" | "float8" | "double" | "double precision" => ("Float".to_string(), "float"),
+        "numeric" | "decimal" | "money" => (
+            if args.is_empty() { "Numeric".to_string() } else { format!("Numeric({})", args.join(", ")) },
+            "Decimal",
+        ),
+        "date" => ("Date".to_string(), "date"),
+        "datetime" | "timestamp" | "timestamp without time zone" => ("DateTime".to_string(), "datetime"),
+        "timestamptz" | "timestamp with time zone" => ("DateTime(timezone=True)".to_string(), "datetime"),
+        "time" | "time without time zone" | "timetz" | "time with time zone" => ("Time".to_string(), "time"),
+        "interval" => ("Interval".to_string(), "timedelta"),
+        "uuid" => ("Uuid".to_string(), "UUID"),
+        "json" | "jsonb" => ("JSON".to_string(), "Any"),
+        "blob" | "bytea" | "binary" | "varbinary" | "longblob" => ("LargeBinary".to_string(), "bytes"),
+        _ => {
+            // SQLite type affinity rules, which also cover most vendor aliases.
+            let guessed = if base.contains("int") {
+                ("Integer".to_string(), "int")
+            } else if base.contains("char") || base.contains("clob") || base.contains("text") {
+                ("Text".to_string(), "str")
+            } else if base.contains("real") || base.contains("floa") || base.contains("doub") {
+                ("Float".to_string(), "float")
+            } else {
+                ("String".to_string(), "str")
+            };
+            return ColumnType {
+                sqlalchemy: guessed.0,
+                python: guessed.1.to_string(),
+                known: false,
+            };
+        }
+    };
+    ColumnType {
+        sqlalchemy,
+        python: python.to_string(),
+        known: true,
+    }
+}
+
+impl Table {
+    pub fn module(&self) -> String {
+        to_python_identifier(&self.name)
+    }
+
+    pub fn class(&self) -> String {
+        to_camel_case(&to_singular(&self.module()))
+    }
+
+    pub fn primary_key(&self) -> Vec<&Column> {
+        self.columns.iter().filter(|c| c.primary_key).collect()
+    }
+
+    /// Whether the table can be a mapped class: SQLAlchemy refuses to map one
+    /// without a primary key, so those are rendered as a plain `Table`.
+    pub fn is_mapped(&self) -> bool {
+        !self.primary_key().is_empty()
+    }
+
+    /// Module-level `Table` object rendered for a table that is not mapped.
+    pub fn table_object(&self) -> String {
+        format!("{}_table", self.module())
+    }
+
+    /// A single integer primary key, filled in by the database on insert.
+    pub fn generated_key(&self) -> Option<&Column> {
+        match self.primary_key().as_slice() {
+            [column] if column_type(&column.data_type).python == "int" => Some(column),
+            _ => None,
+        }
+    }
+
+    /// Sample values for the columns a `Create` schema requires, as a JSON object.
+    pub fn sample(&self) -> Value {
+        let generated = self.generated_key().map(|c| c.name.clone());
+        let mut object = serde_json::Map::new();
+        for column in &self.columns {
+            if Some(&column.name) == generated.as_ref() || column.nullable || column.default.is_some() {
+                continue;
+            }
+            let value = match column_type(&column.data_type).python.as_str() {
+                "int" => json!(1),
+                "float" | "Decimal" => json!(1.5),
+                "bool" => json!(true),
+                "date" => json!("2024-01-01"),
+                "datetime" => json!("2024-01-01T00:00:00"),
+                "time" => json!("12:00:00"),
+                "timedelta" => json!(60),
+                "UUID" => json!("00000000-0000-0000-0000-000000000001"),
+                "bytes" => json!("data"),
+                "Any" => json!({}),
+                python if python.starts_with("List[") => json!([]),
+                _ => json!(format!("{} 1", column.name)),
+            };
+            object.insert(attribute(&column.name), value);
+        }
+        Value::Object(object)
+    }
+}
+
+/// Python attribute for a column name.
+pub fn attribute(column: &str) -> String {
+    if is_identifier(column) && to_python_identifier(column) == column.to_lowercase() && !column.starts_with('_') {
+        column.to_string()
+    } else {
+        to_field_name(column)
+    }
+}
+
+fn quoted(text: &str) -> String {
+    python_literal(&Value::String(text.to_string()))
+}
+
+/// `Column` arguments after the name: type, foreign key, constraints and default.
+fn column_arguments(table: &Table, column: &Column) -> Vec<String> {
+    let mut arguments = vec![column_type(&column.data_type).sqlalchemy];
+    for foreign_key in table.foreign_keys.iter().filter(|f| f.columns == [column.name.clone()]) {
+        let target = format!("{}.{}", foreign_key.table, foreign_key.references.first().map_or("id", String::as_str));
+        let on_delete = foreign_key
+            .on_delete
+            .as_ref()
+            .map(|action| format!(", ondelete={}", quoted(action)))
+            .unwrap_or_default();
+        arguments.push(format!("ForeignKey({}{})", quoted(&target), on_delete));
+    }
+    if column.primary_key {
+        arguments.push("primary_key=True".to_string());
+    } else {
+        arguments.push(format!("nullable={}", if column.nullable { "True" } else { "False" }));
+    }
+    if column.unique && !column.primary_key {
+        arguments.push("unique=True".to_string());
+    }
+    if let Some(default) = &column.default {
+        arguments.push(format!("server_default=text({})", quoted(default)));
+    }
+    arguments
+}
+
+fn render_column(table: &Table, column: &Column) -> String {
+    let attribute = attribute(&column.name);
+    let mut arguments = column_arguments(table, column);
+    if attribute != column.name {
+        arguments.insert(0, quoted(&column.name));
+    }
+    format!("    {} = Column({})\n", attribute, arguments.join(", "))
+}
+
+fn schema_field(column: &Column, optional: bool) -> String {
+    let python = column_type(&column.data_type).python;
+    if optional && python != "Any" {
+        format!("    {}: Optional[{}] = None\n", attribute(&column.name), python)
+    } else if optional {
+        format!("    {}: Any = None\n", attribute(&column.name))
+    } else {
+        format!("    {}: {}\n", attribute(&column.name), python)
+    }
+}
+
+/// `Create`, `Update` and `Read` schemas of a table, `read` being the body `Read` adds.
+fn render_schemas(table: &Table, read: &str) -> String {
+    let class = table.class();
+    let generated = table.generated_key().map(|c| c.name.clone());
+    let writable: Vec<&Column> = table.columns.iter().filter(|c| Some(&c.name) != generated.as_ref()).collect();
+    let mut create = String::new();
+    for column in &writable {
+        create.push_str(&schema_field(column, column.nullable || column.default.is_some()));
+    }
+    if create.is_empty() {
+        create.push_str("    pass\n");
+    }
+    let mut update = String::new();
+    for column in writable.iter().filter(|c| !c.primary_key) {
+        update.push_str(&schema_field(column, true));
+    }
+    if update.is_empty() {
+        update.push_str("    pass\n");
+    }
+    format!(
+        "class {0}Create(BaseModel):\n{1}\n\nclass {0}Update(BaseModel):\n{2}\n\nclass {0}Read({0}Create):\n{3}",
+        class, create, update, read
+    )
+}
+
+/// Standard library and typing imports for the annotations in `code`.
+fn standard_imports(code: &str, annotations: &str) -> Vec<String> {
+    let word = |w: &str| code.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|x| x == w);
+    let mut standard = Vec::new();
+    let datetime: Vec<&str> = ["date", "datetime", "time", "timedelta"].into_iter().filter(|w| word(w)).collect();
+    if !datetime.is_empty() {
+        standard.push(format!("from datetime import {}", datetime.join(", ")));
+    }
+    if word("Decimal") {
+        standard.push("from decimal import Decimal".to_string());
+    }
+    let typing = typing_names(&[annotations]);
+    if !typing.is_empty() {
+        standard.push(format!("from typing import {}", typing.join(", ")));
+    }
+    if annotations.contains(": UUID") || annotations.contains("[UUID]") {
+        standard.push("from uuid import UUID".to_string());
+    }
+    standard
+}
+
+/// Renders a table as a module holding the SQLAlchemy model bound to
+/// `app.core.database.Base` and its `Create`, `Update` and `Read` schemas.
+/// A table without a primary key gets a `Table` on `Base.metadata` instead of the model.
+pub fn render_table(header: &str, table: &Table, tables: &[Table]) -> String {
+    let mut table_args = Vec::new();
+    for foreign_key in table.foreign_keys.iter().filter(|f| f.columns.len() > 1) {
+        let targets: Vec<String> = foreign_key
+            .references
+            .iter()
+            .map(|r| quoted(&format!("{}.{}", foreign_key.table, r)))
+            .collect();
+        let columns: Vec<String> = foreign_key.columns.iter().map(|c| quoted(c)).collect();
+        table_args.push(format!("ForeignKeyConstraint([{}], [{}])", columns.join(", "), targets.join(", ")));
+    }
+    for index in &table.indexes {
+        let columns: Vec<String> = index.columns.iter().map(|c| quoted(c)).collect();
+        if index.unique && index.columns.len() > 1 {
+            table_args.push(format!("UniqueConstraint({}, name={})", columns.join(", "), quoted(&index.name)));
+        } else if !(index.unique && index.columns.len() == 1) {
+            table_args.push(format!("Index({}, {})", quoted(&index.name), columns.join(", ")));
+        }
+    }
+
+    let mut body = String::new();
+    let mut relationships = String::new();
+    if table.is_mapped() {
+        body.push_str(&format!("class {}(Base):\n    __tablename__ = {}\n", table.class(), quoted(&table.name)));
+        if !table_args.is_empty() {
+            body.push_str("    __table_args__ = (\n");
+            for argument in &table_args {
+                body.push_str(&format!("        {},\n", argument));
+            }
+            body.push_str("    )\n");
+        }
+        body.push('\n');
+        for column in &table.columns {
+            body.push_str(&render_column(table, column));
+        }
+        relationships = render_relationships(table, tables);
+        if !relationships.is_empty() {
+            body.push('\n');
+            body.push_str(&relationships);
+        }
+    } else {
+        body.push_str(&format!("{} = Table(\n    {},\n    Base.metadata,\n", table.table_object(), quoted(&table.name)));
+        for column in &table.columns {
+            let mut arguments = column_arguments(table, column);
+            arguments.insert(0, quoted(&column.name));
+            body.push_str(&format!("    Column({}),\n", arguments.join(", ")));
+        }
+        for argument in &table_args {
+            body.push_str(&format!("    {},\n", argument));
+        }
+        body.push_str(")\n");
+    }
+
+    let mut read = String::from("    model_config = ConfigDict(from_attributes=True)\n");
+    if let Some(key) = table.generated_key() {
+        read.push('\n');
+        read.push_str(&schema_field(key, false));
+    }
+    let schemas = render_schemas(table, &read);
+    let code = format!("{}\n\n{}", body, schemas);
+    let standard = standard_imports(&code, &schemas);
+
+    let sqlalchemy: Vec<&str> = [
+        "ARRAY", "BigInteger", "Boolean", "Column", "Date", "DateTime", "Float", "ForeignKey", "ForeignKeyConstraint",
+        "Index", "Integer", "Interval", "JSON", "LargeBinary", "Numeric", "SmallInteger", "String", "Table", "Text",
+        "Time", "UniqueConstraint", "Uuid",
+    ]
+    .into_iter()
+    .filter(|name| body.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|w| w == *name))
+    .chain(body.contains("server_default=text(").then_some("text"))
+    .collect();
+
+    let mut imports = String::new();
+    if !standard.is_empty() {
+        imports.push_str(&format!("{}\n\n", standard.join("\n")));
+    }
+    imports.push_str("from pydantic import BaseModel, ConfigDict\n");
+    imports.push_str(&format!("from sqlalchemy import {}\n", sqlalchemy.join(", ")));
+    if !relationships.is_empty() {
+        imports.push_str("from sqlalchemy.orm import relationship\n");
+    }
+    imports.push_str("\nfrom app.core.database import Base\n");
+    format!("{}\n{}\n\n{}", header, imports, code)
+}
+
+/// Many-to-one relationships for single-column foreign keys to imported, mapped tables.
+fn render_relationships(table: &Table, tables: &[Table]) -> String {
+    let mut relationships = String::new();
+    for foreign_key in table.foreign_keys.iter().filter(|f| f.columns.len() == 1) {
+        let Some(target) = tables.iter().find(|t| t.name == foreign_key.table && t.is_mapped()) else {
+            continue;
+        };
+        let column = attribute(&foreign_key.columns[0]);
+        let name = match column.strip_suffix("_id") {
+            Some(stem) if !stem.is_empty() => stem.to_string(),
+            _ => to_singular(&target.module()),
+        };
+        if table.columns.iter().any(|c| attribute(&c.name) == name) || relationships.contains(&format!("    {} =", name)) {
+            continue;
+        }
+        let remote_side = match (target.name == table.name, table.primary_key().as_slice()) {
+            (true, [key]) => format!(", remote_side=[{}]", attribute(&key.name)),
+            _ => String::new(),
+        };
+        relationships.push_str(&format!(
+            "    {} = relationship({}, foreign_keys=[{}]{})\n",
+            name,
+            quoted(&target.class()),
+            column,
+            remote_side
+        ));
+    }
+    relationships
+}
+
+/// Renders a collection as a Beanie document with the same `Create`, `Update`
+/// and `Read` schemas as `render_table`; `Read` carries the ObjectId as a string.
+pub fn render_document(header: &str, table: &Table) -> String {
+    let mut body = format!("class {}(Document):\n", table.class());
+    for column in &table.columns {
+        body.push_str(&schema_field(column, column.nullable));
+    }
+    if !table.columns.is_empty() {
+        body.push('\n');
+    }
+    body.push_str(&format!("    class Settings:\n        name = {}\n", quoted(&table.name)));
+    let schemas = render_schemas(table, "    id: str\n");
+    let code = format!("{}\n\n{}", body, schemas);
+
+    let mut imports = String::new();
+    let standard = standard_imports(&code, &code);
+    if !standard.is_empty() {
+        imports.push_str(&format!("{}\n\n", standard.join("\n")));
+    }
+    imports.push_str("from beanie import Document\nfrom pydantic import BaseModel\n");
+    format!("{}\n{}\n\n{}", header, imports, code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str, primary_key: bool) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable: !primary_key,
+            default: None,
+            primary_key,
+            unique: false,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<Column>) -> Table {
+        Table { name: name.to_string(), columns, foreign_keys: Vec::new(), indexes: Vec::new() }
+    }
+
+    #[test]
+    fn table_with_primary_key_is_a_mapped_class() {
+        let users = table("users", vec![column("id", "INTEGER", true), column("email", "VARCHAR(255)", false)]);
+        let rendered = render_table("# Model: users", &users, &[]);
+        assert!(rendered.contains("class User(Base):\n    __tablename__ = \"users\"\n"), "{}", rendered);
+        assert!(rendered.contains("    id = Column(Integer, primary_key=True)\n"), "{}", rendered);
+        assert!(rendered.contains("from sqlalchemy import Column, Integer, String\n"), "{}", rendered);
+        assert!(rendered.contains("class UserRead(UserCreate):\n    model_config = ConfigDict(from_attributes=True)\n\n    id: int\n"), "{}", rendered);
+    }
+
+    #[test]
+    fn table_without_primary_key_is_a_plain_table() {
+        let mut events = table("audit_log", vec![column("at", "TIMESTAMP", false), column("Message", "TEXT", false)]);
+        events.columns[0].nullable = false;
+        events.indexes.push(Index { name: "ix_audit_log_at".into(), columns: vec!["at".into()], unique: false });
+        assert!(!events.is_mapped());
+
+        let rendered = render_table("# Model: audit_log", &events, &[]);
+        assert!(!rendered.contains("class AuditLog(Base)"), "{}", rendered);
+        assert!(
+            rendered.contains(
+                "audit_log_table = Table(\n    \"audit_log\",\n    Base.metadata,\n    Column(\"at\", DateTime, nullable=False),\n    Column(\"Message\", Text, nullable=True),\n    Index(\"ix_audit_log_at\", \"at\"),\n)\n"
+            ),
+            "{}",
+            rendered
+        );
+        assert!(rendered.contains("from sqlalchemy import Column, DateTime, Index, Table, Text\n"), "{}", rendered);
+        assert!(rendered.contains("class AuditLogCreate(BaseModel):\n    at: datetime\n    Message: Optional[str] = None\n"), "{}", rendered);
+    }
+
+    #[test]
+    fn no_relationship_to_a_table_without_primary_key() {
+        let tags = table("tags", vec![column("name", "TEXT", false)]);
+        let mut posts = table("posts", vec![column("id", "INTEGER", true), column("tag_id", "INTEGER", false)]);
+        posts.foreign_keys.push(ForeignKey {
+            columns: vec!["tag_id".into()],
+            table: "tags".into(),
+            references: vec!["name".into()],
+            on_delete: None,
+        });
+        let rendered = render_table("# Model: posts", &posts, &[tags]);
+        assert!(rendered.contains("tag_id = Column(Integer, ForeignKey(\"tags.name\"), nullable=True)"), "{}", rendered);
+        assert!(!rendered.contains("relationship("), "{}", rendered);
+    }
+
+    #[test]
+    fn sample_skips_the_generated_key_and_optional_columns() {
+        let mut users = table("users", vec![column("id", "INTEGER", true), column("email", "VARCHAR(255)", false), column("age", "INT", false)]);
+        users.columns[1].nullable = false;
+        assert_eq!(users.generated_key().map(|c| c.name.as_str()), Some("id"));
+        assert_eq!(users.sample(), json!({"email": "email 1"}));
+    }
+}
@@ -1,19 +1,31 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use crate::utils::python::MethodSpec;
 
-pub fn create_test_file(component_type: &str, name: &str, test_content: &str) {
-    fs::create_dir_all("tests").unwrap();
-    let test_dir = match component_type {
+pub fn test_dir(component_type: &str) -> &'static str {
+    match component_type {
         "route" => "tests/routes",
         "model" => "tests/models",
         "service" => "tests/services",
         "core" => "tests/core",
         "usecase" => "tests/usecases",
+        "port" => "tests/ports",
+        "adapter" => "tests/infrastructure",
+        "contract" => "tests/contracts",
         _ => "tests",
-    };
+    }
+}
+
+pub fn test_path(component_type: &str, name: &str) -> PathBuf {
+    Path::new(test_dir(component_type)).join(format!("test_{}.py", name))
+}
+
+pub fn create_test_file(component_type: &str, name: &str, test_content: &str) {
+    fs::create_dir_all("tests").unwrap();
+    let test_dir = test_dir(component_type);
 
     fs::create_dir_all(test_dir).unwrap();
-    let test_path = Path::new(test_dir).join(format!("test_{}.py", name));
+    let test_path = test_path(component_type, name);
     
     fs::write(&test_path, test_content)
         .unwrap_or_else(|_| panic!("❌ Failed to write test file for {}", name));
@@ -21,6 +33,45 @@ pub fn create_test_file(component_type: &str, name: &str, test_content: &str) {
     println!("🧪 Test created at '{}'", test_path.display());
 }
 
+/// Appends a `@pytest.fixture` block to `tests/conftest.py` unless a fixture with
+/// the same name is already defined there.
+pub fn add_fixture(root: &Path, name: &str, fixture: &str) {
+    let tests = root.join("tests");
+    if !tests.exists() {
+        return;
+    }
+    let conftest = tests.join("conftest.py");
+    let content = fs::read_to_string(&conftest).unwrap_or_default();
+    if content.contains(&format!("def {}(", name)) {
+        return;
+    }
+
+    let mut updated = if content.contains("import pytest") {
+        content.trim_end().to_string()
+    } else {
+        format!("import pytest\n{}", content).trim_end().to_string()
+    };
+    updated.push_str("\n\n\n");
+    updated.push_str(fixture.trim_end());
+    updated.push('\n');
+    fs::write(&conftest, updated).expect("❌ Failed to write conftest");
+    println!("🔗 Updated '{}'", conftest.strip_prefix(".").unwrap_or(&conftest).display());
+}
+
+pub fn remove_fixture(root: &Path, name: &str) {
+    let conftest = root.join("tests").join("conftest.py");
+    let Ok(content) = fs::read_to_string(&conftest) else {
+        return;
+    };
+    let signature = format!("def {}(", name);
+    let blocks: Vec<&str> = content.split("\n\n\n").collect();
+    let kept: Vec<&str> = blocks.iter().copied().filter(|b| !b.contains(&signature)).collect();
+    if kept.len() != blocks.len() {
+        fs::write(&conftest, format!("{}\n", kept.join("\n\n\n").trim_end())).expect("❌ Failed to write conftest");
+        println!("🔗 Updated '{}'", conftest.strip_prefix(".").unwrap_or(&conftest).display());
+    }
+}
+
 pub fn generate_model_test(name: &str) -> String {
     format!(
         r#"# 🧪 Test for model: {0}
@@ -37,6 +88,51 @@ def test_{0}_example():
     )
 }
 
+pub fn generate_document_test(name: &str, class: &str, collection: &str) -> String {
+    format!(
+        r#"# 🧪 Test for model: {0}
+import pytest
+from beanie import Document
+from app.models.{0} import {1}
+
+def test_{0}_is_a_document():
+    assert issubclass({1}, Document)
+    assert {1}.Settings.name == "{2}"
+"#,
+        name, class, collection
+    )
+}
+
+pub fn generate_model_sample_test(name: &str, class: &str, samples: &[String]) -> String {
+    let samples: Vec<String> = samples.iter().map(|s| format!("\"{}\"", s)).collect();
+    format!(
+        r#"# 🧪 Test for model: {0}
+import json
+from pathlib import Path
+
+import pytest
+from app.models.{0} import {1}
+
+SAMPLES = Path(__file__).parent / "samples"
+
+
+def load(sample):
+    payload = json.loads((SAMPLES / sample).read_text())
+    return payload if isinstance(payload, list) else [payload]
+
+
+@pytest.mark.parametrize("sample", [{2}])
+def test_{0}_parses_sample(sample):
+    for payload in load(sample):
+        model = {1}.model_validate(payload)
+        assert model.model_dump(by_alias=True, exclude_unset=True).keys() == payload.keys()
+"#,
+        name,
+        class,
+        samples.join(", ")
+    )
+}
+
 pub fn generate_service_test(name: &str) -> String {
     format!(
         r#"# 🧪 Test for service: {0}
@@ -97,37 +193,601 @@ def test_{0}_route_headers():
     )
 }
 
-pub fn generate_usecase_test(name: &str) -> String {
-    format!(
-        r#"# 🧪 Test for use case: {0}
+pub struct TestDependency {
+    pub param: String,
+    pub import: String,
+    pub mock_spec: String,
+    /// conftest fixture providing an in-memory fake, used instead of a `Mock`.
+    pub fixture: Option<String>,
+}
+
+/// `is_async` runs the coroutine `execute` in its own event loop.
+pub fn generate_usecase_test(name: &str, class: &str, dependencies: &[TestDependency], is_async: bool) -> String {
+    let mocked: Vec<&TestDependency> = dependencies.iter().filter(|d| d.fixture.is_none()).collect();
+    let mut content = format!("# 🧪 Test for use case: {}\n", name);
+    if is_async {
+        content.push_str("import asyncio\n\n");
+    }
+    content.push_str("import pytest\n");
+    if !mocked.is_empty() {
+        content.push_str("from unittest.mock import Mock\n");
+    }
+    content.push_str(&format!(
+        "from app.usecases.{0} import {1}, {1}Input, {1}Output\n",
+        name, class
+    ));
+    for dependency in &mocked {
+        content.push_str(&dependency.import);
+        content.push('\n');
+    }
+
+    for dependency in &mocked {
+        content.push_str(&format!(
+            r#"
+@pytest.fixture
+def {0}():
+    return Mock(spec={1})
+"#,
+            dependency.param, dependency.mock_spec
+        ));
+    }
+
+    let fixtures: Vec<&str> = dependencies
+        .iter()
+        .map(|d| d.fixture.as_deref().unwrap_or(&d.param))
+        .collect();
+    let kwargs: Vec<String> = dependencies
+        .iter()
+        .zip(&fixtures)
+        .map(|(d, fixture)| format!("{}={}", d.param, fixture))
+        .collect();
+    content.push_str(&format!(
+        r#"
+@pytest.fixture
+def usecase({0}):
+    return {1}({2})
+"#,
+        fixtures.join(", "),
+        class,
+        kwargs.join(", ")
+    ));
+
+    if !dependencies.is_empty() {
+        let assertions: String = dependencies
+            .iter()
+            .zip(&fixtures)
+            .map(|(d, fixture)| format!("    assert usecase.{} is {}\n", d.param, fixture))
+            .collect();
+        content.push_str(&format!(
+            r#"
+def test_{0}_initialization(usecase, {1}):
+{2}"#,
+            name,
+            fixtures.join(", "),
+            assertions
+        ));
+    }
+
+    content.push_str(&format!(
+        r#"
+def test_{0}_execution(usecase):
+    result = {2}
+    assert isinstance(result, {1}Output)
+"#,
+        name,
+        class,
+        usecase_call(class, is_async)
+    ));
+    content
+}
+
+/// `usecase.execute(...)` with an empty input, awaited when `is_async`.
+pub fn usecase_call(class: &str, is_async: bool) -> String {
+    let call = format!("usecase.execute({}Input())", class);
+    if is_async { format!("asyncio.run({})", call) } else { call }
+}
+
+pub fn sample_argument(annotation: Option<&str>) -> String {
+    let annotation = annotation.unwrap_or("").trim();
+    let base = annotation.split('[').next().unwrap_or("").trim().to_lowercase();
+    match base.as_str() {
+        "int" => "1".to_string(),
+        "float" => "1.0".to_string(),
+        "str" => "\"test\"".to_string(),
+        "bool" => "True".to_string(),
+        "list" | "sequence" | "iterable" => "[]".to_string(),
+        "dict" | "mapping" => "{}".to_string(),
+        "set" => "set()".to_string(),
+        "tuple" => "()".to_string(),
+        "bytes" => "b\"test\"".to_string(),
+        _ => "None".to_string(),
+    }
+}
+
+pub fn sample_call(method: &MethodSpec) -> String {
+    let args: Vec<String> = method
+        .params
+        .iter()
+        .filter(|p| p.default.is_none() && !p.name.starts_with('*'))
+        .map(|p| sample_argument(p.annotation.as_deref()))
+        .collect();
+    format!("{}({})", method.name, args.join(", "))
+}
+
+pub fn generate_port_test(name: &str, class: &str, methods: &[MethodSpec]) -> String {
+    let mut content = format!(
+        r#"# 🧪 Test for port: {0}
+import inspect
+from abc import ABC
 import pytest
-from unittest.mock import Mock
-from app.usecases.{0} import {0}
-from app.ports.example_port import ExamplePort
-
-class MockPort(ExamplePort):
-    def get_data(self) -> str:
-        return "test data"
-
-def test_{0}_initialization():
-    port = MockPort()
-    usecase = {0}(port)
-    assert isinstance(usecase.port, ExamplePort)
-
-def test_{0}_execution():
-    port = MockPort()
-    usecase = {0}(port)
-    result = usecase.execute()
-    assert result == "test data"
-
-def test_{0}_with_mock():
-    mock_port = Mock(spec=ExamplePort)
-    mock_port.get_data.return_value = "mocked data"
-    usecase = {0}(mock_port)
-    result = usecase.execute()
-    assert result == "mocked data"
-    mock_port.get_data.assert_called_once()
+from app.ports.{0} import {1}
+
+def test_{0}_is_a_port():
+    assert issubclass({1}, ABC)
 "#,
-        name
+        name, class
+    );
+
+    if !methods.is_empty() {
+        content.push_str(&format!(
+            r#"
+def test_{0}_cannot_be_instantiated():
+    assert inspect.isabstract({1})
+    with pytest.raises(TypeError):
+        {1}()
+"#,
+            name, class
+        ));
+    }
+
+    for method in methods {
+        content.push_str(&format!(
+            r#"
+def test_{0}_declares_{2}():
+    assert "{2}" in {1}.__abstractmethods__
+"#,
+            name, class, method.name
+        ));
+    }
+    content
+}
+
+pub fn generate_adapter_test(
+    name: &str,
+    class: &str,
+    port_module: &str,
+    port_class: &str,
+    methods: &[MethodSpec],
+) -> String {
+    let uses_asyncio = methods.iter().any(|m| m.is_async);
+    let mut content = format!(
+        r#"# 🧪 Test for adapter: {0}
+{4}import pytest
+from app.infrastructure.{0} import {1}
+from app.ports.{2} import {3}
+
+def test_{0}_implements_{2}():
+    adapter = {1}()
+    assert isinstance(adapter, {3})
+"#,
+        name,
+        class,
+        port_module,
+        port_class,
+        if uses_asyncio { "import asyncio\n" } else { "" }
+    );
+
+    for method in methods {
+        let call = if method.is_async {
+            format!("asyncio.run(adapter.{})", sample_call(method))
+        } else {
+            format!("adapter.{}", sample_call(method))
+        };
+        content.push_str(&format!(
+            r#"
+def test_{0}_{1}_is_stubbed():
+    adapter = {2}()
+    with pytest.raises(NotImplementedError):
+        {3}
+"#,
+            name, method.name, class, call
+        ));
+    }
+    content
+}
+
+const WRITE_VERBS: [&str; 7] = ["save", "add", "create", "insert", "put", "store", "upsert"];
+const READ_VERBS: [&str; 5] = ["get", "find", "fetch", "load", "read"];
+const DELETE_VERBS: [&str; 2] = ["delete", "remove"];
+const LIST_VERBS: [&str; 2] = ["list", "all"];
+
+fn verb(method: &MethodSpec) -> &str {
+    method.name.split('_').next().unwrap_or("")
+}
+
+fn required_params(method: &MethodSpec) -> Vec<&crate::utils::python::Param> {
+    method
+        .params
+        .iter()
+        .filter(|p| p.default.is_none() && !p.name.starts_with('*'))
+        .collect()
+}
+
+fn annotation_base(annotation: Option<&str>) -> String {
+    let annotation = annotation.unwrap_or("").trim();
+    let annotation = annotation.strip_prefix("Optional[").unwrap_or(annotation);
+    annotation.split(['[', ' ', '|']).next().unwrap_or("").trim().to_lowercase()
+}
+
+/// Contract suite shared by every adapter of a port. Repository-shaped ports
+/// (a write plus a keyed read) get round-trip, not-found, delete and list
+/// scenarios; every other method gets a failing stub to replace, so a suite
+/// nobody wrote cannot pass.
+pub fn generate_contract_test(port: &str, port_class: &str, methods: &[MethodSpec]) -> String {
+    let names: Vec<String> = methods.iter().map(|m| format!("\"{}\"", m.name)).collect();
+    let write = methods
+        .iter()
+        .find(|m| WRITE_VERBS.contains(&verb(m)) && required_params(m).len() == 1);
+    let read = methods.iter().find(|m| {
+        let returns = m.returns.as_deref().unwrap_or("").trim();
+        let returns_list = ["list", "List", "Sequence", "Iterable"].iter().any(|t| returns.starts_with(t));
+        READ_VERBS.contains(&verb(m)) && required_params(m).len() == 1 && !returns_list
+    });
+    let keyed = |verbs: &[&str]| methods.iter().find(|m| verbs.contains(&verb(m)) && required_params(m).len() == 1);
+    let (delete, list) = (keyed(&DELETE_VERBS), methods.iter().find(|m| LIST_VERBS.contains(&verb(m)) && required_params(m).is_empty()));
+
+    let mut covered: Vec<&MethodSpec> = Vec::new();
+    let mut scenarios = String::new();
+    let mut helpers = String::new();
+    let mut is_async = false;
+    if let (Some(write), Some(read)) = (write, read) {
+        covered.extend([write, read]);
+        covered.extend(delete);
+        covered.extend(list);
+        is_async = covered.iter().any(|m| m.is_async);
+        let fixtures = if is_async { "adapter, run, new_item" } else { "adapter, new_item" };
+        let call = |method: &MethodSpec, args: &str| {
+            let call = format!("adapter.{}({})", method.name, args);
+            if method.is_async { format!("run({})", call) } else { call }
+        };
+
+        let item_param = required_params(write)[0];
+        let item = match annotation_base(item_param.annotation.as_deref()).as_str() {
+            "" | "dict" | "mapping" | "any" => "    return {\"id\": 1}\n".to_string(),
+            _ => format!(
+                "    pytest.fail(\"Build a sample {} for {}, then remove this line\")\n",
+                item_param.annotation.as_deref().unwrap_or("item"),
+                write.name
+            ),
+        };
+        let key_param = required_params(read)[0];
+        let missing = match annotation_base(key_param.annotation.as_deref()).as_str() {
+            "int" => "987654321".to_string(),
+            "" | "str" | "any" => "\"missing-key\"".to_string(),
+            _ => format!(
+                "pytest.fail(\"Pick a {} no adapter knows about, then remove this line\")",
+                key_param.annotation.as_deref().unwrap_or("key")
+            ),
+        };
+        helpers.push_str(&format!(
+            r#"
+
+@pytest.fixture
+def new_item():
+{item}
+
+def _key(saved, item):
+    """Key to read the item back with: what the write returned, or the item's id."""
+    if isinstance(saved, (int, str)) and not isinstance(saved, bool):
+        return saved
+    value = item if saved is None or isinstance(saved, bool) else saved
+    if isinstance(value, dict):
+        return value.get("id")
+    return getattr(value, "id", None)
+"#
+        ));
+
+        scenarios.push_str(&format!(
+            r#"
+
+def test_{write}_then_{read}({fixtures}):
+    saved = {save}
+    found = {get_saved}
+    assert found is not None
+    if saved is not None and not isinstance(saved, (bool, int, str)):
+        assert found == saved
+
+
+def test_{read}_missing(adapter{run}):
+    assert not {get_missing}
+"#,
+            write = write.name,
+            read = read.name,
+            fixtures = fixtures,
+            save = call(write, "new_item"),
+            get_saved = call(read, "_key(saved, new_item)"),
+            run = if read.is_async { ", run" } else { "" },
+            get_missing = call(read, &missing),
+        ));
+        if let Some(delete) = delete {
+            scenarios.push_str(&format!(
+                r#"
+
+def test_{delete}_then_{read}({fixtures}):
+    key = _key({save}, new_item)
+    {remove}
+    assert not {get}
+"#,
+                delete = delete.name,
+                read = read.name,
+                fixtures = fixtures,
+                save = call(write, "new_item"),
+                remove = call(delete, "key"),
+                get = call(read, "key"),
+            ));
+        }
+        if let Some(list) = list {
+            scenarios.push_str(&format!(
+                r#"
+
+def test_{list}_includes_{write}({fixtures}):
+    saved = {save}
+    assert len({all}) >= 1
+    assert {found} is not None
+"#,
+                list = list.name,
+                write = write.name,
+                fixtures = fixtures,
+                save = call(write, "new_item"),
+                all = call(list, ""),
+                found = call(read, "_key(saved, new_item)"),
+            ));
+        }
+    }
+    for method in methods.iter().filter(|m| !covered.iter().any(|c| c.name == m.name)) {
+        scenarios.push_str(&format!(
+            r#"
+
+def test_{0}_contract(adapter):
+    pytest.fail("Write the behaviour every adapter must share for {0}")
+"#,
+            method.name
+        ));
+    }
+
+    let (asyncio, run) = if is_async {
+        (
+            "import asyncio\n",
+            r#"
+
+@pytest.fixture
+def run():
+    loop = asyncio.new_event_loop()
+    yield loop.run_until_complete
+    loop.close()
+"#,
+        )
+    } else {
+        ("", "")
+    };
+    format!(
+        r#"# 🤝 Contract tests for port: {0}
+# Every adapter listed in ADAPTERS must pass this suite.
+{3}import inspect
+import pytest
+from app.ports.{0} import {1}
+
+ADAPTERS = [
+]
+
+
+@pytest.fixture(params=ADAPTERS, ids=lambda adapter: adapter.__name__)
+def adapter(request):
+    return request.param()
+{4}{5}
+
+def test_implements_{0}(adapter):
+    assert isinstance(adapter, {1})
+
+
+@pytest.mark.parametrize("method", [{2}])
+def test_matches_{0}_signature(adapter, method):
+    expected = getattr({1}, method)
+    actual = getattr(type(adapter), method)
+    assert list(inspect.signature(actual).parameters) == list(inspect.signature(expected).parameters)
+    assert inspect.iscoroutinefunction(actual) == inspect.iscoroutinefunction(expected)
+{6}"#,
+        port,
+        port_class,
+        names.join(", "),
+        asyncio,
+        run,
+        helpers,
+        scenarios
     )
 }
+
+/// One request of a route test generated for a port-backed router; the
+/// collections and the answer are already rendered as Python literals.
+pub struct RouteTestCase {
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub params: Option<String>,
+    pub headers: Option<String>,
+    pub json: Option<String>,
+    /// Success status the route answers with, e.g. "201".
+    pub status: String,
+    /// Value the fake returns for the call, served back as the response body.
+    pub answer: Option<String>,
+}
+
+const ROUTE_TEST_HELPERS: &str = r#"
+
+def answer(fake, method, value):
+    """Makes the fake's `method` record the call as usual, then return `value`."""
+    original = getattr(fake, method)
+    if inspect.iscoroutinefunction(original):
+        async def call(*args, **kwargs):
+            await original(*args, **kwargs)
+            return value
+    else:
+        def call(*args, **kwargs):
+            original(*args, **kwargs)
+            return value
+    setattr(fake, method, call)
+    return value
+
+
+def served(route_name, value):
+    """`value` as FastAPI sends it back through the route's response model."""
+    route = next(r for r in app.routes if getattr(r, "name", None) == route_name)
+    if route.response_model is None:
+        return jsonable_encoder(value)
+    adapter = TypeAdapter(route.response_model)
+    return adapter.dump_python(adapter.validate_python(value), mode="json", by_alias=True)
+"#;
+
+/// Route test that serves the port from its fake and checks each operation
+/// answers a sample request with its success status and the value the fake
+/// returned, serialized by the route's response model.
+/// `overrides` are further `(port, class)` pairs the routes inject, served by
+/// their `fake_<port>` fixtures as well.
+pub fn generate_port_route_test(
+    name: &str,
+    port: &str,
+    port_class: &str,
+    cases: &[&RouteTestCase],
+    overrides: &[(&str, &str)],
+) -> String {
+    let ports: Vec<(&str, &str)> = [(port, port_class)].into_iter().chain(overrides.iter().copied()).collect();
+    let imports: String = ports.iter().map(|(p, c)| format!("from app.ports.{} import {}\n", p, c)).collect();
+    let fixtures: String = ports.iter().map(|(p, _)| format!("fake_{}, ", p)).collect();
+    let calls: String = ports.iter().map(|(p, c)| format!("    override_port({}, fake_{})\n", c, p)).collect();
+    let answers = cases.iter().any(|c| c.answer.is_some());
+    let mut content = format!(
+        r#"# 🧪 Test for route: {0}
+{1}import pytest
+{2}from fastapi.testclient import TestClient
+{3}from app.main import app
+{4}
+@pytest.fixture
+def client({5}override_port):
+{6}    return TestClient(app)
+{7}"#,
+        name,
+        if answers { "import inspect\n\n" } else { "" },
+        if answers { "from fastapi.encoders import jsonable_encoder\n" } else { "" },
+        if answers { "from pydantic import TypeAdapter\n" } else { "" },
+        imports,
+        fixtures,
+        calls,
+        if answers { ROUTE_TEST_HELPERS } else { "" }
+    );
+    for case in cases {
+        let mut arguments = vec![format!("\"{}\"", case.method), format!("\"{}\"", case.url)];
+        for (keyword, value) in [("params", &case.params), ("headers", &case.headers), ("json", &case.json)] {
+            if let Some(value) = value {
+                arguments.push(format!("{}={}", keyword, value));
+            }
+        }
+        // A 204 has no body to compare, the answer only lets the route succeed.
+        let (setup, body) = match &case.answer {
+            Some(value) if case.status != "204" => (
+                format!("    expected = answer(fake_{}, \"{}\", {})\n", port, case.name, value),
+                format!("    assert response.json() == served(\"{}\", expected)\n", case.name),
+            ),
+            Some(value) => (format!("    answer(fake_{}, \"{}\", {})\n", port, case.name, value), String::new()),
+            None => (String::new(), String::new()),
+        };
+        content.push_str(&format!(
+            r#"
+def test_{0}(client, fake_{1}):
+{2}    response = client.request({3})
+    assert response.status_code == {4}, response.text
+{5}    assert fake_{1}.calls[-1][0] == "{0}"
+"#,
+            case.name,
+            port,
+            setup,
+            arguments.join(", "),
+            case.status,
+            body
+        ));
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn methods(specs: &[&str]) -> Vec<MethodSpec> {
+        specs.iter().map(|s| MethodSpec::parse(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn repository_contract_has_behaviour_scenarios() {
+        let suite = generate_contract_test(
+            "user_repository",
+            "UserRepository",
+            &methods(&["save(user:dict)->dict", "get(id:int)->Optional[dict]", "delete(id:int)->bool", "list_all()->List[dict]"]),
+        );
+        assert!(suite.contains("def test_save_then_get(adapter, new_item):"));
+        assert!(suite.contains("    assert not adapter.get(987654321)\n"));
+        assert!(suite.contains("def test_delete_then_get("));
+        assert!(suite.contains("def test_list_all_includes_save("));
+        assert!(suite.contains("    return {\"id\": 1}\n"));
+        assert!(!suite.contains("pytest.fail"));
+        assert!(!suite.contains("import asyncio"));
+    }
+
+    #[test]
+    fn uncovered_methods_get_failing_stubs() {
+        let suite = generate_contract_test("gateway", "Gateway", &methods(&["charge(amount:int)->bool", "get(id:int)->dict"]));
+        assert!(suite.contains("def test_charge_contract(adapter):\n    pytest.fail("));
+        assert!(suite.contains("def test_get_contract(adapter):\n    pytest.fail("));
+        assert!(!suite.contains("new_item"));
+    }
+
+    #[test]
+    fn route_tests_check_status_and_served_body() {
+        let case = |name: &str, method: &str, status: &str, answer: Option<&str>| RouteTestCase {
+            name: name.to_string(),
+            method: method.to_string(),
+            url: "/users/1".to_string(),
+            params: None,
+            headers: None,
+            json: None,
+            status: status.to_string(),
+            answer: answer.map(str::to_string),
+        };
+        let (get, delete) = (case("get_user", "GET", "200", Some("{\"id\": 1}")), case("delete_user", "DELETE", "204", Some("True")));
+        let test = generate_port_route_test("users", "users_repository", "UsersRepository", &[&get, &delete], &[]);
+        assert!(test.contains("    return TestClient(app)\n"), "{}", test);
+        assert!(!test.contains("raise_server_exceptions"), "{}", test);
+        assert!(!test.contains("!= 422"), "{}", test);
+        assert!(test.contains(
+            "def test_get_user(client, fake_users_repository):\n    expected = answer(fake_users_repository, \"get_user\", {\"id\": 1})\n    response = client.request(\"GET\", \"/users/1\")\n    assert response.status_code == 200, response.text\n    assert response.json() == served(\"get_user\", expected)\n"
+        ), "{}", test);
+        assert!(test.contains("    answer(fake_users_repository, \"delete_user\", True)\n"), "{}", test);
+        assert!(test.contains("    assert response.status_code == 204, response.text\n    assert fake_users_repository.calls[-1][0] == \"delete_user\"\n"), "{}", test);
+        assert!(test.contains("def served(route_name, value):"), "{}", test);
+
+        let ping = case("ping", "GET", "200", None);
+        let test = generate_port_route_test("health", "health_port", "HealthPort", &[&ping], &[]);
+        assert!(!test.contains("def answer(") && !test.contains("import inspect"), "{}", test);
+    }
+
+    #[test]
+    fn async_ports_run_in_one_event_loop() {
+        let suite = generate_contract_test(
+            "product_repository",
+            "ProductRepository",
+            &methods(&["async create_product(data:ProductCreate)->ProductRead", "async get_product(id:int)->Optional[ProductRead]"]),
+        );
+        assert!(suite.contains("import asyncio\n"));
+        assert!(suite.contains("    saved = run(adapter.create_product(new_item))\n"));
+        assert!(suite.contains("pytest.fail(\"Build a sample ProductCreate for create_product, then remove this line\")"));
+    }
+}
@@ -0,0 +1,427 @@
+use std::collections::BTreeMap;
+use serde_json::Value;
+use crate::utils::spec::{ref_name, Spec, HTTP_METHODS};
+use crate::utils::string::{to_camel_case, to_snake_case};
+
+pub const GENERATED_HEADER: &str = "// Generated by faspi from the app routes. Do not edit, run `faspi client ts` instead.";
+
+const RESERVED: [&str; 44] = [
+    "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do", "else",
+    "enum", "export", "extends", "false", "finally", "for", "function", "if", "implements", "import", "in",
+    "instanceof", "interface", "let", "new", "null", "package", "private", "protected", "public", "return",
+    "static", "super", "switch", "this", "throw", "true", "try", "typeof", "var", "void", "while",
+];
+
+/// "pet_id" -> "petId", avoiding reserved words.
+pub fn to_lower_camel_case(name: &str) -> String {
+    let camel = to_camel_case(&to_snake_case(name));
+    let mut chars = camel.chars();
+    let lower = match chars.next() {
+        Some(first) => format!("{}{}", first.to_lowercase(), chars.as_str()),
+        None => "value".to_string(),
+    };
+    if RESERVED.contains(&lower.as_str()) { format!("{}_", lower) } else { lower }
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Property key, quoted when it is not a plain identifier.
+fn property_key(name: &str) -> String {
+    if is_identifier(name) { name.to_string() } else { serde_json::to_string(name).unwrap_or_default() }
+}
+
+fn literal(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn union(mut types: Vec<String>) -> String {
+    types.dedup();
+    if types.iter().any(|t| t == "unknown") {
+        return "unknown".to_string();
+    }
+    match types.len() {
+        0 => "unknown".to_string(),
+        _ => types.join(" | "),
+    }
+}
+
+fn array_of(item: String) -> String {
+    if item.contains(' ') { format!("Array<{}>", item) } else { format!("{}[]", item) }
+}
+
+/// TypeScript type for a JSON schema, referring to components by name.
+pub fn ts_type(schema: &Value) -> String {
+    if let Some(name) = ref_name(schema) {
+        return name.to_string();
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            return union(variants.iter().map(ts_type).collect());
+        }
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        return parts.iter().map(ts_type).collect::<Vec<_>>().join(" & ");
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(literal).collect());
+    }
+    if let Some(value) = schema.get("const") {
+        return literal(value);
+    }
+    let kinds: Vec<&str> = match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        Some(Value::String(kind)) => vec![kind.as_str()],
+        _ if schema.get("properties").is_some() => vec!["object"],
+        _ => vec![],
+    };
+    if kinds.is_empty() {
+        return "unknown".to_string();
+    }
+    union(kinds.into_iter().map(|kind| single_type(kind, schema)).collect())
+}
+
+fn single_type(kind: &str, schema: &Value) -> String {
+    match kind {
+        "string" if schema.get("format").and_then(Value::as_str) == Some("binary") => "Blob".to_string(),
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => array_of(schema.get("items").map_or("unknown".to_string(), ts_type)),
+        "object" => match schema.get("properties").and_then(Value::as_object) {
+            Some(properties) if !properties.is_empty() => {
+                let required = required_fields(schema);
+                let fields: Vec<String> = properties
+                    .iter()
+                    .map(|(name, property)| {
+                        let optional = if required.contains(&name.as_str()) { "" } else { "?" };
+                        format!("{}{}: {}", property_key(name), optional, ts_type(property))
+                    })
+                    .collect();
+                format!("{{ {} }}", fields.join("; "))
+            }
+            _ => match schema.get("additionalProperties") {
+                Some(values @ Value::Object(map)) if !map.is_empty() => format!("Record<string, {}>", ts_type(values)),
+                _ => "Record<string, unknown>".to_string(),
+            },
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+fn required_fields(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn doc_comment(schema: &Value, indent: &str) -> String {
+    match schema.get("description").and_then(Value::as_str).map(str::trim).filter(|d| !d.is_empty()) {
+        Some(description) => format!("{}/** {} */\n", indent, description.replace("*/", "* /").replace('\n', " ")),
+        None => String::new(),
+    }
+}
+
+/// Renders the component schemas as interfaces and type aliases.
+pub fn render_types(spec: &Spec) -> String {
+    let mut content = format!("{}\n", GENERATED_HEADER);
+    for (name, schema) in spec.schemas() {
+        content.push('\n');
+        content.push_str(&doc_comment(schema, ""));
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let is_object = schema.get("type").and_then(Value::as_str).is_none_or(|t| t == "object") && properties.is_some();
+        if !is_object {
+            content.push_str(&format!("export type {} = {};\n", name, ts_type(schema)));
+            continue;
+        }
+        let required = required_fields(schema);
+        content.push_str(&format!("export interface {} {{\n", name));
+        for (field, property) in properties.into_iter().flatten() {
+            content.push_str(&doc_comment(property, "  "));
+            let optional = if required.contains(&field.as_str()) { "" } else { "?" };
+            content.push_str(&format!("  {}{}: {};\n", property_key(field), optional, ts_type(property)));
+        }
+        content.push_str("}\n");
+    }
+    content
+}
+
+struct Argument {
+    name: String,
+    wire: String,
+    location: String,
+    required: bool,
+    ts: String,
+}
+
+const CLIENT_RUNTIME: &str = r#"export interface ClientOptions {
+  /** Prefix of every request, e.g. "https://api.example.com". */
+  baseUrl?: string;
+  /** Bearer token sent to the routes that require authentication. */
+  token?: string | (() => string | undefined | Promise<string | undefined>);
+  /** Headers added to every request. */
+  headers?: Record<string, string>;
+  fetch?: typeof fetch;
+}
+
+export class ApiError extends Error {
+  readonly status: number;
+  readonly body: unknown;
+
+  constructor(status: number, body: unknown) {
+    super(`Request failed with status ${status}`);
+    this.name = "ApiError";
+    this.status = status;
+    this.body = body;
+  }
+}
+
+interface RequestOptions {
+  query?: Record<string, unknown>;
+  headers?: Record<string, unknown>;
+  body?: unknown;
+  form?: Record<string, unknown>;
+  auth?: boolean;
+}
+
+export class ApiClient {
+  private readonly options: ClientOptions;
+
+  constructor(options: ClientOptions = {}) {
+    this.options = options;
+  }
+
+  private async request<T>(method: string, path: string, init: RequestOptions = {}): Promise<T> {
+    const search = new URLSearchParams();
+    for (const [key, value] of Object.entries(init.query ?? {})) {
+      if (value === undefined || value === null) continue;
+      for (const item of Array.isArray(value) ? value : [value]) search.append(key, String(item));
+    }
+    const headers: Record<string, string> = { ...this.options.headers };
+    for (const [key, value] of Object.entries(init.headers ?? {})) {
+      if (value !== undefined && value !== null) headers[key] = String(value);
+    }
+    if (init.auth && this.options.token) {
+      const token = typeof this.options.token === "function" ? await this.options.token() : this.options.token;
+      if (token) headers["Authorization"] = `Bearer ${token}`;
+    }
+    let body: string | undefined;
+    if (init.form !== undefined) {
+      const form = new URLSearchParams();
+      for (const [key, value] of Object.entries(init.form)) {
+        if (value !== undefined && value !== null) form.append(key, String(value));
+      }
+      headers["Content-Type"] = "application/x-www-form-urlencoded";
+      body = form.toString();
+    } else if (init.body !== undefined) {
+      headers["Content-Type"] = "application/json";
+      body = JSON.stringify(init.body);
+    }
+    const query = search.toString();
+    const url = `${(this.options.baseUrl ?? "").replace(/\/$/, "")}${path}${query ? `?${query}` : ""}`;
+    const response = await (this.options.fetch ?? fetch)(url, { method, headers, body });
+    const text = await response.text();
+    let data: unknown = text;
+    if (text && (response.headers.get("content-type") ?? "").includes("json")) data = JSON.parse(text);
+    if (!response.ok) throw new ApiError(response.status, data);
+    return (text ? data : undefined) as T;
+  }
+"#;
+
+/// Renders a fetch-based client with one typed method per operation;
+/// `names` maps operation ids to the preferred method name.
+pub fn render_client(spec: &Spec, names: &BTreeMap<String, String>) -> String {
+    let mut operations = spec.operations();
+    operations.sort_by_key(|o| (o.path.to_string(), HTTP_METHODS.iter().position(|m| *m == o.method)));
+
+    let mut methods = Vec::new();
+    let mut taken: Vec<String> = Vec::new();
+    for operation in &operations {
+        let operation_id = operation.operation.get("operationId").and_then(Value::as_str).unwrap_or_default();
+        let base = names.get(operation_id).cloned().unwrap_or_else(|| {
+            let fallback = format!("{} {}", operation.method, operation.path.replace(['{', '}', '/'], " "));
+            to_lower_camel_case(if operation_id.is_empty() { &fallback } else { operation_id })
+        });
+        let mut name = base.clone();
+        let mut counter = 2;
+        while taken.contains(&name) {
+            name = format!("{}{}", base, counter);
+            counter += 1;
+        }
+        taken.push(name.clone());
+
+        let arguments: Vec<Argument> = operation
+            .parameters
+            .iter()
+            .filter_map(|parameter| {
+                let wire = parameter.get("name").and_then(Value::as_str)?;
+                let location = parameter.get("in").and_then(Value::as_str).unwrap_or("query");
+                Some(Argument {
+                    name: to_lower_camel_case(wire),
+                    wire: wire.to_string(),
+                    location: location.to_string(),
+                    required: location == "path" || parameter.get("required").and_then(Value::as_bool).unwrap_or(false),
+                    ts: ts_type(parameter.get("schema").unwrap_or(&Value::Null)),
+                })
+            })
+            .collect();
+
+        let mut signature = Vec::new();
+        let mut url = operation.path.to_string();
+        for argument in arguments.iter().filter(|a| a.location == "path") {
+            signature.push(format!("{}: {}", argument.name, argument.ts));
+            url = url.replace(
+                &format!("{{{}}}", argument.wire),
+                &format!("${{encodeURIComponent(String({}))}}", argument.name),
+            );
+        }
+        let mut init = Vec::new();
+        if let Some((media, schema, required)) = spec.request_body(operation.operation) {
+            signature.push(format!("body{}: {}", if required { "" } else { "?" }, ts_type(schema)));
+            init.push(if media.contains("form") { "form: body".to_string() } else { "body".to_string() });
+        }
+        let extra: Vec<&Argument> = arguments.iter().filter(|a| a.location == "query" || a.location == "header").collect();
+        if !extra.is_empty() {
+            let fields: Vec<String> = extra
+                .iter()
+                .map(|a| format!("{}{}: {}", property_key(&a.wire), if a.required { "" } else { "?" }, a.ts))
+                .collect();
+            let default = if extra.iter().any(|a| a.required) { "" } else { " = {}" };
+            signature.push(format!("params: {{ {} }}{}", fields.join("; "), default));
+            for location in ["query", "header"] {
+                let entries: Vec<String> = extra
+                    .iter()
+                    .filter(|a| a.location == location)
+                    .map(|a| {
+                        let access = if is_identifier(&a.wire) { format!("params.{}", a.wire) } else { format!("params[{}]", literal(&Value::String(a.wire.clone()))) };
+                        format!("{}: {}", property_key(&a.wire), access)
+                    })
+                    .collect();
+                if !entries.is_empty() {
+                    init.push(format!("{}: {{ {} }}", if location == "query" { "query" } else { "headers" }, entries.join(", ")));
+                }
+            }
+        }
+        if operation.operation.get("security").is_some() {
+            init.push("auth: true".to_string());
+        }
+
+        let returns = match spec.success_response(operation.operation) {
+            Some((status, Some(schema))) if status != "204" => ts_type(schema),
+            _ => "void".to_string(),
+        };
+        let init = if init.is_empty() { String::new() } else { format!(", {{ {} }}", init.join(", ")) };
+        let summary = operation
+            .operation
+            .get("summary")
+            .and_then(Value::as_str)
+            .map(|s| format!("  /** {} — `{} {}` */\n", s, operation.method.to_uppercase(), operation.path))
+            .unwrap_or_default();
+        methods.push(format!(
+            "{}  {}({}): Promise<{}> {{\n    return this.request<{}>({}, `{}`{});\n  }}\n",
+            summary,
+            name,
+            signature.join(", "),
+            returns,
+            returns,
+            literal(&Value::String(operation.method.to_uppercase())),
+            url,
+            init
+        ));
+    }
+
+    let components: Vec<&str> = spec.schemas().into_iter().map(|(name, _)| name).collect();
+    let body = methods.join("\n");
+    let used: Vec<&str> = components
+        .into_iter()
+        .filter(|name| body.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|word| word == *name))
+        .collect();
+    let imports = if used.is_empty() {
+        String::new()
+    } else {
+        format!("import type {{ {} }} from \"./types\";\n\n", used.join(", "))
+    };
+    format!("{}\n{}{}\n{}}}\n", GENERATED_HEADER, imports, CLIENT_RUNTIME, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(paths: Value) -> Spec {
+        Spec::parse(
+            &json!({
+                "openapi": "3.1.0",
+                "paths": paths,
+                "components": {"schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "description": "A pet",
+                        "properties": {"id": {"type": "integer"}, "pet-name": {"type": ["string", "null"]}},
+                        "required": ["id"],
+                    },
+                    "Kind": {"enum": ["cat", "dog"]},
+                }},
+            })
+            .to_string(),
+            "spec.json",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn schemas_map_to_typescript_types() {
+        assert_eq!(ts_type(&json!({"$ref": "#/components/schemas/Pet"})), "Pet");
+        assert_eq!(ts_type(&json!({"anyOf": [{"type": "integer"}, {"type": "number"}, {}]})), "unknown");
+        assert_eq!(ts_type(&json!({"type": "array", "items": {"type": ["string", "null"]}})), "Array<string | null>");
+        assert_eq!(ts_type(&json!({"type": "string", "format": "binary"})), "Blob");
+        assert_eq!(ts_type(&json!({"type": "object", "additionalProperties": {"type": "boolean"}})), "Record<string, boolean>");
+        assert_eq!(ts_type(&json!({"properties": {"a b": {"const": 1}}, "required": ["a b"]})), "{ \"a b\": 1 }");
+        assert_eq!(to_lower_camel_case("pet_id"), "petId");
+        assert_eq!(to_lower_camel_case("Delete"), "delete_");
+    }
+
+    #[test]
+    fn components_render_as_interfaces_and_aliases() {
+        let types = render_types(&spec(json!({})));
+        assert!(types.contains("/** A pet */\nexport interface Pet {\n  id: number;\n  \"pet-name\"?: string | null;\n}\n"), "{}", types);
+        assert!(types.contains("export type Kind = \"cat\" | \"dog\";\n"), "{}", types);
+    }
+
+    #[test]
+    fn operations_become_typed_client_methods() {
+        let spec = spec(json!({
+            "/pets/{pet_id}": {"get": {
+                "operationId": "read_pet",
+                "security": [{"bearer": []}],
+                "parameters": [
+                    {"name": "pet_id", "in": "path", "schema": {"type": "integer"}},
+                    {"name": "X-Trace", "in": "header", "schema": {"type": "string"}},
+                ],
+                "responses": {"200": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}}},
+            }},
+            "/pets": {"post": {
+                "operationId": "read_pet",
+                "requestBody": {"required": true, "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}},
+                "responses": {"204": {}},
+            }},
+        }));
+        let names = BTreeMap::from([("read_pet".to_string(), "getPet".to_string())]);
+        let client = render_client(&spec, &names);
+        assert!(client.contains("import type { Pet } from \"./types\";"), "{}", client);
+        assert!(client.contains("  getPet(body: Pet): Promise<void> {\n    return this.request<void>(\"POST\", `/pets`, { body });"), "{}", client);
+        assert!(
+            client.contains(
+                "  getPet2(petId: number, params: { \"X-Trace\"?: string } = {}): Promise<Pet> {\n    return this.request<Pet>(\"GET\", `/pets/${encodeURIComponent(String(petId))}`, { headers: { \"X-Trace\": params[\"X-Trace\"] }, auth: true });"
+            ),
+            "{}",
+            client
+        );
+    }
+}